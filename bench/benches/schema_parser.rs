@@ -0,0 +1,28 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// 64 flat fields: `uint256 field0,uint256 field1,...`.
+fn flat_schema() -> String {
+    (0..64).map(|i| format!("uint256 field{}", i)).collect::<Vec<_>>().join(",")
+}
+
+/// Tuple arrays nested 8 deep: `(address a7,(address a6,...,bool b6)[] t6,bool b7)[] t7,string s7`.
+fn nested_schema() -> String {
+    (0..8).fold("uint256 leaf".to_string(), |inner, i| format!("(address a{i},{inner},bool b{i})[] t{i},string s{i}"))
+}
+
+/// 16 tuple array fields of 3 levels each, e.g. attestations bundling references and their links.
+fn tuple_heavy_schema() -> String {
+    (0..16)
+        .map(|i| format!("tuple(address who,(uint64 at,(bytes32 ref,string uri)[] links)[] refs,bool ok)[{}] t{}", i + 1, i))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn parse_schema(c: &mut Criterion) {
+    for (name, schema) in [("flat", flat_schema()), ("nested", nested_schema()), ("tuple_heavy", tuple_heavy_schema())] {
+        c.bench_function(&format!("parse_schema/{}", name), |b| b.iter(|| substreams::parse_schema(black_box(&schema))));
+    }
+}
+
+criterion_group!(benches, parse_schema);
+criterion_main!(benches);
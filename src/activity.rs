@@ -0,0 +1,491 @@
+use crate::expirations::LAST_BLOCK_TIME_KEY;
+use crate::params::Params;
+use crate::pb::contract::v1 as contract;
+use contract::AddressRole;
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+use substreams::pb::substreams::store_delta::Operation;
+use substreams::pb::substreams::Clock;
+use substreams::store::{
+    DeltaInt64, DeltaProto, Deltas, StoreAdd, StoreAddInt64, StoreAppend, StoreDelete, StoreGet, StoreGetArray, StoreGetInt64, StoreGetProto, StoreSet,
+    StoreSetIfNotExists, StoreSetIfNotExistsInt64, StoreSetIfNotExistsProto, StoreSetInt64, StoreSetProto,
+};
+use substreams::Hex;
+
+/// Store key of an address acting in a given role: `<role>:<address>`.
+pub fn activity_key(role: AddressRole, address: &[u8]) -> String {
+    let role = match role {
+        AddressRole::Attester => "attester",
+        AddressRole::Recipient => "recipient",
+        AddressRole::Unspecified => "unspecified",
+    };
+    format!("{}:{}", role, Hex(address))
+}
+
+/// Returns every `(ordinal, block_number, role, address)` touched by the attestations of the block.
+fn touched_addresses(events: &contract::Events) -> impl Iterator<Item = (u64, u64, AddressRole, &Vec<u8>)> {
+    events.eas_attesteds.iter().flat_map(|attested| {
+        [
+            (attested.evt_index as u64, attested.evt_block_number, AddressRole::Attester, &attested.attester),
+            (
+                attested.evt_index as u64,
+                attested.evt_block_number,
+                AddressRole::Recipient,
+                &attested.recipient,
+            ),
+        ]
+    })
+}
+
+/// Records the block number at which each attester and recipient was first seen.
+#[substreams::handlers::store]
+fn store_address_first_seen(events: contract::Events, store: StoreSetIfNotExistsInt64) {
+    for (ordinal, block_number, role, address) in touched_addresses(&events) {
+        store.set_if_not_exists(ordinal, activity_key(role, address), &(block_number as i64));
+    }
+}
+
+/// `(ordinal, key, activity)` writes of the attesters and recipients of the block, the first seen block read through
+/// `first_seen`.
+fn address_activity_writes(events: &contract::Events, first_seen: impl Fn(&str) -> Option<i64>) -> Vec<(u64, String, contract::AddressActivity)> {
+    let mut activities = BTreeMap::new();
+    for (ordinal, block_number, role, address) in touched_addresses(events) {
+        activities.insert(activity_key(role, address), (ordinal, block_number, role, address));
+    }
+    // one write per address at its last ordinal in the block, in ordinal order so that deltas roll back in order
+    let mut activities: Vec<_> = activities.into_iter().collect();
+    activities.sort_by_key(|(_, (ordinal, _, _, _))| *ordinal);
+
+    activities
+        .into_iter()
+        .map(|(key, (ordinal, block_number, role, address))| {
+            let first_seen_block_number = first_seen(&key).map(|block| block as u64).unwrap_or(block_number);
+            let activity = contract::AddressActivity {
+                role: role as i32,
+                address: address.clone(),
+                first_seen_block_number,
+                last_seen_block_number: block_number,
+            };
+            (ordinal, key, activity)
+        })
+        .collect()
+}
+
+/// Keeps the first and last seen block of each attester and recipient, written once per address and block.
+#[substreams::handlers::store]
+fn store_address_activity(events: contract::Events, first_seen: StoreGetInt64, store: StoreSetProto<contract::AddressActivity>) {
+    for (ordinal, key, activity) in address_activity_writes(&events, |key| first_seen.get_last(key)) {
+        store.set(ordinal, key, &activity);
+    }
+}
+
+/// Emits the updated first/last seen activity of every attester and recipient active in the block.
+#[substreams::handlers::map]
+fn map_address_activity(deltas: Deltas<DeltaProto<contract::AddressActivity>>) -> Result<contract::AddressActivities, substreams::errors::Error> {
+    Ok(contract::AddressActivities {
+        address_activities: deltas
+            .deltas
+            .into_iter()
+            .filter(|delta| delta.operation != Operation::Delete)
+            .map(|delta| delta.new_value)
+            .collect(),
+    })
+}
+
+/// Records the first attester of each schema, keyed by the hex-encoded schema UID.
+#[substreams::handlers::store]
+fn store_schema_first_attester(events: contract::Events, store: StoreSetIfNotExistsProto<contract::SchemaAttester>) {
+    for attested in events.eas_attesteds {
+        store.set_if_not_exists(
+            attested.evt_index as u64,
+            Hex(&attested.schema_id).to_string(),
+            &contract::SchemaAttester {
+                schema_id: attested.schema_id.clone(),
+                first_attester: attested.attester.clone(),
+                first_attested_block_number: attested.evt_block_number,
+                latest_attester: attested.attester,
+                latest_attested_block_number: attested.evt_block_number,
+            },
+        );
+    }
+}
+
+/// `(ordinal, key, attester)` writes of the schemas attested in the block, the first attester read through
+/// `first_attester`.
+fn schema_attester_writes(
+    events: &contract::Events,
+    first_attester: impl Fn(&str) -> Option<contract::SchemaAttester>,
+) -> Vec<(u64, String, contract::SchemaAttester)> {
+    let mut latest = BTreeMap::new();
+    for attested in &events.eas_attesteds {
+        latest.insert(Hex(&attested.schema_id).to_string(), attested);
+    }
+    // one write per schema at its last ordinal in the block, in ordinal order so that deltas roll back in order
+    let mut latest: Vec<_> = latest.into_iter().collect();
+    latest.sort_by_key(|(_, attested)| attested.evt_index);
+
+    latest
+        .into_iter()
+        .filter_map(|(key, attested)| {
+            let first = first_attester(&key)?;
+            let attester = contract::SchemaAttester {
+                latest_attester: attested.attester.clone(),
+                latest_attested_block_number: attested.evt_block_number,
+                ..first
+            };
+            Some((attested.evt_index as u64, key, attester))
+        })
+        .collect()
+}
+
+/// Keeps the first and latest attester of each schema, written once per schema and block.
+#[substreams::handlers::store]
+fn store_schema_attesters(events: contract::Events, first_attester: StoreGetProto<contract::SchemaAttester>, store: StoreSetProto<contract::SchemaAttester>) {
+    for (ordinal, key, attester) in schema_attester_writes(&events, |key| first_attester.get_last(key)) {
+        store.set(ordinal, key, &attester);
+    }
+}
+
+/// Emits the first attestation of every schema attested for the first time in the block, i.e. the attestations
+/// creating an entry of `store_schema_first_attester`, as a lightweight signal for ecosystem monitoring.
+#[substreams::handlers::map]
+fn map_first_attestations(
+    events: contract::Events,
+    deltas: Deltas<DeltaProto<contract::SchemaAttester>>,
+) -> Result<contract::FirstAttestations, substreams::errors::Error> {
+    let first_attestations = deltas
+        .deltas
+        .iter()
+        .filter(|delta| delta.operation == Operation::Create)
+        .filter_map(|delta| {
+            let attested = events.eas_attesteds.iter().find(|attested| attested.evt_index as u64 == delta.ordinal)?;
+
+            Some(contract::FirstAttestation {
+                evt_tx_hash: attested.evt_tx_hash.clone(),
+                evt_index: attested.evt_index,
+                evt_block_time: attested.evt_block_time,
+                evt_block_timestamp_seconds: attested.evt_block_timestamp_seconds,
+                evt_block_number: attested.evt_block_number,
+                schema_id: attested.schema_id.clone(),
+                uid: attested.uid.clone(),
+                attester: attested.attester.clone(),
+                recipient: attested.recipient.clone(),
+                schema: attested.schema.clone(),
+            })
+        })
+        .collect();
+
+    Ok(contract::FirstAttestations { first_attestations })
+}
+
+/// Emits the updated first/latest attester of every schema attested in the block.
+#[substreams::handlers::map]
+fn map_schema_attesters(deltas: Deltas<DeltaProto<contract::SchemaAttester>>) -> Result<contract::SchemaAttesters, substreams::errors::Error> {
+    Ok(contract::SchemaAttesters {
+        schema_attesters: deltas
+            .deltas
+            .into_iter()
+            .filter(|delta| delta.operation != Operation::Delete)
+            .map(|delta| delta.new_value)
+            .collect(),
+    })
+}
+
+/// Number of days of the rolling window of `map_active_attesters`, the current day included.
+const ACTIVE_WINDOW_DAYS: i64 = 7;
+
+/// Day of a timestamp, in days since the unix epoch (UTC).
+fn day(timestamp_seconds: u64) -> i64 {
+    (timestamp_seconds / 86_400) as i64
+}
+
+/// Store key of a day, zero-padded so that no key prefixes another one.
+fn day_key(day: i64) -> String {
+    format!("{:08}", day)
+}
+
+/// Keeps the last day each attester attested on, keyed by the hex-encoded attester, written once per attester and
+/// block.
+#[substreams::handlers::store]
+fn store_attester_last_active_day(events: contract::Events, store: StoreSetInt64) {
+    let mut latest = BTreeMap::new();
+    for attested in &events.eas_attesteds {
+        latest.insert(Hex(&attested.attester).to_string(), attested);
+    }
+    // in ordinal order so that deltas roll back in order
+    let mut latest: Vec<_> = latest.into_iter().collect();
+    latest.sort_by_key(|(_, attested)| attested.evt_index);
+
+    for (key, attested) in latest {
+        store.set(attested.evt_index as u64, key, &day(attested.evt_block_timestamp_seconds));
+    }
+}
+
+/// Days leaving the rolling window with the days started since the previous block (`previous_timestamp`, unset on the
+/// first block): from the day after the one pruned by the previous block, whatever the number of blocks or days
+/// without attestations in between, to the day leaving with the block day.
+fn expired_days(previous_timestamp: Option<i64>, timestamp: i64) -> RangeInclusive<i64> {
+    let current_day = day(timestamp as u64);
+    let previous_day = previous_timestamp.map_or(current_day, |previous_timestamp| day(previous_timestamp as u64));
+    previous_day - ACTIVE_WINDOW_DAYS + 1..=current_day - ACTIVE_WINDOW_DAYS
+}
+
+/// `(ordinal, day, change)` writes of the last active day deltas: an attester active again moves from the count of its
+/// previous day, when still within the window, to the count of the current one.
+fn active_day_writes(deltas: &[DeltaInt64]) -> Vec<(u64, i64, i64)> {
+    let mut writes = vec![];
+    for delta in deltas.iter().filter(|delta| delta.operation != Operation::Delete) {
+        let (previous_day, active_day) = (delta.old_value, delta.new_value);
+        if delta.operation == Operation::Update {
+            if previous_day == active_day {
+                continue;
+            }
+            if previous_day > active_day - ACTIVE_WINDOW_DAYS {
+                writes.push((delta.ordinal, previous_day, -1));
+            }
+        }
+        writes.push((delta.ordinal, active_day, 1));
+    }
+    writes
+}
+
+/// Counts the attesters by last active day over the rolling window, keyed by day: an attester active again moves from
+/// the count of its previous day to the count of the current one. Days leaving the window are pruned on the block
+/// starting the day they leave it, see `expired_days`.
+#[substreams::handlers::store]
+fn store_active_attester_days(deltas: Deltas<DeltaInt64>, last_block_time: StoreGetInt64, store: StoreAddInt64) {
+    if let Some(timestamp) = last_block_time.get_last(LAST_BLOCK_TIME_KEY) {
+        // the value before this block's write
+        for expired_day in expired_days(last_block_time.get_first(LAST_BLOCK_TIME_KEY), timestamp) {
+            store.delete_prefix(0, &day_key(expired_day));
+        }
+    }
+
+    for (ordinal, day, change) in active_day_writes(&deltas.deltas) {
+        store.add(ordinal, day_key(day), change);
+    }
+}
+
+/// Emits the number of distinct attesters active on the day of the block (`daily_active_attesters`) and over the
+/// rolling 7 days ending on it (`weekly_active_attesters`), for blocks with attestations. Counts grow over the day,
+/// the last output of a day holding its final counts.
+#[substreams::handlers::map]
+fn map_active_attesters(events: contract::Events, days: StoreGetInt64) -> Result<Option<contract::ActiveAttesters>, substreams::errors::Error> {
+    let Some(attested) = events.eas_attesteds.first() else {
+        return Ok(None);
+    };
+    let current_day = day(attested.evt_block_timestamp_seconds);
+    let count = |day: i64| days.get_last(day_key(day)).unwrap_or_default().max(0) as u64;
+
+    Ok(Some(contract::ActiveAttesters {
+        day: current_day as u64,
+        block_number: attested.evt_block_number,
+        daily_active_attesters: count(current_day),
+        weekly_active_attesters: (current_day - ACTIVE_WINDOW_DAYS + 1..=current_day).map(count).sum(),
+    }))
+}
+
+/// Keeps the first day each schema was attested on, keyed by the hex-encoded schema UID.
+#[substreams::handlers::store]
+fn store_schema_first_active_day(events: contract::Events, store: StoreSetIfNotExistsInt64) {
+    for attested in events.eas_attesteds {
+        store.set_if_not_exists(
+            attested.evt_index as u64,
+            Hex(&attested.schema_id).to_string(),
+            &day(attested.evt_block_timestamp_seconds),
+        );
+    }
+}
+
+/// Keeps the last day each schema was attested on, keyed by the hex-encoded schema UID, written once per schema and
+/// block.
+#[substreams::handlers::store]
+fn store_schema_last_active_day(events: contract::Events, store: StoreSetInt64) {
+    let mut latest = BTreeMap::new();
+    for attested in &events.eas_attesteds {
+        latest.insert(Hex(&attested.schema_id).to_string(), attested);
+    }
+    // in ordinal order so that deltas roll back in order
+    let mut latest: Vec<_> = latest.into_iter().collect();
+    latest.sort_by_key(|(_, attested)| attested.evt_index);
+
+    for (key, attested) in latest {
+        store.set(attested.evt_index as u64, key, &day(attested.evt_block_timestamp_seconds));
+    }
+}
+
+/// Lists the schemas active on each day, keyed by day, each schema once per day: the candidates to dormancy once the
+/// day is far enough behind.
+#[substreams::handlers::store]
+fn store_schema_active_days(deltas: Deltas<DeltaInt64>, store: StoreAppend<String>) {
+    for delta in deltas.deltas {
+        let first_today = match delta.operation {
+            Operation::Create => true,
+            Operation::Update => delta.old_value != delta.new_value,
+            _ => false,
+        };
+        if first_today {
+            store.append(delta.ordinal, day_key(delta.new_value), delta.key);
+        }
+    }
+}
+
+/// Last active days of the schemas becoming dormant with the days started since the previous block day, usually none
+/// or one: a schema last active on day `d` becomes dormant on day `d + dormant_days + 1`, its `dormant_days` full
+/// days without attestation being over.
+fn dormancy_days(previous_day: i64, current_day: i64, dormant_days: i64) -> RangeInclusive<i64> {
+    previous_day - dormant_days..=current_day - dormant_days - 1
+}
+
+/// Emits a `SchemaDormant` signal for every schema that has had no attestation for `dormant_days` (param, default
+/// `30`) full days, as of the first block of the day, after averaging at least `min_daily_attestations` (param,
+/// default `1`) attestations a day from its first to its last active day.
+#[substreams::handlers::map]
+fn map_dormant_schemas(
+    params: String,
+    clock: Clock,
+    last_block_time: StoreGetInt64,
+    active_days: StoreGetArray<String>,
+    first_active_days: StoreGetInt64,
+    last_active_days: StoreGetInt64,
+    attestation_counts: StoreGetInt64,
+) -> Result<contract::DormantSchemas, substreams::errors::Error> {
+    let params = Params::parse(&params);
+    let dormant_days = params.get_or("dormant_days", 30i64)?;
+    let min_daily_attestations = params.get_or("min_daily_attestations", 1f64)?;
+    // the value before this block's write: unset on the first block, which starts no day
+    let Some(previous_timestamp) = last_block_time.get_first(LAST_BLOCK_TIME_KEY) else {
+        return Ok(contract::DormantSchemas::default());
+    };
+    let current_day = day(clock.timestamp.map(|timestamp| timestamp.seconds as u64).unwrap_or_default());
+
+    let mut dormant_schemas = vec![];
+    for last_active_day in dormancy_days(day(previous_timestamp as u64), current_day, dormant_days) {
+        for schema_id in active_days.get_last(day_key(last_active_day)).unwrap_or_default() {
+            if last_active_days.get_last(&schema_id) != Some(last_active_day) {
+                continue;
+            }
+            let attestation_count = attestation_counts.get_last(&schema_id).unwrap_or_default().max(0) as u64;
+            let first_active_day = first_active_days.get_last(&schema_id).unwrap_or(last_active_day);
+            let average_daily_attestations = attestation_count as f64 / (last_active_day - first_active_day + 1) as f64;
+            if average_daily_attestations < min_daily_attestations {
+                continue;
+            }
+            dormant_schemas.push(contract::SchemaDormant {
+                schema_id: hex::decode(&schema_id).unwrap_or_default(),
+                block_number: clock.number,
+                last_active_day: last_active_day as u64,
+                dormant_days: (current_day - last_active_day - 1) as u64,
+                attestation_count,
+                average_daily_attestations,
+            });
+        }
+    }
+
+    Ok(contract::DormantSchemas { dormant_schemas })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: i64 = 86_400;
+
+    fn attested(evt_index: u32, schema_id: u8, attester: u8, recipient: u8) -> contract::EasAttested {
+        contract::EasAttested {
+            evt_index,
+            evt_block_number: 9,
+            schema_id: vec![schema_id; 32],
+            attester: vec![attester; 20],
+            recipient: vec![recipient; 20],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn address_activity_is_written_once_per_role_and_address() {
+        let events = contract::Events {
+            eas_attesteds: vec![attested(1, 0x55, 0x11, 0x44), attested(3, 0x55, 0x11, 0x11)],
+            ..Default::default()
+        };
+        let first_seen = |key: &str| (key == activity_key(AddressRole::Attester, &[0x11; 20])).then_some(2);
+
+        let writes: Vec<_> = address_activity_writes(&events, first_seen)
+            .into_iter()
+            .map(|(ordinal, key, activity)| (ordinal, key, activity.first_seen_block_number, activity.last_seen_block_number))
+            .collect();
+        // the attester acting as a recipient keeps two entries, each at the last ordinal of its role
+        assert_eq!(
+            writes,
+            [
+                (1, format!("recipient:{}", "44".repeat(20)), 9, 9),
+                (3, format!("attester:{}", "11".repeat(20)), 2, 9),
+                (3, format!("recipient:{}", "11".repeat(20)), 9, 9),
+            ]
+        );
+    }
+
+    #[test]
+    fn schema_attesters_keep_their_first_attester_and_take_the_latest() {
+        let events = contract::Events {
+            eas_attesteds: vec![attested(1, 0x55, 0x11, 0x44), attested(2, 0x66, 0x11, 0x44), attested(4, 0x55, 0x22, 0x44)],
+            ..Default::default()
+        };
+        // the first attester store only knows schema 0x55
+        let first_attester = |key: &str| {
+            (key == "55".repeat(32)).then(|| contract::SchemaAttester {
+                schema_id: vec![0x55; 32],
+                first_attester: vec![0x11; 20],
+                first_attested_block_number: 3,
+                ..Default::default()
+            })
+        };
+
+        let writes = schema_attester_writes(&events, first_attester);
+        assert_eq!(writes.len(), 1);
+        let (ordinal, key, attester) = &writes[0];
+        assert_eq!((*ordinal, key.as_str()), (4, "55".repeat(32).as_str()));
+        assert_eq!((attester.first_attester[0], attester.first_attested_block_number), (0x11, 3));
+        assert_eq!((attester.latest_attester[0], attester.latest_attested_block_number), (0x22, 9));
+    }
+
+    #[test]
+    fn days_leave_the_active_window_once() {
+        let day_range = |range: RangeInclusive<i64>| range.collect::<Vec<_>>();
+        // same day, and first block: nothing leaves
+        assert_eq!(day_range(expired_days(Some(20 * DAY), 20 * DAY + 5)), [] as [i64; 0]);
+        assert_eq!(day_range(expired_days(None, 20 * DAY)), [] as [i64; 0]);
+        // the block starting day 20 prunes day 13, the window being days 14 to 20
+        assert_eq!(day_range(expired_days(Some(20 * DAY - 1), 20 * DAY)), [13]);
+        // a gap of 30 days prunes every day left since the last pruned one
+        assert_eq!(day_range(expired_days(Some(20 * DAY), 50 * DAY)), (14..=43).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn active_attesters_move_to_their_current_day() {
+        let delta = |operation, ordinal, old_value, new_value| DeltaInt64 {
+            operation,
+            ordinal,
+            key: "11".repeat(20),
+            old_value,
+            new_value,
+        };
+        let deltas = [
+            delta(Operation::Create, 1, 0, 20),
+            delta(Operation::Update, 2, 18, 20),
+            delta(Operation::Update, 3, 10, 20),
+            delta(Operation::Update, 4, 20, 20),
+        ];
+        // the attester last active on day 10 left the window, its count with it
+        assert_eq!(active_day_writes(&deltas), [(1, 20, 1), (2, 18, -1), (2, 20, 1), (3, 20, 1)]);
+    }
+
+    #[test]
+    fn schemas_become_dormant_once_their_dormant_days_are_over() {
+        let days = |previous_day, current_day| dormancy_days(previous_day, current_day, 30).collect::<Vec<_>>();
+        // day 41 starts: schemas last active on day 10 have had 30 full days without attestation
+        assert_eq!(days(40, 41), [10]);
+        assert_eq!(days(41, 41), [] as [i64; 0]);
+        assert_eq!(days(40, 43), [10, 11, 12]);
+    }
+}
@@ -0,0 +1,89 @@
+use crate::params::Params;
+use crate::pb::contract::v1 as contract;
+use crate::{keccak256, networks};
+use contract::AdminEventKind;
+use substreams_ethereum::pb::eth::v2 as eth;
+
+/// Proxy administration events (ERC-1967) and ownership transfers (OpenZeppelin `Ownable`) tracked on the EAS and
+/// schema registry contracts.
+const ADMIN_EVENTS: [(&str, AdminEventKind); 3] = [
+    ("Upgraded(address)", AdminEventKind::Upgraded),
+    ("AdminChanged(address,address)", AdminEventKind::AdminChanged),
+    ("OwnershipTransferred(address,address)", AdminEventKind::OwnershipTransferred),
+];
+
+/// Returns the `(previous, new)` addresses carried by an admin event: the new implementation for `Upgraded` (no
+/// previous one), the admins for `AdminChanged` (not indexed) and the owners for `OwnershipTransferred` (indexed).
+fn admin_addresses(kind: AdminEventKind, log: &eth::Log) -> Option<(Vec<u8>, Vec<u8>)> {
+    let address = |word: &[u8]| word.get(12..32).map(Vec::from);
+    match kind {
+        AdminEventKind::Upgraded => Some((vec![], address(log.topics.get(1)?)?)),
+        AdminEventKind::AdminChanged => Some((address(log.data.get(..32)?)?, address(log.data.get(32..64)?)?)),
+        AdminEventKind::OwnershipTransferred => Some((address(log.topics.get(1)?)?, address(log.topics.get(2)?)?)),
+        AdminEventKind::Unspecified => None,
+    }
+}
+
+/// Emits the upgrades, admin changes and ownership transfers of the EAS and schema registry contracts, so that
+/// operators are alerted when the implementation they index changes. Only proxied deployments, such as the OP-stack
+/// predeploys, emit them. The contracts are selected with the `chain_family` and `chain` params, like for `map_events`.
+#[substreams::handlers::map]
+fn map_admin_events(params: String, blk: eth::Block) -> Result<contract::AdminEvents, substreams::errors::Error> {
+    let network = networks::from_params(&Params::parse(&params))?;
+    network.check_block(blk.number)?;
+    let topics: Vec<_> = ADMIN_EVENTS.iter().map(|(signature, kind)| (keccak256(signature.as_bytes()), *kind)).collect();
+
+    let admin_events = blk
+        .logs()
+        .filter(|log| log.address() == network.contracts.eas || log.address() == network.contracts.schema_registry)
+        .filter_map(|log| {
+            let (_, kind) = topics.iter().find(|(topic, _)| log.log.topics.first().is_some_and(|first| first == topic))?;
+            let (previous_address, new_address) = admin_addresses(*kind, log.log)?;
+            Some(contract::AdminEvent {
+                evt_tx_hash: log.receipt.transaction.hash.clone(),
+                evt_index: log.log.block_index,
+                evt_block_time: Some(blk.timestamp().to_owned()),
+                evt_block_timestamp_seconds: blk.timestamp_seconds(),
+                evt_block_number: blk.number,
+                contract: log.address().to_vec(),
+                kind: *kind as i32,
+                previous_address,
+                new_address,
+            })
+        })
+        .collect();
+
+    Ok(contract::AdminEvents { admin_events })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn decodes_admin_event_addresses() {
+        assert_eq!(
+            keccak256(ADMIN_EVENTS[0].0.as_bytes()),
+            hex!("bc7cd75a20ee27fd9adebab32041f755214dbc6bffa90cc0225b39da2e5c2d3b")
+        );
+
+        let word = |byte: u8| [vec![0; 12], vec![byte; 20]].concat();
+        let upgraded = eth::Log {
+            topics: vec![vec![], word(0x11)],
+            ..Default::default()
+        };
+        assert_eq!(admin_addresses(AdminEventKind::Upgraded, &upgraded), Some((vec![], vec![0x11; 20])));
+
+        let admin_changed = eth::Log {
+            topics: vec![vec![]],
+            data: [word(0x22), word(0x33)].concat(),
+            ..Default::default()
+        };
+        assert_eq!(
+            admin_addresses(AdminEventKind::AdminChanged, &admin_changed),
+            Some((vec![0x22; 20], vec![0x33; 20]))
+        );
+        assert_eq!(admin_addresses(AdminEventKind::OwnershipTransferred, &admin_changed), None);
+    }
+}
@@ -0,0 +1,89 @@
+use crate::pb::contract::v1 as contract;
+
+/// Moves the attestation into the group of its schema, created on the first attestation of the schema with the
+/// schema-level fields, which are cleared on the attestations of the group.
+fn add_to_group(groups: &mut Vec<contract::SchemaAttesteds>, mut attested: contract::EasAttested) {
+    let index = match groups.iter().position(|group| group.schema_id == attested.schema_id) {
+        Some(index) => index,
+        None => {
+            groups.push(contract::SchemaAttesteds {
+                schema_id: attested.schema_id.clone(),
+                ..Default::default()
+            });
+            groups.len() - 1
+        }
+    };
+    let group = &mut groups[index];
+    // undecoded attestations carry no schema text, the first attestation carrying it sets it on the group
+    if group.schema.is_empty() && !attested.schema.is_empty() {
+        group.schema = std::mem::take(&mut attested.schema);
+        group.schema_field_count = attested.schema_field_count;
+        group.schema_field_types = std::mem::take(&mut attested.schema_field_types);
+        group.schema_shape_id = std::mem::take(&mut attested.schema_shape_id);
+    }
+    if group.schema_registered_time.is_none() && attested.schema_registered_time.is_some() {
+        group.schema_registered_block = attested.schema_registered_block;
+        group.schema_registered_time = attested.schema_registered_time.take();
+    }
+    attested.schema.clear();
+    attested.schema_field_count = 0;
+    attested.schema_field_types.clear();
+    attested.schema_shape_id.clear();
+    attested.schema_registered_block = 0;
+    attested.schema_registered_time = None;
+    group.attesteds.push(attested);
+}
+
+/// Emits the attestations of the block grouped by schema, the schema text, field types, shape ID and registration
+/// being stated once per group rather than on every attestation. Groups come in the order of the first attestation of
+/// their schema in the block, attestations in log order within their group. Revocations are left out.
+#[substreams::handlers::map]
+fn map_events_by_schema(events: contract::Events) -> Result<contract::EventsBySchema, substreams::errors::Error> {
+    let mut schemas = vec![];
+    for attested in events.eas_attesteds {
+        add_to_group(&mut schemas, attested);
+    }
+
+    Ok(contract::EventsBySchema {
+        schemas,
+        chain_id: events.chain_id,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn attestations_are_grouped_by_schema() {
+        let attested = |uid: u8, schema_id: u8, schema: &str| contract::EasAttested {
+            uid: vec![uid; 32],
+            schema_id: vec![schema_id; 32],
+            schema: schema.to_string(),
+            schema_field_count: 1,
+            schema_field_types: vec!["bool".to_string()],
+            ..Default::default()
+        };
+
+        let events = contract::Events {
+            eas_attesteds: vec![attested(1, 0xaa, ""), attested(2, 0xbb, "bool b"), attested(3, 0xaa, "bool a")],
+            ..Default::default()
+        };
+        let by_schema = map_events_by_schema(events).unwrap();
+
+        let groups: Vec<_> = by_schema
+            .schemas
+            .iter()
+            .map(|group| (group.schema_id[0], group.schema.as_str(), group.attesteds.len()))
+            .collect();
+        assert_eq!(groups, [(0xaa, "bool a", 2), (0xbb, "bool b", 1)]);
+        let uids: Vec<_> = by_schema.schemas[0].attesteds.iter().map(|attested| attested.uid[0]).collect();
+        assert_eq!(uids, [1, 3]);
+        assert_eq!(by_schema.schemas[0].schema_field_types, ["bool"]);
+        assert!(by_schema
+            .schemas
+            .iter()
+            .flat_map(|group| &group.attesteds)
+            .all(|attested| attested.schema.is_empty() && attested.schema_field_types.is_empty()));
+    }
+}
@@ -0,0 +1,236 @@
+use crate::abi::eas_contract::events::Attested;
+use crate::abi::eas_contract::functions::{
+    Attest, AttestByDelegation, MultiAttest, MultiAttestByDelegation, MultiRevoke, MultiRevokeByDelegation, Revoke, RevokeByDelegation,
+};
+use crate::abi::eas_schema_registry_contract::functions::Register;
+use crate::{keccak256, Attestation};
+use hex_literal::hex;
+use substreams::scalar::BigInt;
+use substreams_ethereum::pb::eth::v2 as eth;
+use substreams_ethereum::Function;
+
+/// ERC-4337 EntryPoint deployments (v0.6 and v0.7), identical across chains.
+const ENTRY_POINTS: [[u8; 20]; 2] = [
+    hex!("5ff137d4b0fdcd49dca30c7cf57e578a026d2789"),
+    hex!("0000000071727de22e5e9d8baf0edac6f37da032"),
+];
+
+/// `ISchemaResolver` hooks called by EAS on the resolver of the schema of the attestations, when it has one.
+const RESOLVER_ATTEST_HOOKS: [&str; 2] = [
+    "attest((bytes32,bytes32,uint64,uint64,uint64,bytes32,address,address,bool,bytes))",
+    "multiAttest((bytes32,bytes32,uint64,uint64,uint64,bytes32,address,address,bool,bytes)[],uint256[])",
+];
+
+/// `AttestationRequestData` tuple of the EAS `attest*` functions:
+/// `(recipient, expirationTime, revocable, refUID, data, value)`.
+type AttestationRequestData = (Vec<u8>, BigInt, bool, [u8; 32], Vec<u8>, BigInt);
+
+/// Whether the block carries call traces, absent from `base` detail level blocks (headers, transactions and logs
+/// only). Trace-derived enrichment is skipped without them.
+pub fn has_call_traces(blk: &eth::Block) -> bool {
+    blk.detail_level != eth::block::DetailLevel::DetaillevelBase as i32
+}
+
+/// Returns the call of the transaction that emitted the given log.
+pub fn emitting_call<'a>(trx: &'a eth::TransactionTrace, log: &eth::Log) -> Option<&'a eth::Call> {
+    trx.calls
+        .iter()
+        .find(|call| call.logs.iter().any(|call_log| call_log.block_index == log.block_index))
+}
+
+/// Returns the parent of the given call, `None` for the root call.
+fn parent_call<'a>(trx: &'a eth::TransactionTrace, call: &eth::Call) -> Option<&'a eth::Call> {
+    if call.depth == 0 {
+        return None;
+    }
+    trx.calls.iter().find(|parent| parent.index == call.parent_index)
+}
+
+/// Decodes the `(schema, request)` pairs of an EAS `attest*` call, flattened in the order the `Attested` logs are emitted.
+fn decode_attestation_requests(call: &eth::Call) -> Option<Vec<([u8; 32], AttestationRequestData)>> {
+    if let Some(attest) = Attest::match_and_decode(call) {
+        return Some(vec![attest.request]);
+    }
+    if let Some(attest) = AttestByDelegation::match_and_decode(call) {
+        return Some(vec![(attest.delegated_request.0, attest.delegated_request.1)]);
+    }
+    if let Some(attest) = MultiAttest::match_and_decode(call) {
+        return Some(
+            attest
+                .multi_requests
+                .into_iter()
+                .flat_map(|(schema, requests)| requests.into_iter().map(move |request| (schema, request)))
+                .collect(),
+        );
+    }
+    if let Some(attest) = MultiAttestByDelegation::match_and_decode(call) {
+        return Some(
+            attest
+                .multi_delegated_requests
+                .into_iter()
+                .flat_map(|(schema, requests, _, _)| requests.into_iter().map(move |request| (schema, request)))
+                .collect(),
+        );
+    }
+    None
+}
+
+/// Returns the name of the EAS `attest*` function called and the schema UIDs of its requests, `None` for any other call.
+pub fn attest_call(call: &eth::Call) -> Option<(&'static str, Vec<[u8; 32]>)> {
+    let name = [
+        (Attest::match_call(call), Attest::NAME),
+        (AttestByDelegation::match_call(call), AttestByDelegation::NAME),
+        (MultiAttest::match_call(call), MultiAttest::NAME),
+        (MultiAttestByDelegation::match_call(call), MultiAttestByDelegation::NAME),
+    ]
+    .into_iter()
+    .find_map(|(matched, name)| matched.then_some(name))?;
+    let schemas = decode_attestation_requests(call)
+        .map(|requests| requests.into_iter().map(|(schema, _)| schema).collect())
+        .unwrap_or_default();
+    Some((name, schemas))
+}
+
+/// Rebuilds an attestation from the calldata of the EAS `attest*` call behind an `Attested` log, without any RPC call.
+///
+/// The call tree is walked up from the call that emitted the log until an `attest*` call is found, so attestations
+/// submitted through Multicall3, account-abstraction bundlers or any other wrapper contract are resolved as well.
+/// Returns `None` when the block carries no call traces or the calldata cannot be matched to the log.
+pub fn attestation_from_calldata(trx: &eth::TransactionTrace, log: &eth::Log, eas: &[u8], event: &Attested, time: u64) -> Option<Attestation> {
+    let mut call = emitting_call(trx, log)?;
+    let (call, requests) = loop {
+        match decode_attestation_requests(call) {
+            Some(requests) => break (call, requests),
+            None => call = parent_call(trx, call)?,
+        }
+    };
+
+    // position of the log among the `Attested` logs emitted within the matched call
+    let position = trx
+        .receipt
+        .as_ref()?
+        .logs
+        .iter()
+        .filter(|trx_log| trx_log.ordinal >= call.begin_ordinal && trx_log.ordinal <= call.end_ordinal)
+        .filter(|trx_log| trx_log.address == eas && Attested::match_log(trx_log))
+        .position(|trx_log| trx_log.block_index == log.block_index)?;
+    let (schema, (recipient, expiration_time, revocable, ref_uid, data, _)) = requests.into_iter().nth(position)?;
+
+    Some(Attestation {
+        uid: event.uid,
+        schema,
+        time,
+        expiration_time: expiration_time.to_u64(),
+        revocation_time: 0,
+        ref_uid,
+        recipient: recipient.as_slice().try_into().ok()?,
+        attester: event.attester.as_slice().try_into().ok()?,
+        revocable,
+        data,
+    })
+}
+
+/// Returns the ERC-4337 UserOperation sender (the smart account) behind the call that emitted the given log,
+/// found by walking up the call tree to the call made by an EntryPoint. `None` outside of `handleOps` transactions.
+pub fn user_operation_sender(trx: &eth::TransactionTrace, log: &eth::Log) -> Option<Vec<u8>> {
+    let mut call = emitting_call(trx, log)?;
+    loop {
+        if ENTRY_POINTS.iter().any(|entry_point| call.caller == entry_point) && !ENTRY_POINTS.iter().any(|entry_point| call.address == entry_point) {
+            return Some(call.address.clone());
+        }
+        call = parent_call(trx, call)?;
+    }
+}
+
+/// Returns the signer of the `revokeByDelegation`/`multiRevokeByDelegation` call behind a `Revoked` log, matched by
+/// the revoked UID, so revocations submitted by a relayer are attributed to the actual revoker.
+/// `None` for direct revocations or when the block carries no call traces.
+pub fn revocation_delegation_signer(trx: &eth::TransactionTrace, log: &eth::Log, uid: &[u8; 32]) -> Option<Vec<u8>> {
+    let mut call = emitting_call(trx, log)?;
+    loop {
+        if let Some(revoke) = RevokeByDelegation::match_and_decode(call) {
+            let (_, (revoked_uid, _), _, revoker) = revoke.delegated_request;
+            return (revoked_uid == *uid).then_some(revoker);
+        }
+        if let Some(revoke) = MultiRevokeByDelegation::match_and_decode(call) {
+            return revoke
+                .multi_delegated_requests
+                .into_iter()
+                .find(|(_, requests, _, _)| requests.iter().any(|(revoked_uid, _)| revoked_uid == uid))
+                .map(|(_, _, _, revoker)| revoker);
+        }
+        if Revoke::match_call(call) || MultiRevoke::match_call(call) {
+            return None;
+        }
+        call = parent_call(trx, call)?;
+    }
+}
+
+/// Returns the calls made by EAS (at `eas`) to other contracts during the given EAS call, i.e. the schema resolver
+/// hooks. The delegate calls of the EAS proxy (e.g. the OP-stack predeploys) into its implementation run EAS itself
+/// and are left out.
+fn hook_calls<'a>(trx: &'a eth::TransactionTrace, eas_call: &eth::Call, eas: &[u8]) -> impl Iterator<Item = &'a eth::Call> {
+    let (begin_ordinal, end_ordinal) = (eas_call.begin_ordinal, eas_call.end_ordinal);
+    let eas = eas.to_vec();
+    trx.calls
+        .iter()
+        .filter(move |call| call.caller == eas && call.address != eas && call.call_type != eth::CallType::Delegate as i32)
+        .filter(move |call| call.begin_ordinal >= begin_ordinal && call.end_ordinal <= end_ordinal)
+}
+
+/// Returns the logs emitted by the contracts EAS (at `eas`) called during the EAS call that emitted the given log,
+/// i.e. by the schema resolver hooks, in log order. Empty when the block carries no call traces.
+pub fn resolver_logs<'a>(trx: &'a eth::TransactionTrace, log: &eth::Log, eas: &[u8]) -> Vec<&'a eth::Log> {
+    let Some(eas_call) = emitting_call(trx, log) else {
+        return vec![];
+    };
+    let resolver_calls: Vec<_> = hook_calls(trx, eas_call, eas).collect();
+
+    trx.receipt
+        .iter()
+        .flat_map(|receipt| receipt.logs.iter())
+        .filter(|trx_log| {
+            resolver_calls
+                .iter()
+                .any(|call| trx_log.ordinal >= call.begin_ordinal && trx_log.ordinal <= call.end_ordinal)
+        })
+        .collect()
+}
+
+/// Returns the resolver `attest`/`multiAttest` hook calls made by EAS (at `eas`) during the given EAS call, reverted
+/// ones included.
+pub fn resolver_attest_calls<'a>(trx: &'a eth::TransactionTrace, eas_call: &eth::Call, eas: &[u8]) -> Vec<&'a eth::Call> {
+    let selectors: Vec<[u8; 32]> = RESOLVER_ATTEST_HOOKS.iter().map(|hook| keccak256(hook.as_bytes())).collect();
+    hook_calls(trx, eas_call, eas)
+        .filter(|call| call.input.get(..4).is_some_and(|selector| selectors.iter().any(|hook| hook[..4] == *selector)))
+        .collect()
+}
+
+/// Whether a resolver hook call rejected the attestations: it reverted, or returned `false`.
+pub fn hook_rejected(call: &eth::Call) -> bool {
+    let accepted = ethabi::decode(&[ethabi::ParamType::Bool], &call.return_data)
+        .ok()
+        .and_then(|tokens| tokens.into_iter().next()?.into_bool());
+    call.status_failed || accepted == Some(false)
+}
+
+/// Whether a resolver hook call rejected the attestation during the EAS call that emitted the given `Attested` log,
+/// although the attestation went through. EAS reverts such attestations: only EAS-compatible contracts calling their
+/// resolver in a try-style wrapper, or ignoring its result, let them through. `None` when no hook call is found, e.g.
+/// for schemas without resolver or when the block carries no call traces.
+pub fn resolver_call_rejected(trx: &eth::TransactionTrace, log: &eth::Log, eas: &[u8]) -> Option<bool> {
+    let hooks = resolver_attest_calls(trx, emitting_call(trx, log)?, eas);
+    (!hooks.is_empty()).then(|| hooks.iter().any(|call| hook_rejected(call)))
+}
+
+/// Returns the resolver given to the schema registry `register` call behind a `Registered` log, i.e. the resolver
+/// recorded at registration time. `None` when the block carries no call traces.
+pub fn registration_resolver(trx: &eth::TransactionTrace, log: &eth::Log) -> Option<Vec<u8>> {
+    let mut call = emitting_call(trx, log)?;
+    loop {
+        if let Some(register) = Register::match_and_decode(call) {
+            return Some(register.resolver);
+        }
+        call = parent_call(trx, call)?;
+    }
+}
@@ -0,0 +1,83 @@
+use crate::{keccak256, Attestation, Schema};
+use std::fmt;
+use substreams::Hex;
+
+/// Hex formatting of UIDs and addresses, matching the stream's default rendering for UIDs (`0x`-prefixed lowercase)
+/// and EIP-55 checksummed for addresses, so that library users do not hand-roll formatting that drifts from it.
+pub trait ToHex {
+    fn to_hex(&self) -> String;
+}
+
+/// UIDs, schema UIDs and hashes: `0x`-prefixed lowercase hex.
+impl ToHex for [u8; 32] {
+    fn to_hex(&self) -> String {
+        format!("0x{}", Hex(self))
+    }
+}
+
+/// Addresses: EIP-55 checksummed, the hex letters being uppercased where the matching nibble of the keccak256 of the
+/// lowercase hex is 8 or more.
+impl ToHex for [u8; 20] {
+    fn to_hex(&self) -> String {
+        let lowercase = Hex(self).to_string();
+        let hash = keccak256(lowercase.as_bytes());
+        let checksummed: String = lowercase
+            .chars()
+            .enumerate()
+            .map(|(i, char)| match (hash[i / 2] >> (4 * (1 - i % 2))) & 0xf {
+                8.. => char.to_ascii_uppercase(),
+                _ => char,
+            })
+            .collect();
+        format!("0x{}", checksummed)
+    }
+}
+
+/// `<uid> (schema <schema_uid>, attester <address>, recipient <address>)`.
+impl fmt::Display for Attestation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (schema {}, attester {}, recipient {})",
+            self.uid.to_hex(),
+            self.schema.to_hex(),
+            self.attester.to_hex(),
+            self.recipient.to_hex()
+        )
+    }
+}
+
+/// `<schema_uid>: <schema>`, the schema signature being quoted.
+impl fmt::Display for Schema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {:?}", self.uid_id.to_hex(), self.schema)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn formats_uids_and_checksummed_addresses() {
+        // EIP-55 test vectors
+        assert_eq!(
+            hex!("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").to_hex(),
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+        );
+        assert_eq!(
+            hex!("fb6916095ca1df60bb79ce92ce3ea74c37c5d359").to_hex(),
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359"
+        );
+        assert_eq!([0xab; 32].to_hex(), format!("0x{}", "ab".repeat(32)));
+
+        let schema = Schema {
+            uid_id: [0x55; 32],
+            resolver: vec![],
+            revocable: true,
+            schema: "bool like".to_string(),
+        };
+        assert_eq!(schema.to_string(), format!("0x{}: \"bool like\"", "55".repeat(32)));
+    }
+}
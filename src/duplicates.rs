@@ -0,0 +1,91 @@
+use crate::keccak256;
+use crate::pb::contract::v1 as contract;
+use substreams::store::{StoreGet, StoreGetString, StoreSetIfNotExists, StoreSetIfNotExistsString};
+use substreams::Hex;
+
+/// Hash of the content of an attestation: keccak256 of its schema UID, recipient and decoded data. `None` for
+/// attestations without decoded data, which cannot be compared.
+fn content_hash(attested: &contract::EasAttested) -> Option<[u8; 32]> {
+    if attested.decoded_data.is_empty() || attested.enrichment_failed {
+        return None;
+    }
+    Some(keccak256(
+        &[attested.schema_id.as_slice(), &attested.recipient, attested.decoded_data.as_bytes()].concat(),
+    ))
+}
+
+/// Keeps the UID of the first attestation of each content, keyed by the hex-encoded content hash.
+#[substreams::handlers::store]
+fn store_attestation_contents(events: contract::Events, store: StoreSetIfNotExistsString) {
+    for attested in events.eas_attesteds {
+        if let Some(hash) = content_hash(&attested) {
+            store.set_if_not_exists(attested.evt_index as u64, Hex(hash).to_string(), &Hex(&attested.uid).to_string());
+        }
+    }
+}
+
+/// Emits the attestations of the block repeating the schema, recipient and decoded data of an earlier attestation,
+/// with the UID of that first attestation (`duplicate_of_uid`), so that airdrop and reputation systems can discount
+/// spam re-attestations.
+#[substreams::handlers::map]
+fn map_duplicate_attestations(events: contract::Events, contents: StoreGetString) -> Result<contract::DuplicateAttestations, substreams::errors::Error> {
+    let duplicate_attestations = events
+        .eas_attesteds
+        .into_iter()
+        .filter_map(|attested| {
+            let hash = content_hash(&attested)?;
+            let first_uid = contents.get_last(Hex(hash).to_string())?;
+            let duplicate_of_uid = hex::decode(first_uid).ok().filter(|first_uid| *first_uid != attested.uid)?;
+            Some(contract::DuplicateAttestation {
+                evt_tx_hash: attested.evt_tx_hash,
+                evt_index: attested.evt_index,
+                evt_block_time: attested.evt_block_time,
+                evt_block_timestamp_seconds: attested.evt_block_timestamp_seconds,
+                evt_block_number: attested.evt_block_number,
+                uid: attested.uid,
+                schema_id: attested.schema_id,
+                recipient: attested.recipient,
+                attester: attested.attester,
+                content_hash: hash.to_vec(),
+                duplicate_of_uid,
+            })
+        })
+        .collect();
+
+    Ok(contract::DuplicateAttestations { duplicate_attestations })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_hash_covers_schema_recipient_and_decoded_data() {
+        let attested = contract::EasAttested {
+            uid: vec![1; 32],
+            schema_id: vec![5; 32],
+            recipient: vec![4; 20],
+            attester: vec![1; 20],
+            decoded_data: r#"{"score":"7"}"#.to_string(),
+            ..Default::default()
+        };
+        // the UID and attester are not part of the content
+        let repeated = contract::EasAttested {
+            uid: vec![2; 32],
+            attester: vec![2; 20],
+            ..attested.clone()
+        };
+        assert_eq!(content_hash(&attested), content_hash(&repeated));
+
+        let other_recipient = contract::EasAttested {
+            recipient: vec![3; 20],
+            ..attested.clone()
+        };
+        assert_ne!(content_hash(&attested), content_hash(&other_recipient));
+        let undecoded = contract::EasAttested {
+            decoded_data: String::new(),
+            ..attested
+        };
+        assert_eq!(content_hash(&undecoded), None);
+    }
+}
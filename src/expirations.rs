@@ -0,0 +1,115 @@
+use crate::params::Params;
+use crate::pb::contract::v1 as contract;
+use contract::AttestationState;
+use substreams::pb::substreams::Clock;
+use substreams::store::{StoreAppend, StoreGet, StoreGetArray, StoreGetInt64, StoreGetProto, StoreSet, StoreSetInt64};
+use substreams::Hex;
+
+/// Width in seconds of the buckets the expiring attestations are indexed by.
+const EXPIRATION_BUCKET_SECONDS: u64 = 3600;
+
+/// Store key of the block time kept by `store_last_block_time`.
+pub const LAST_BLOCK_TIME_KEY: &str = "time";
+
+/// Store key of the bucket of an expiration time: the hour since the epoch, zero-padded so keys sort by time.
+fn expiration_bucket_key(expiration_time: u64) -> String {
+    format!("{:010}", expiration_time / EXPIRATION_BUCKET_SECONDS)
+}
+
+/// Index entry of an expiring attestation: `<uid>:<expiration_time>`.
+fn index_entry(uid: &[u8], expiration_time: u64) -> String {
+    format!("{}:{}", Hex(uid), expiration_time)
+}
+
+/// Returns the `(uid key, expiration_time)` of the index entries expiring within `(from, to]`.
+fn expiring_entries(entries: &[String], from: u64, to: u64) -> Vec<(&str, u64)> {
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let (uid, expiration_time) = entry.split_once(':')?;
+            Some((uid, expiration_time.parse().ok()?))
+        })
+        .filter(|(_, expiration_time)| (from + 1..=to).contains(expiration_time))
+        .collect()
+}
+
+/// Indexes the UID of every expiring attestation by the hour of its expiration time.
+#[substreams::handlers::store]
+fn store_expiration_index(events: contract::Events, store: StoreAppend<String>) {
+    for attested in events.eas_attesteds.iter().filter(|attested| attested.expiration_time != 0) {
+        store.append(
+            attested.evt_index as u64,
+            expiration_bucket_key(attested.expiration_time),
+            index_entry(&attested.uid, attested.expiration_time),
+        );
+    }
+}
+
+/// Keeps the time of the last block, read back at the start of the next one as the lower bound of its time range.
+#[substreams::handlers::store]
+fn store_last_block_time(clock: Clock, store: StoreSetInt64) {
+    let timestamp = clock.timestamp.map(|timestamp| timestamp.seconds).unwrap_or_default();
+    store.set(0, LAST_BLOCK_TIME_KEY, &timestamp);
+}
+
+/// Emits a warning for every attestation entering its final `notice_days` (param, default `7`) before expiration, i.e.
+/// whose expiration time minus the notice falls between the previous block time (excluded) and the block time, and
+/// that the status store still holds as active. Attestations created within their final days are not reported.
+#[substreams::handlers::map]
+fn map_expiring_attestations(
+    params: String,
+    clock: Clock,
+    expiration_index: StoreGetArray<String>,
+    last_block_time: StoreGetInt64,
+    status: StoreGetProto<contract::AttestationStatus>,
+) -> Result<contract::ExpiringAttestations, substreams::errors::Error> {
+    let notice = Params::parse(&params).get_or("notice_days", 7u64)? * 86400;
+    let timestamp = clock.timestamp.map(|timestamp| timestamp.seconds as u64).unwrap_or_default();
+    // the value before this block's write: unset on the first block, which has no time range yet
+    let Some(previous_timestamp) = last_block_time.get_first(LAST_BLOCK_TIME_KEY) else {
+        return Ok(contract::ExpiringAttestations::default());
+    };
+    let (from, to) = (previous_timestamp as u64 + notice, timestamp + notice);
+
+    let mut expiring_attestations = vec![];
+    for bucket in from / EXPIRATION_BUCKET_SECONDS..=to / EXPIRATION_BUCKET_SECONDS {
+        let entries = expiration_index
+            .get_last(expiration_bucket_key(bucket * EXPIRATION_BUCKET_SECONDS))
+            .unwrap_or_default();
+        for (uid, expiration_time) in expiring_entries(&entries, from, to) {
+            let Some(status) = status.get_last(uid).filter(|status| status.state == AttestationState::Active as i32) else {
+                continue;
+            };
+            expiring_attestations.push(contract::ExpiringAttestation {
+                uid: status.uid,
+                schema_id: status.schema_id,
+                attester: status.attester,
+                recipient: status.recipient,
+                expiration_time,
+                seconds_until_expiration: expiration_time.saturating_sub(timestamp),
+                block_number: clock.number,
+            });
+        }
+    }
+
+    Ok(contract::ExpiringAttestations { expiring_attestations })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expiring_entries_fall_within_the_time_range() {
+        let entries = vec![
+            index_entry(&[1; 32], 100),
+            index_entry(&[2; 32], 150),
+            index_entry(&[3; 32], 200),
+            "invalid".to_string(),
+        ];
+
+        let expiring: Vec<_> = expiring_entries(&entries, 100, 200).into_iter().map(|(_, time)| time).collect();
+        assert_eq!(expiring, [150, 200]);
+        assert_eq!(expiration_bucket_key(7200), "0000000002");
+    }
+}
@@ -0,0 +1,128 @@
+use crate::params::Params;
+use crate::pb::contract::v1 as contract;
+use crate::{calls, keccak256, networks};
+use ethabi::ParamType;
+use substreams::Hex;
+use substreams_ethereum::pb::eth::v2 as eth;
+
+/// Custom errors of the EAS contract, reported by name when an `attest*` call reverts with one of them.
+const EAS_ERRORS: [&str; 20] = [
+    "AccessDenied()",
+    "AlreadyRevoked()",
+    "AlreadyRevokedOffchain()",
+    "AlreadyTimestamped()",
+    "InsufficientValue()",
+    "InvalidAttestation()",
+    "InvalidAttestations()",
+    "InvalidExpirationTime()",
+    "InvalidLength()",
+    "InvalidOffset()",
+    "InvalidRegistry()",
+    "InvalidRevocation()",
+    "InvalidRevocations()",
+    "InvalidSchema()",
+    "InvalidSignature()",
+    "InvalidVerifier()",
+    "Irrevocable()",
+    "NotFound()",
+    "NotPayable()",
+    "WrongSchema()",
+];
+
+/// Selector of `Error(string)` reverts.
+const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+/// Selector of `Panic(uint256)` reverts.
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// Human readable revert reason of a reverted call, from its return data, falling back to the trace failure reason.
+fn revert_reason(call: &eth::Call) -> String {
+    let Some((selector, args)) = call.return_data.split_first_chunk::<4>() else {
+        return match call.return_data.is_empty() {
+            true => call.failure_reason.clone(),
+            false => format!("0x{}", Hex(&call.return_data)),
+        };
+    };
+    let decoded = match *selector {
+        ERROR_SELECTOR => ethabi::decode(&[ParamType::String], args)
+            .ok()
+            .and_then(|tokens| tokens.into_iter().next()?.into_string()),
+        PANIC_SELECTOR => ethabi::decode(&[ParamType::Uint(256)], args)
+            .ok()
+            .and_then(|tokens| tokens.into_iter().next()?.into_uint())
+            .map(|code| format!("Panic({:#x})", code)),
+        _ => EAS_ERRORS
+            .iter()
+            .find(|error| keccak256(error.as_bytes())[..4] == *selector)
+            .map(|error| error.to_string()),
+    };
+    decoded.unwrap_or_else(|| format!("0x{}", Hex(&call.return_data)))
+}
+
+/// Emits the reverted `attest*` calls to the EAS contract, so that failed attestation attempts can be monitored,
+/// flagging the ones whose resolver hook calls all succeeded and the ones a resolver hook call rejected.
+#[substreams::handlers::map]
+fn map_failed_attestations(params: String, blk: eth::Block) -> Result<contract::FailedAttestations, substreams::errors::Error> {
+    let network = networks::from_params(&Params::parse(&params))?;
+    network.check_block(blk.number)?;
+    if !calls::has_call_traces(&blk) {
+        return Ok(contract::FailedAttestations::default());
+    }
+
+    let failed_attestations = blk
+        .transaction_traces
+        .iter()
+        .flat_map(|trx| trx.calls.iter().map(move |call| (trx, call)))
+        .filter(|(_, call)| call.status_failed && call.address == network.contracts.eas)
+        .filter_map(|(trx, call)| {
+            let (method, schema_ids) = calls::attest_call(call)?;
+            let hooks = calls::resolver_attest_calls(trx, call, &network.contracts.eas);
+            Some(contract::FailedAttestation {
+                tx_hash: trx.hash.clone(),
+                call_index: call.index,
+                block_time: Some(blk.timestamp().to_owned()),
+                block_timestamp_seconds: blk.timestamp_seconds(),
+                block_number: blk.number,
+                tx_from: trx.from.clone(),
+                caller: call.caller.clone(),
+                method: method.to_string(),
+                schema_ids: schema_ids.into_iter().map(Vec::from).collect(),
+                revert_reason: revert_reason(call),
+                tx_failed: trx.status != eth::TransactionTraceStatus::Succeeded as i32,
+                // the resolvers accepted the attestations, the call failed for another reason
+                resolver_call_succeeded: !hooks.is_empty() && !hooks.iter().any(|hook| calls::hook_rejected(hook)),
+                // a resolver reverted or returned `false`, failing the call
+                resolver_call_rejected: hooks.iter().any(|hook| calls::hook_rejected(hook)),
+            })
+        })
+        .collect();
+
+    Ok(contract::FailedAttestations { failed_attestations })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethabi::Token;
+
+    fn reverted(return_data: Vec<u8>) -> eth::Call {
+        eth::Call {
+            status_failed: true,
+            status_reverted: true,
+            failure_reason: "execution reverted".to_string(),
+            return_data,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn decodes_revert_reasons() {
+        let message = [ERROR_SELECTOR.to_vec(), ethabi::encode(&[Token::String("not allowed".to_string())])].concat();
+        assert_eq!(revert_reason(&reverted(message)), "not allowed");
+        assert_eq!(revert_reason(&reverted(keccak256(b"InvalidSchema()")[..4].to_vec())), "InvalidSchema()");
+        let panic = [PANIC_SELECTOR.to_vec(), ethabi::encode(&[Token::Uint(0x11.into())])].concat();
+        assert_eq!(revert_reason(&reverted(panic)), "Panic(0x11)");
+        assert_eq!(revert_reason(&reverted(vec![0xde, 0xad, 0xbe, 0xef])), "0xdeadbeef");
+        assert_eq!(revert_reason(&reverted(vec![])), "execution reverted");
+    }
+}
@@ -0,0 +1,131 @@
+use crate::params::Params;
+use crate::pb::contract::v1 as contract;
+use serde_json::{Map, Value};
+use substreams::pb::substreams::Clock;
+use substreams::store::{StoreAdd, StoreAddInt64, StoreGet, StoreGetArray, StoreGetInt64, StoreGetString, StoreSetIfNotExists, StoreSetIfNotExistsString};
+use substreams::Hex;
+
+/// Whether a decoded value is zero or empty: `null`, `false`, zero numbers and strings (`"0"`, `"0x00…"`), empty
+/// strings, arrays and objects.
+fn is_zero_value(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::Bool(value) => !value,
+        Value::Number(number) => number.as_f64() == Some(0.0),
+        Value::String(string) => string.strip_prefix("0x").unwrap_or(string).chars().all(|char| char == '0'),
+        Value::Array(values) => values.is_empty(),
+        Value::Object(fields) => fields.is_empty(),
+    }
+}
+
+/// Decoded fields of a fully decoded attestation, `None` for attestations without (complete) decoded data.
+fn decoded_fields(attested: &contract::EasAttested) -> Option<Map<String, Value>> {
+    if attested.decoded_data.is_empty() || attested.enrichment_failed {
+        return None;
+    }
+    serde_json::from_str(&attested.decoded_data).ok()
+}
+
+/// Store key of the count of decoded attestations of a schema: `decoded:<schema_id>`.
+fn decoded_key(schema_id: &[u8]) -> String {
+    format!("decoded:{}", Hex(schema_id))
+}
+
+/// Store key of the count of zero values of a schema field: `zero:<schema_id>:<field>`.
+fn zero_key(schema_id: &[u8], field: &str) -> String {
+    format!("zero:{}:{}", Hex(schema_id), field)
+}
+
+/// Keeps the decoded field names of each schema, comma separated, keyed by the hex-encoded schema UID, as decoded from
+/// its first fully decoded attestation.
+#[substreams::handlers::store]
+fn store_schema_field_names(events: contract::Events, store: StoreSetIfNotExistsString) {
+    for attested in &events.eas_attesteds {
+        if let Some(fields) = decoded_fields(attested) {
+            let names: Vec<&str> = fields.keys().map(String::as_str).collect();
+            store.set_if_not_exists(attested.evt_index as u64, Hex(&attested.schema_id).to_string(), &names.join(","));
+        }
+    }
+}
+
+/// Counts, per schema, the fully decoded attestations, and per schema field, the zero or empty values among them.
+#[substreams::handlers::store]
+fn store_schema_field_stats(events: contract::Events, store: StoreAddInt64) {
+    for attested in &events.eas_attesteds {
+        let Some(fields) = decoded_fields(attested) else {
+            continue;
+        };
+        let ordinal = attested.evt_index as u64;
+        store.add(ordinal, decoded_key(&attested.schema_id), 1);
+        for (field, _) in fields.iter().filter(|(_, value)| is_zero_value(value)) {
+            store.add(ordinal, zero_key(&attested.schema_id, field), 1);
+        }
+    }
+}
+
+/// Every `interval` blocks (param, default `10000`), emits the zero or empty value rate of every field of every schema
+/// seen so far, so that schema designers and data consumers can identify unused fields.
+#[substreams::handlers::map]
+fn map_schema_field_stats(
+    params: String,
+    clock: Clock,
+    schema_index: StoreGetArray<String>,
+    field_names: StoreGetString,
+    stats: StoreGetInt64,
+) -> Result<contract::SchemaFieldStats, substreams::errors::Error> {
+    let interval = Params::parse(&params).get_or("interval", 10000u64)?;
+    if interval == 0 || !clock.number.is_multiple_of(interval) {
+        return Ok(contract::SchemaFieldStats::default());
+    }
+
+    let mut field_stats = vec![];
+    for schema_id in schema_index.get_last(crate::schemas::SCHEMA_INDEX_KEY).unwrap_or_default() {
+        let Some(names) = field_names.get_last(&schema_id) else {
+            continue;
+        };
+        let schema_id = hex::decode(&schema_id).unwrap_or_default();
+        let decoded_attestations = stats.get_last(decoded_key(&schema_id)).unwrap_or(0).max(0) as u64;
+        for field in names.split(',').filter(|field| !field.is_empty()) {
+            let zero_values = stats.get_last(zero_key(&schema_id, field)).unwrap_or(0).max(0) as u64;
+            field_stats.push(contract::SchemaFieldStat {
+                schema_id: schema_id.clone(),
+                field: field.to_string(),
+                decoded_attestations,
+                zero_values,
+                zero_rate: match decoded_attestations {
+                    0 => 0.0,
+                    _ => zero_values as f64 / decoded_attestations as f64,
+                },
+            });
+        }
+    }
+
+    Ok(contract::SchemaFieldStats {
+        block_number: clock.number,
+        field_stats,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn detects_zero_and_empty_values() {
+        let zeros = [
+            json!(null),
+            json!(false),
+            json!(0),
+            json!("0"),
+            json!("0x0000"),
+            json!(""),
+            json!([]),
+            json!({}),
+        ];
+        assert!(zeros.iter().all(is_zero_value));
+
+        let values = [json!(true), json!(1), json!("42"), json!("0x01"), json!("alice"), json!([0]), json!({"a": 0})];
+        assert!(!values.iter().any(is_zero_value));
+    }
+}
@@ -0,0 +1,145 @@
+use crate::params::Params;
+use crate::pb::contract::v1 as contract;
+use substreams::errors::Error;
+
+/// Named filter group, one per subscriber of a shared deployment. An event matches the group when it matches every
+/// criterion of the group, and a criterion when its field is one of the listed values.
+#[derive(Debug, Clone, PartialEq)]
+struct FilterGroup {
+    name: String,
+    schemas: Vec<Vec<u8>>,
+    attesters: Vec<Vec<u8>>,
+    recipients: Vec<Vec<u8>>,
+}
+
+impl FilterGroup {
+    fn matches(&self, schema: Option<&[u8]>, attester: &[u8], recipient: Option<&[u8]>) -> bool {
+        let matches = |values: &[Vec<u8>], field: Option<&[u8]>| values.is_empty() || field.is_some_and(|field| values.iter().any(|value| value == field));
+        matches(&self.schemas, schema) && matches(&self.attesters, Some(attester)) && matches(&self.recipients, recipient)
+    }
+}
+
+/// Parses the `groups` param: `;` separated `<name>:<criterion>:<criterion>...` groups, with `schema=`, `attester=`
+/// and `recipient=` criteria listing comma separated hex values. A group without criteria matches every event.
+fn parse_groups(groups: &str) -> Result<Vec<FilterGroup>, Error> {
+    groups
+        .split(';')
+        .map(str::trim)
+        .filter(|group| !group.is_empty())
+        .map(|group| {
+            let invalid = |reason: &str| Error::msg(format!("invalid group '{}' for param 'groups': {}", group, reason));
+            let mut parts = group.split(':');
+            let name = parts
+                .next()
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .ok_or_else(|| invalid("missing name"))?;
+            let mut filter_group = FilterGroup {
+                name: name.to_string(),
+                schemas: vec![],
+                attesters: vec![],
+                recipients: vec![],
+            };
+            for criterion in parts {
+                let (field, values) = criterion.split_once('=').ok_or_else(|| invalid("expected <field>=<values>"))?;
+                let values = values
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty())
+                    .map(|value| hex::decode(value.trim_start_matches("0x")).map_err(|_| invalid(&format!("invalid hex value '{}'", value))))
+                    .collect::<Result<Vec<_>, _>>()?;
+                match field.trim() {
+                    "schema" => filter_group.schemas.extend(values),
+                    "attester" => filter_group.attesters.extend(values),
+                    "recipient" => filter_group.recipients.extend(values),
+                    field => return Err(invalid(&format!("unknown field '{}'", field))),
+                }
+            }
+            Ok(filter_group)
+        })
+        .collect()
+}
+
+/// Names of the groups matched by an event, in param order.
+fn matching_groups(groups: &[FilterGroup], schema: Option<&[u8]>, attester: &[u8], recipient: Option<&[u8]>) -> Vec<String> {
+    groups
+        .iter()
+        .filter(|group| group.matches(schema, attester, recipient))
+        .map(|group| group.name.clone())
+        .collect()
+}
+
+/// Keeps the events matched by at least one of the filter groups of the `groups` param, each tagged with the names of
+/// the groups it matches, so a single deployment serves the subscriptions of several consumers. Offchain revocations
+/// match on their revoker as attester, timestamps only match groups without criteria.
+#[substreams::handlers::map]
+fn map_grouped_events(params: String, events: contract::Events) -> Result<contract::GroupedEvents, substreams::errors::Error> {
+    let groups = parse_groups(Params::parse(&params).get("groups").unwrap_or_default())?;
+
+    let attesteds = events
+        .eas_attesteds
+        .into_iter()
+        .map(|event| contract::GroupedAttested {
+            groups: matching_groups(&groups, Some(&event.schema_id), &event.attester, Some(&event.recipient)),
+            event: Some(event),
+        })
+        .filter(|grouped| !grouped.groups.is_empty())
+        .collect();
+    let revokeds = events
+        .eas_revokeds
+        .into_iter()
+        .map(|event| contract::GroupedRevoked {
+            groups: matching_groups(&groups, Some(&event.schema), &event.attester, Some(&event.recipient)),
+            event: Some(event),
+        })
+        .filter(|grouped| !grouped.groups.is_empty())
+        .collect();
+    let revoked_offchains = events
+        .eas_revoked_offchains
+        .into_iter()
+        .map(|event| contract::GroupedRevokedOffchain {
+            groups: matching_groups(&groups, None, &event.revoker, None),
+            event: Some(event),
+        })
+        .filter(|grouped| !grouped.groups.is_empty())
+        .collect();
+    let timestampeds = events
+        .eas_timestampeds
+        .into_iter()
+        .map(|event| contract::GroupedTimestamped {
+            groups: matching_groups(&groups, None, &[], None),
+            event: Some(event),
+        })
+        .filter(|grouped| !grouped.groups.is_empty())
+        .collect();
+
+    Ok(contract::GroupedEvents {
+        attesteds,
+        revokeds,
+        revoked_offchains,
+        timestampeds,
+        chain_id: events.chain_id,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn events_are_tagged_with_their_matching_groups() {
+        let groups = parse_groups("team_a:schema=0x01,0x02;team_b:attester=0x0a:recipient=0x0b; all").unwrap();
+        assert_eq!(groups.len(), 3);
+
+        let tags = |schema: &[u8], attester: &[u8], recipient: &[u8]| matching_groups(&groups, Some(schema), attester, Some(recipient));
+        assert_eq!(tags(&hex!("02"), &hex!("0a"), &hex!("0b")), ["team_a", "team_b", "all"]);
+        assert_eq!(tags(&hex!("03"), &hex!("0a"), &hex!("0c")), ["all"]);
+        // criteria on fields the event does not carry never match
+        assert_eq!(matching_groups(&groups, None, &hex!("0a"), None), ["all"]);
+
+        assert!(parse_groups("team_a:issuer=0x01").is_err());
+        assert!(parse_groups(":schema=0x01").is_err());
+        assert!(parse_groups("team_a:schema=0xzz").is_err());
+    }
+}
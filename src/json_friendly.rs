@@ -0,0 +1,172 @@
+use crate::params::Params;
+use crate::pb::contract::v1 as contract;
+use crate::schema_parser::RenderOptions;
+
+/// Renders bytes as lowercase hex, empty bytes as an empty string.
+pub fn hex(options: &RenderOptions, bytes: &[u8]) -> String {
+    match bytes.is_empty() {
+        true => String::new(),
+        false => options.hex(bytes),
+    }
+}
+
+fn attested(options: &RenderOptions, attested: contract::EasAttested) -> contract::EasAttestedHex {
+    contract::EasAttestedHex {
+        evt_tx_hash: hex(options, &attested.evt_tx_hash),
+        evt_index: attested.evt_index,
+        evt_address: hex(options, &attested.evt_address),
+        evt_tx_ordinal: attested.evt_tx_ordinal,
+        evt_block_time: attested.evt_block_time,
+        evt_block_number: attested.evt_block_number,
+        recipient: hex(options, &attested.recipient),
+        attester: hex(options, &attested.attester),
+        attester_label: attested.attester_label,
+        uid: hex(options, &attested.uid),
+        schema_id: hex(options, &attested.schema_id),
+        data: hex(options, &attested.data),
+        schema: attested.schema,
+        decoded_data: attested.decoded_data,
+        decoded_data_compressed: hex(options, &attested.decoded_data_compressed),
+        evt_block_timestamp_seconds: attested.evt_block_timestamp_seconds,
+        aa_sender: hex(options, &attested.aa_sender),
+        bundler: hex(options, &attested.bundler),
+        enrichment_failed: attested.enrichment_failed,
+        decoded_values: attested.decoded_values,
+        data_keccak: hex(options, &attested.data_keccak),
+        schema_registered_block: attested.schema_registered_block,
+        schema_registered_time: attested.schema_registered_time,
+        attestation_time: attested.attestation_time,
+        expiration_time: attested.expiration_time,
+        is_expired_at_emission: attested.is_expired_at_emission,
+        time_mismatch: attested.time_mismatch,
+        schema_field_count: attested.schema_field_count,
+        schema_field_types: attested.schema_field_types,
+        tx_nonce: attested.tx_nonce,
+        tx_method_selector: hex(options, &attested.tx_method_selector),
+        extra_data_bytes: attested.extra_data_bytes,
+        extra_data_hex: attested.extra_data_hex,
+        missing_fields: attested.missing_fields,
+        ref_uid: hex(options, &attested.ref_uid),
+        schema_shape_id: hex(options, &attested.schema_shape_id),
+        module_version: attested.module_version,
+        decoding_ruleset_version: attested.decoding_ruleset_version,
+        revocability_conflict: attested.revocability_conflict,
+        resolver_call_rejected: attested.resolver_call_rejected,
+        schema_handle: attested.schema_handle,
+        raw_attestation: attested.raw_attestation,
+    }
+}
+
+fn revoked(options: &RenderOptions, revoked: contract::EasRevoked) -> contract::EasRevokedHex {
+    contract::EasRevokedHex {
+        evt_tx_hash: hex(options, &revoked.evt_tx_hash),
+        evt_index: revoked.evt_index,
+        evt_address: hex(options, &revoked.evt_address),
+        evt_tx_ordinal: revoked.evt_tx_ordinal,
+        evt_block_time: revoked.evt_block_time,
+        evt_block_number: revoked.evt_block_number,
+        recipient: hex(options, &revoked.recipient),
+        attester: hex(options, &revoked.attester),
+        attester_label: revoked.attester_label,
+        uid: hex(options, &revoked.uid),
+        schema: hex(options, &revoked.schema),
+        evt_block_timestamp_seconds: revoked.evt_block_timestamp_seconds,
+        delegation_signer: hex(options, &revoked.delegation_signer),
+        resolver_context: revoked
+            .resolver_context
+            .into_iter()
+            .map(|resolver_log| contract::ResolverLogHex {
+                address: hex(options, &resolver_log.address),
+                topics: resolver_log.topics,
+                data: resolver_log.data,
+            })
+            .collect(),
+        module_version: revoked.module_version,
+    }
+}
+
+fn revoked_offchain(options: &RenderOptions, revocation: contract::EasRevokedOffchain) -> contract::EasRevokedOffchainHex {
+    contract::EasRevokedOffchainHex {
+        evt_tx_hash: hex(options, &revocation.evt_tx_hash),
+        evt_index: revocation.evt_index,
+        evt_address: hex(options, &revocation.evt_address),
+        evt_tx_ordinal: revocation.evt_tx_ordinal,
+        evt_block_time: revocation.evt_block_time,
+        evt_block_number: revocation.evt_block_number,
+        revoker: hex(options, &revocation.revoker),
+        data: hex(options, &revocation.data),
+        timestamp: revocation.timestamp,
+        evt_block_timestamp_seconds: revocation.evt_block_timestamp_seconds,
+        module_version: revocation.module_version,
+    }
+}
+
+fn timestamped(options: &RenderOptions, timestamped: contract::EasTimestamped) -> contract::EasTimestampedHex {
+    contract::EasTimestampedHex {
+        evt_tx_hash: hex(options, &timestamped.evt_tx_hash),
+        evt_index: timestamped.evt_index,
+        evt_address: hex(options, &timestamped.evt_address),
+        evt_tx_ordinal: timestamped.evt_tx_ordinal,
+        evt_block_time: timestamped.evt_block_time,
+        evt_block_number: timestamped.evt_block_number,
+        data: hex(options, &timestamped.data),
+        timestamp: timestamped.timestamp,
+        batch_index: timestamped.batch_index,
+        batch_size: timestamped.batch_size,
+        evt_block_timestamp_seconds: timestamped.evt_block_timestamp_seconds,
+        module_version: timestamped.module_version,
+    }
+}
+
+/// Same output as `map_events`, with every bytes field (addresses, UIDs, hashes, data) rendered as a lowercase hex
+/// string, for sinks that mangle raw bytes fields. `hex_prefix` (param, default `true`) controls the `0x` prefix.
+#[substreams::handlers::map]
+fn map_events_json_friendly(params: String, events: contract::Events) -> Result<contract::EventsHex, substreams::errors::Error> {
+    let options = RenderOptions {
+        hex_prefix: Params::parse(&params).get_or("hex_prefix", true)?,
+        ..Default::default()
+    };
+
+    Ok(contract::EventsHex {
+        eas_attesteds: events.eas_attesteds.into_iter().map(|event| attested(&options, event)).collect(),
+        eas_revokeds: events.eas_revokeds.into_iter().map(|event| revoked(&options, event)).collect(),
+        eas_revoked_offchains: events
+            .eas_revoked_offchains
+            .into_iter()
+            .map(|event| revoked_offchain(&options, event))
+            .collect(),
+        eas_timestampeds: events.eas_timestampeds.into_iter().map(|event| timestamped(&options, event)).collect(),
+        touched_keys: events.touched_keys,
+        final_blocks_only: events.final_blocks_only,
+        chain_id: events.chain_id,
+        metrics: events.metrics,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytes_become_hex_and_metrics_are_kept() {
+        let events = contract::Events {
+            eas_revokeds: vec![contract::EasRevoked {
+                uid: vec![0xab; 2],
+                ..Default::default()
+            }],
+            chain_id: 8453,
+            metrics: Some(contract::PipelineMetrics {
+                block_number: 6_000,
+                revoked_total: 1,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let hex = map_events_json_friendly("hex_prefix=false".to_string(), events.clone()).unwrap();
+
+        assert_eq!(hex.eas_revokeds[0].uid, "abab");
+        assert_eq!(hex.eas_revokeds[0].attester, "");
+        assert_eq!(hex.chain_id, 8453);
+        assert_eq!(hex.metrics, events.metrics);
+    }
+}
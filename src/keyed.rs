@@ -0,0 +1,59 @@
+use crate::params::Params;
+use crate::pb::contract::v1 as contract;
+use crate::schema_parser::RenderOptions;
+use std::str::FromStr;
+
+/// Field used as partition key by `map_keyed_events`, set with the `partition_key` param.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum PartitionKey {
+    /// Schema UID, preserving the order of the events of each schema.
+    #[default]
+    Schema,
+    /// Recipient address, preserving the order of the events of each recipient.
+    Recipient,
+}
+
+impl FromStr for PartitionKey {
+    type Err = String;
+    fn from_str(key: &str) -> Result<Self, Self::Err> {
+        match key {
+            "schema" => Ok(PartitionKey::Schema),
+            "recipient" => Ok(PartitionKey::Recipient),
+            _ => Err(format!("Unsupported partition key: {}", key)),
+        }
+    }
+}
+
+/// Emits the attestations and onchain revocations of the block in log order, each carrying an explicit partition key
+/// (hex-encoded schema UID or recipient, per the `partition_key` param) so message-queue sinks preserve per-key ordering.
+/// Offchain revocations and timestamps are left out, having neither schema nor recipient.
+#[substreams::handlers::map]
+fn map_keyed_events(params: String, events: contract::Events) -> Result<contract::KeyedEvents, substreams::errors::Error> {
+    let partition_key: PartitionKey = Params::parse(&params).get_or("partition_key", PartitionKey::Schema)?;
+    let options = RenderOptions::default();
+    let key = |schema: &[u8], recipient: &[u8]| match partition_key {
+        PartitionKey::Schema => options.hex(schema),
+        PartitionKey::Recipient => options.hex(recipient),
+    };
+
+    let attesteds = events.eas_attesteds.into_iter().map(|attested| contract::KeyedEvent {
+        partition_key: key(&attested.schema_id, &attested.recipient),
+        evt_index: attested.evt_index,
+        attested: Some(attested),
+        revoked: None,
+    });
+    let revokeds = events.eas_revokeds.into_iter().map(|revoked| contract::KeyedEvent {
+        partition_key: key(&revoked.schema, &revoked.recipient),
+        evt_index: revoked.evt_index,
+        attested: None,
+        revoked: Some(revoked),
+    });
+
+    let mut keyed_events: Vec<_> = attesteds.chain(revokeds).collect();
+    keyed_events.sort_by_key(|keyed| keyed.evt_index);
+
+    Ok(contract::KeyedEvents {
+        keyed_events,
+        chain_id: events.chain_id,
+    })
+}
@@ -1,51 +1,267 @@
 mod abi;
+mod activity;
+mod admin;
+mod by_schema;
+mod calls;
+mod display;
+mod duplicates;
+mod expirations;
+mod failures;
+mod field_stats;
+mod groups;
+mod json_friendly;
+mod keyed;
+mod metrics;
+mod networks;
+mod params;
 mod pb;
+mod recipients;
+mod reconciliation;
+mod references;
+mod resolvers;
+mod revocations;
+mod rpc;
 mod schema_parser;
-use abi::eas_contract::functions::GetAttestation;
-use abi::eas_schema_registry_contract::functions::GetSchema;
-use ethabi::decode;
+mod schema_resolver;
+mod schemas;
+mod status;
+#[cfg(test)]
+mod testing;
+mod timestamps;
+mod webhooks;
+use abi::eas_contract::functions::MultiTimestamp;
+pub use display::ToHex;
+use ethabi::{decode, ParamType, Token};
 use hex_literal::hex;
+pub use networks::{deployment_block, Network, NETWORKS};
+use params::Params;
 use pb::contract::v1 as contract;
+use rpc::{EasRpc, RpcFailure};
+pub use schema_parser::FieldType;
+use schema_parser::{BinaryEncoding, KeyStyle, RenderOptions};
+use schema_resolver::{SchemaResolver, SchemaSnapshot, SchemaSource};
 use serde_json::{Map, Value};
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, BTreeSet};
+use std::str::FromStr;
+use substreams::store::StoreGetProto;
+use substreams::Hex;
 use substreams_ethereum::pb::eth::v2 as eth;
-use substreams_ethereum::rpc::RpcBatch;
-use substreams_ethereum::Event;
+use substreams_ethereum::{Event, Function};
+use tiny_keccak::{Hasher, Keccak};
 
 substreams_ethereum::init!();
 
 const EAS_TRACKED_CONTRACT: [u8; 20] = hex!("4200000000000000000000000000000000000021");
 const EAS_SCHEMA_REGISTRY_CONTRACT: [u8; 20] = hex!("4200000000000000000000000000000000000020");
 
+/// Crate version stamped on every event as `module_version`.
+const MODULE_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// Version of the attestation decoding rules (schema parsing, ABI decoding, JSON rendering) stamped on every
+/// attestation, bumped whenever a change alters `decoded_data` or `decoded_values` for the same input, so that rows
+/// decoded under older rules can be targeted for reprocessing. The `decoding_ruleset_v<N>` golden test pins the output
+/// of each version.
+///
+/// 1. Initial rules.
+/// 2. Data longer than the schema encoding is decoded up to it, the rest being reported as extra data.
+/// 3. Single pass schema parser.
+/// 4. `decoded_data` written straight from the decoded tokens.
+/// 5. Extra data holds the heads of appended fields rather than the tails of the declared ones.
+/// 6. Whitespace accepted before array suffixes.
+const DECODING_RULESET_VERSION: u32 = 6;
+
 /// Decodes ABI-encoded attestation data into a JSON map using the schema signature string.
 /// Returns a Map with error information if the schema or data cannot be decoded.
 pub fn decode_data(data: &[u8], schema_signature: &str) -> Map<String, Value> {
-    // Strip outer parentheses if the entire schema is wrapped in them
+    decode_data_with_options(data, schema_signature, &RenderOptions::default())
+}
+
+/// Same as [`decode_data`], rendering the decoded values with the given options.
+pub fn decode_data_with_options(data: &[u8], schema_signature: &str, options: &RenderOptions) -> Map<String, Value> {
+    try_decode_data(data, schema_signature, options).unwrap_or_else(|error| Map::from_iter([("error".to_string(), Value::String(error.to_string()))]))
+}
+
+/// Same as [`decode_data_with_options`], returning the error message when the schema or data cannot be decoded.
+fn try_decode_data(data: &[u8], schema_signature: &str, options: &RenderOptions) -> Result<Map<String, Value>, &'static str> {
+    let fields = parse_schema(schema_signature).map_err(|_| "Invalid schema")?;
+    Ok(Map::from_iter(decode_fields(data, &fields, options)?.fields(&fields, options)))
+}
+
+/// Decodes hex-encoded attestation data (`0x` prefix optional) with a schema signature into the JSON text of
+/// `decoded_data`, going through the same decoding as `map_events` with the default params. Meant for debugging
+/// onchain payloads from native tools and tests, hence left out of the wasm module.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn decode_attestation_json(data_hex: &str, schema: &str) -> Result<String, String> {
+    let data = hex::decode(data_hex.trim().trim_start_matches("0x")).map_err(|error| format!("Invalid data hex: {}", error))?;
+    let decoded = try_decode_data(&data, schema, &RenderOptions::default())?;
+    Ok(Value::Object(decoded).to_string())
+}
+
+/// Fuzzing entry point (`cargo fuzz run parse_schema`): schema strings are attacker controlled on-chain.
+#[cfg(fuzzing)]
+pub fn fuzz_parse_schema(data: &[u8]) {
+    if let Ok(schema_signature) = std::str::from_utf8(data) {
+        let _ = parse_schema(schema_signature);
+    }
+}
+
+/// Fuzzing entry point (`cargo fuzz run decode_data`): the input is a schema string, a newline, then the
+/// ABI-encoded attestation data.
+#[cfg(fuzzing)]
+pub fn fuzz_decode_data(data: &[u8]) {
+    let Some(newline) = data.iter().position(|byte| *byte == b'\n') else {
+        return;
+    };
+    if let Ok(schema_signature) = std::str::from_utf8(&data[..newline]) {
+        let _ = decode_data(&data[newline + 1..], schema_signature);
+    }
+}
+
+/// Parsed `(type, name)` fields of a schema, or the parse error.
+type ParsedSchema = Result<Vec<(FieldType, String)>, String>;
+
+/// Parses the fields of a schema signature, stripping the outer parentheses if the entire schema is wrapped in them.
+pub fn parse_schema(schema_signature: &str) -> ParsedSchema {
     let schema_signature = if schema_signature.starts_with('(') && schema_signature.ends_with(')') && schema_signature.len() > 2 {
         &schema_signature[1..schema_signature.len() - 1]
     } else {
         schema_signature
     };
+    schema_parser::parse_schema_fields(schema_signature)
+}
 
-    // Try to parse the schema fields
-    match schema_parser::parse_schema_fields(schema_signature) {
-        Ok(fields) => {
-            let types = fields.iter().map(|(t, _)| schema_parser::fieldtype_to_paramtype(t)).collect::<Vec<_>>();
-            match decode(&types, data) {
-                Ok(tokens) => fields.into_iter().zip(tokens.into_iter()).fold(Map::new(), |mut res, ((ft, name), token)| {
-                    res.insert(name, schema_parser::token_to_json_with_schema(&ft, &token));
-                    res
-                }),
-                Err(e) => {
-                    substreams::log::info!("Error decoding data with schema {}: {:?}", schema_signature, e);
-                    Map::from_iter([("error".to_string(), Value::String("Invalid data".to_string()))])
-                }
-            }
+/// Attestation data decoded with the parsed fields of its schema.
+struct DecodedData {
+    /// Tokens of the decoded fields in field order, those of the leading complete fields for truncated data.
+    tokens: Vec<Token>,
+    /// Bytes outside the encoding of the schema fields, e.g. fields appended to the data by a later, informal version
+    /// of the schema: the heads found between the heads and the tails of the schema fields, then the bytes past the
+    /// last tail.
+    extra_data: Vec<u8>,
+    /// Keys of the trailing fields missing from truncated data, rendered as `null`.
+    missing_fields: Vec<String>,
+}
+
+impl DecodedData {
+    /// `(key, value)` pairs in field order, the missing fields being `null`.
+    fn fields(&self, fields: &[(FieldType, String)], options: &RenderOptions) -> Vec<(String, Value)> {
+        fields
+            .iter()
+            .zip(self.tokens.iter().map(Some).chain(std::iter::repeat(None)))
+            .map(|((ft, name), token)| {
+                let value = token.map_or(Value::Null, |token| schema_parser::token_to_json_with_schema(ft, token, options));
+                (options.key_style.apply(name), value)
+            })
+            .collect()
+    }
+}
+
+/// Decodes ABI-encoded attestation data with the parsed fields of its schema. Data longer than the encoding of the
+/// schema fields is decoded up to that encoding, keeping the bytes outside of it as extra data. Truncated data is
+/// decoded up to the last complete leading field, the missing ones being rendered as `null`.
+fn decode_fields(data: &[u8], fields: &[(FieldType, String)], options: &RenderOptions) -> Result<DecodedData, &'static str> {
+    let types = fields.iter().map(|(t, _)| schema_parser::fieldtype_to_paramtype(t)).collect::<Vec<_>>();
+    let (tokens, extra_data) = match decode(&types, data) {
+        Ok(tokens) => {
+            let extra_data = extra_data(data, &types, &tokens);
+            (tokens, extra_data)
         }
-        Err(e) => {
-            substreams::log::info!("Error parsing schema {}: {:?}", schema_signature, e);
-            Map::from_iter([("error".to_string(), Value::String("Invalid schema".to_string()))])
+        // the heads of the leading fields are laid out the same whatever the number of fields
+        Err(_) => {
+            let tokens = (1..types.len())
+                .rev()
+                .find_map(|count| decode(&types[..count], data).ok())
+                .ok_or("Invalid data")?;
+            (tokens, vec![])
+        }
+    };
+
+    let missing_fields: Vec<String> = fields[tokens.len()..].iter().map(|(_, name)| options.key_style.apply(name)).collect();
+    Ok(DecodedData {
+        tokens,
+        extra_data,
+        missing_fields,
+    })
+}
+
+/// Size of the head of a value of the given type: its encoding for static types, an offset word for dynamic ones.
+fn head_size(param_type: &ParamType) -> usize {
+    match param_type {
+        _ if param_type.is_dynamic() => 32,
+        ParamType::Tuple(types) => types.iter().map(head_size).sum(),
+        ParamType::FixedArray(inner, len) => head_size(inner) * len,
+        _ => 32,
+    }
+}
+
+/// Bytes of `data` outside the encoding of the decoded fields. Fields appended to the data put their heads after the
+/// heads of the decoded fields, before the tails of the dynamic ones, and their own tails after those: the extra data
+/// is the gap between the heads and the first tail, followed by the bytes past the furthest tail end.
+fn extra_data(data: &[u8], types: &[ParamType], tokens: &[Token]) -> Vec<u8> {
+    let heads_end = types.iter().map(head_size).sum::<usize>();
+    let mut head = 0;
+    let mut tails: Vec<(usize, usize)> = vec![];
+    for (param_type, token) in types.iter().zip(tokens) {
+        if param_type.is_dynamic() {
+            // decoding succeeded: the offset word is in the data and fits
+            let offset = data
+                .get(head + 24..head + 32)
+                .and_then(|word| word.try_into().ok())
+                .map(u64::from_be_bytes)
+                .unwrap_or_default() as usize;
+            // the encoding of a lone dynamic value is its offset word followed by its tail
+            tails.push((offset, offset + ethabi::encode(std::slice::from_ref(token)).len() - 32));
         }
+        head += head_size(param_type);
     }
+    let tails_start = tails.iter().map(|(start, _)| *start).min().unwrap_or(heads_end);
+    let tails_end = tails.iter().map(|(_, end)| *end).max().unwrap_or(heads_end);
+    [
+        data.get(heads_end..tails_start.max(heads_end)).unwrap_or_default(),
+        data.get(tails_end..).unwrap_or_default(),
+    ]
+    .concat()
+}
+
+/// Keccak-256 hash of the given bytes.
+fn keccak256(data: &[u8]) -> [u8; 32] {
+    let mut hash = [0u8; 32];
+    let mut hasher = Keccak::v256();
+    hasher.update(data);
+    hasher.finalize(&mut hash);
+    hash
+}
+
+/// HMAC (RFC 2104) with keccak256 as hash function, whose block size is 136 bytes.
+fn hmac_keccak256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    const BLOCK_SIZE: usize = 136;
+    let mut block_key = [0u8; BLOCK_SIZE];
+    match key.len() > BLOCK_SIZE {
+        true => block_key[..32].copy_from_slice(&keccak256(key)),
+        false => block_key[..key.len()].copy_from_slice(key),
+    }
+    let padded = |pad: u8| block_key.iter().map(|byte| byte ^ pad).collect::<Vec<_>>();
+    let inner = keccak256(&[padded(0x36), message.to_vec()].concat());
+    keccak256(&[padded(0x5c), inner.to_vec()].concat())
+}
+
+/// Fingerprint of the decoded-data shape of a schema: keccak256 of its canonical field list, shared by schemas with
+/// identical field names and types whatever their UID, resolver or revocability.
+fn schema_shape_id(fields: &[(FieldType, String)]) -> [u8; 32] {
+    keccak256(schema_parser::canonical_schema(fields).as_bytes())
+}
+
+/// Per-block counters of the attestation enrichment and data decoding, logged as a single summary line and
+/// reported in the pipeline metrics.
+#[derive(Debug, Default)]
+struct DecodeStats {
+    schemas_parsed: usize,
+    cache_hits: usize,
+    decode_failures: usize,
+    /// `eth_call`s issued, one per attestation and schema fetched.
+    rpc_calls: usize,
+    /// Bytes of attestation data run through the decoder.
+    decoded_bytes: usize,
 }
 
 #[derive(Debug, Clone)]
@@ -62,6 +278,28 @@ pub struct Attestation {
     pub data: Vec<u8>,
 }
 
+impl Attestation {
+    /// The attestation record as returned by `getAttestation`, with its bytes and address fields hex-encoded, for
+    /// auditing the enrichment against the decoded fields. The data of redacted schemas is left empty.
+    fn to_raw(&self, options: &RenderOptions, redacted: bool) -> contract::RawAttestation {
+        contract::RawAttestation {
+            uid: options.hex(&self.uid),
+            schema: options.hex(&self.schema),
+            time: self.time,
+            expiration_time: self.expiration_time,
+            revocation_time: self.revocation_time,
+            ref_uid: options.hex(&self.ref_uid),
+            recipient: options.hex(&self.recipient),
+            attester: options.hex(&self.attester),
+            revocable: self.revocable,
+            data: match redacted {
+                true => String::new(),
+                false => options.hex(&self.data),
+            },
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Schema {
     pub uid_id: [u8; 32],
@@ -70,198 +308,1180 @@ pub struct Schema {
     pub schema: String,
 }
 
-fn extract_attesteds(blk: &eth::Block, events: &mut contract::Events) {
-    let attested_events: Vec<_> = blk
-        .receipts()
-        .flat_map(|view| {
-            view.receipt
-                .logs
-                .iter()
-                .filter(|log| log.address == EAS_TRACKED_CONTRACT)
-                .filter_map(move |log| {
-                    if let Some(event) = abi::eas_contract::events::Attested::match_and_decode(log) {
-                        Some((view, log, event))
-                    } else {
-                        None
-                    }
-                })
+/// Where the attestation records behind `Attested` events are read from.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum AttestationSource {
+    /// `getAttestation` RPC calls to the EAS contract.
+    #[default]
+    Rpc,
+    /// Calldata of the `attest*` calls found in the transaction call traces, falling back to RPC when not found.
+    Calldata,
+}
+
+impl FromStr for AttestationSource {
+    type Err = String;
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        match source {
+            "rpc" => Ok(AttestationSource::Rpc),
+            "calldata" => Ok(AttestationSource::Calldata),
+            _ => Err(format!("Unsupported attestation source: {}", source)),
+        }
+    }
+}
+
+/// Schemas whose attestation data gets decoded, set with the `decode_schemas` allowlist and `skip_decode_schemas`
+/// denylist params. Attestations of other schemas keep their raw data, without any schema fetch or decoding.
+#[derive(Debug, Clone, Default)]
+pub struct DecodeFilter {
+    decode_schemas: Option<BTreeSet<[u8; 32]>>,
+    skip_decode_schemas: BTreeSet<[u8; 32]>,
+}
+
+impl DecodeFilter {
+    fn from_params(params: &Params) -> Result<Self, substreams::errors::Error> {
+        let decode_schemas = params.get_list("decode_schemas");
+        Ok(DecodeFilter {
+            decode_schemas: match decode_schemas.is_empty() {
+                true => None,
+                false => Some(parse_schema_ids("decode_schemas", &decode_schemas)?),
+            },
+            skip_decode_schemas: parse_schema_ids("skip_decode_schemas", &params.get_list("skip_decode_schemas"))?,
         })
-        .collect();
+    }
 
-    let attestations = attested_events
-        .chunks(100)
-        .flat_map(|chunk| {
-            let responses = chunk
-                .iter()
-                .fold(RpcBatch::new(), |batch, (_, _, event)| {
-                    batch.add(GetAttestation { uid: event.uid }, EAS_TRACKED_CONTRACT.to_vec())
-                })
-                .execute()
-                .expect("failed to execute GetAttestation RPC batch")
-                .responses;
-
-            responses.into_iter().map(|response| {
-                RpcBatch::decode::<
-                    (
-                        [u8; 32],                   // uid
-                        [u8; 32],                   // schema
-                        substreams::scalar::BigInt, // recipient
-                        substreams::scalar::BigInt, // attester
-                        substreams::scalar::BigInt, // time
-                        [u8; 32],                   // expirationTime
-                        Vec<u8>,                    // refUID
-                        Vec<u8>,                    // resolver
-                        bool,                       // revocable
-                        Vec<u8>,                    // data
-                    ),
-                    GetAttestation,
-                >(&response)
-                .expect("failed to decode GetAttestation response")
-            })
+    pub fn should_decode(&self, schema_id: &[u8; 32]) -> bool {
+        self.decode_schemas.as_ref().is_none_or(|schemas| schemas.contains(schema_id)) && !self.skip_decode_schemas.contains(schema_id)
+    }
+}
+
+pub fn parse_schema_ids(param: &str, values: &[&str]) -> Result<BTreeSet<[u8; 32]>, substreams::errors::Error> {
+    values
+        .iter()
+        .map(|value| {
+            hex::decode(value.trim_start_matches("0x"))
+                .ok()
+                .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+                .ok_or_else(|| substreams::errors::Error::msg(format!("invalid schema UID '{}' for param '{}'", value, param)))
         })
-        .collect::<Vec<_>>();
+        .collect()
+}
 
-    let schema_ids: Vec<_> = attestations
+fn parse_addresses(param: &str, values: &[&str]) -> Result<Vec<[u8; 20]>, substreams::errors::Error> {
+    values
         .iter()
-        .map(|attestation| attestation.1)
-        .collect::<std::collections::HashSet<_>>()
-        .into_iter()
-        .collect();
+        .map(|value| {
+            hex::decode(value.trim_start_matches("0x"))
+                .ok()
+                .and_then(|bytes| <[u8; 20]>::try_from(bytes).ok())
+                .ok_or_else(|| substreams::errors::Error::msg(format!("invalid address '{}' for param '{}'", value, param)))
+        })
+        .collect()
+}
 
-    let schemas: std::collections::HashMap<[u8; 32], String> = schema_ids
-        .chunks(100)
-        .flat_map(|chunk| {
-            let responses = chunk
-                .iter()
-                .fold(RpcBatch::new(), |batch, schema_id| {
-                    batch.add(GetSchema { uid: *schema_id }, EAS_SCHEMA_REGISTRY_CONTRACT.to_vec())
-                })
-                .execute()
-                .expect("failed to execute GetSchema RPC batch")
-                .responses;
-
-            responses.into_iter().map(|response| {
-                let schema = RpcBatch::decode::<
-                    (
-                        [u8; 32], // uid
-                        Vec<u8>,  // resolver
-                        bool,     // revocable
-                        String,   // schema
-                    ),
-                    GetSchema,
-                >(&response)
-                .expect("failed to decode GetSchema response");
-
-                (schema.0, schema.3)
+/// Decimals of token-amount-like fields, set with the `scale_decimals` param (comma separated
+/// `<schema_uid>:<field>:<decimals>` entries). Each listed field of the decoded data gets a `<field>_scaled` companion
+/// holding its value divided by `10^decimals`, as a decimal string.
+#[derive(Debug, Clone, Default)]
+pub struct ScaleHints {
+    decimals: BTreeMap<[u8; 32], Vec<(String, u32)>>,
+}
+
+impl ScaleHints {
+    fn from_params(params: &Params) -> Result<Self, substreams::errors::Error> {
+        let mut decimals: BTreeMap<[u8; 32], Vec<(String, u32)>> = BTreeMap::new();
+        for hint in params.get_list("scale_decimals") {
+            let invalid = || substreams::errors::Error::msg(format!("invalid hint '{}' for param 'scale_decimals'", hint));
+            let mut parts = hint.split(':');
+            let (Some(schema_id), Some(field), Some(field_decimals), None) = (parts.next(), parts.next(), parts.next(), parts.next()) else {
+                return Err(invalid());
+            };
+            let schema_id = parse_schema_ids("scale_decimals", &[schema_id])?.into_iter().next().ok_or_else(invalid)?;
+            let field_decimals = field_decimals.parse().map_err(|_| invalid())?;
+            decimals.entry(schema_id).or_default().push((field.to_string(), field_decimals));
+        }
+        Ok(ScaleHints { decimals })
+    }
+
+    /// Whether the schema has hinted fields.
+    fn applies_to(&self, schema_id: &[u8; 32]) -> bool {
+        self.decimals.contains_key(schema_id)
+    }
+
+    /// Inserts the scaled companion of every hinted field of the schema after the field itself.
+    fn apply(&self, schema_id: &[u8; 32], decoded: Vec<(String, Value)>, options: &RenderOptions) -> Vec<(String, Value)> {
+        let Some(hints) = self.decimals.get(schema_id) else {
+            return decoded;
+        };
+        decoded
+            .into_iter()
+            .flat_map(|(key, value)| {
+                let scaled = hints
+                    .iter()
+                    .find(|(field, _)| options.key_style.apply(field) == key)
+                    .and_then(|(field, decimals)| {
+                        let scaled = scale_decimal(value.as_str()?, *decimals)?;
+                        Some((options.key_style.apply(&format!("{}_scaled", field)), Value::String(scaled)))
+                    });
+                std::iter::once((key, value)).chain(scaled)
             })
+            .collect()
+    }
+}
+
+/// Inserts a `<field>_compact` companion after every `bool` or `address` array field holding at least `min_len`
+/// values, see `schema_parser::compact_array`.
+fn compact_arrays(schema_fields: &[(FieldType, String)], decoded: Vec<(String, Value)>, min_len: usize, options: &RenderOptions) -> Vec<(String, Value)> {
+    decoded
+        .into_iter()
+        .flat_map(|(key, value)| {
+            let compact = schema_fields
+                .iter()
+                .find(|(_, name)| options.key_style.apply(name) == key)
+                .filter(|_| value.as_array().is_some_and(|values| values.len() >= min_len))
+                .and_then(|(field_type, name)| {
+                    let compact = schema_parser::compact_array(field_type, &value)?;
+                    Some((options.key_style.apply(&format!("{}_compact", name)), compact))
+                });
+            std::iter::once((key, value)).chain(compact)
         })
-        .collect();
+        .collect()
+}
+
+/// Labels of enum-like integer fields, set with the `enum_labels` param (comma separated
+/// `<schema_uid>:<field>=<label0>|<label1>|...` entries). Each listed field of the decoded data gets a `<field>_label`
+/// companion holding the label of its value, `null` when the value has no label (not a valid member of the enum).
+#[derive(Debug, Clone, Default)]
+pub struct EnumLabels {
+    labels: BTreeMap<[u8; 32], Vec<(String, Vec<String>)>>,
+}
 
-    events.eas_attesteds.extend(
-        attested_events
+impl EnumLabels {
+    fn from_params(params: &Params) -> Result<Self, substreams::errors::Error> {
+        let mut labels: BTreeMap<[u8; 32], Vec<(String, Vec<String>)>> = BTreeMap::new();
+        for entry in params.get_list("enum_labels") {
+            let invalid = || substreams::errors::Error::msg(format!("invalid labels '{}' for param 'enum_labels'", entry));
+            let (field, field_labels) = entry.split_once('=').ok_or_else(invalid)?;
+            let (schema_id, field) = field.split_once(':').filter(|(_, field)| !field.is_empty()).ok_or_else(invalid)?;
+            let schema_id = parse_schema_ids("enum_labels", &[schema_id])?.into_iter().next().ok_or_else(invalid)?;
+            let field_labels = field_labels.split('|').map(str::to_string).collect();
+            labels.entry(schema_id).or_default().push((field.to_string(), field_labels));
+        }
+        Ok(EnumLabels { labels })
+    }
+
+    /// Whether the schema has labelled fields.
+    fn applies_to(&self, schema_id: &[u8; 32]) -> bool {
+        self.labels.contains_key(schema_id)
+    }
+
+    /// Inserts the label companion of every labelled field of the schema after the field itself.
+    fn apply(&self, schema_id: &[u8; 32], decoded: Vec<(String, Value)>, options: &RenderOptions) -> Vec<(String, Value)> {
+        let Some(labels) = self.labels.get(schema_id) else {
+            return decoded;
+        };
+        decoded
             .into_iter()
-            .zip(attestations.into_iter())
-            .map(|((view, log, event), attestation)| {
-                let schema = schemas.get(&attestation.1).expect("schema should exist in map");
-                let decoded_json = serde_json::Value::Object(decode_data(&attestation.9, schema));
-
-                contract::EasAttested {
-                    evt_tx_hash: view.transaction.hash.clone(),
-                    evt_index: log.block_index,
-                    evt_block_time: Some(blk.timestamp().to_owned()),
-                    evt_block_number: blk.number,
-                    attester: event.attester,
-                    recipient: event.recipient,
-                    schema_id: Vec::from(event.schema),
-                    uid: Vec::from(event.uid),
-                    data: attestation.9,
-                    schema: schema.to_string(),
-                    decoded_data: decoded_json.to_string(),
-                }
-            }),
-    );
-}
-
-fn extract_revokeds(blk: &eth::Block, events: &mut contract::Events) {
-    events.eas_revokeds.append(
-        &mut blk
-            .receipts()
-            .flat_map(|view| {
-                view.receipt.logs.iter().filter(|log| log.address == EAS_TRACKED_CONTRACT).filter_map(|log| {
-                    if let Some(event) = abi::eas_contract::events::Revoked::match_and_decode(log) {
-                        return Some(contract::EasRevoked {
-                            evt_tx_hash: view.transaction.hash.clone(),
-                            evt_index: log.block_index,
-                            evt_block_time: Some(blk.timestamp().to_owned()),
-                            evt_block_number: blk.number,
-                            attester: event.attester,
-                            recipient: event.recipient,
-                            schema: Vec::from(event.schema),
-                            uid: Vec::from(event.uid),
-                        });
+            .flat_map(|(key, value)| {
+                let label = labels.iter().find(|(field, _)| options.key_style.apply(field) == key).map(|(field, labels)| {
+                    let label = value
+                        .as_str()
+                        .and_then(|value| value.parse::<usize>().ok())
+                        .and_then(|index| labels.get(index))
+                        .map_or(Value::Null, |label| Value::String(label.clone()));
+                    (options.key_style.apply(&format!("{}_label", field)), label)
+                });
+                std::iter::once((key, value)).chain(label)
+            })
+            .collect()
+    }
+}
+
+/// Labels of known attesters, set with the `attester_labels` param (comma separated `<address>:<label>` entries), e.g.
+/// `Coinbase` or `Gitcoin`, set on the events of their attestations and revocations (`attester_label`).
+#[derive(Debug, Clone, Default)]
+pub struct AttesterLabels {
+    labels: BTreeMap<[u8; 20], String>,
+}
+
+impl AttesterLabels {
+    fn from_params(params: &Params) -> Result<Self, substreams::errors::Error> {
+        let mut labels = BTreeMap::new();
+        for entry in params.get_list("attester_labels") {
+            let invalid = || substreams::errors::Error::msg(format!("invalid label '{}' for param 'attester_labels'", entry));
+            let (attester, label) = entry.split_once(':').filter(|(_, label)| !label.is_empty()).ok_or_else(invalid)?;
+            let attester = parse_addresses("attester_labels", &[attester])?.into_iter().next().ok_or_else(invalid)?;
+            labels.insert(attester, label.to_string());
+        }
+        Ok(AttesterLabels { labels })
+    }
+
+    /// Label of the attester, empty for unlabelled ones.
+    fn label(&self, attester: &[u8]) -> String {
+        <[u8; 20]>::try_from(attester)
+            .ok()
+            .and_then(|attester| self.labels.get(&attester).cloned())
+            .unwrap_or_default()
+    }
+}
+
+/// Treatment of a redacted field, `hash` or `omit` in the `redact_fields` param.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Redaction {
+    /// Replace the value with the hex keccak256 of its string form, so that equal values can still be matched.
+    Hash,
+    /// Drop the field from `decoded_data`, `null` in `decoded_values`.
+    Omit,
+}
+
+impl FromStr for Redaction {
+    type Err = String;
+    fn from_str(redaction: &str) -> Result<Self, Self::Err> {
+        match redaction {
+            "hash" => Ok(Redaction::Hash),
+            "omit" => Ok(Redaction::Omit),
+            _ => Err(format!("Unsupported redaction: {}", redaction)),
+        }
+    }
+}
+
+/// Fields redacted from the decoded output, set with the `redact_fields` param (comma separated
+/// `<schema_uid>:<field>[:hash|omit]` entries, `hash` by default), e.g. personal data some schemas carry. The raw
+/// `data` of attestations with redacted fields is dropped as well, as it holds the same values. Hashed fields are keyed
+/// with the `redaction_key` param, the `pseudonym_key` one when unset, so that low-entropy values (emails, phone
+/// numbers) cannot be recovered by hashing candidates.
+#[derive(Debug, Clone, Default)]
+pub struct Redactions {
+    fields: BTreeMap<[u8; 32], Vec<(String, Redaction)>>,
+    key: Option<String>,
+}
+
+impl Redactions {
+    fn from_params(params: &Params) -> Result<Self, substreams::errors::Error> {
+        let mut fields: BTreeMap<[u8; 32], Vec<(String, Redaction)>> = BTreeMap::new();
+        for entry in params.get_list("redact_fields") {
+            let invalid = || substreams::errors::Error::msg(format!("invalid field '{}' for param 'redact_fields'", entry));
+            let mut parts = entry.split(':');
+            let (Some(schema_id), Some(field), redaction, None) = (parts.next(), parts.next(), parts.next(), parts.next()) else {
+                return Err(invalid());
+            };
+            let schema_id = parse_schema_ids("redact_fields", &[schema_id])?.into_iter().next().ok_or_else(invalid)?;
+            let redaction = redaction.map(Redaction::from_str).transpose().map_err(|_| invalid())?;
+            fields
+                .entry(schema_id)
+                .or_default()
+                .push((field.to_string(), redaction.unwrap_or(Redaction::Hash)));
+        }
+        let key = params.get("redaction_key").or_else(|| params.get("pseudonym_key")).map(str::to_string);
+        let hashes = fields.values().flatten().any(|(_, redaction)| *redaction == Redaction::Hash);
+        if hashes && key.is_none() {
+            return Err(substreams::errors::Error::msg(
+                "param 'redact_fields' hashes fields but neither 'redaction_key' nor 'pseudonym_key' is set",
+            ));
+        }
+        Ok(Redactions { fields, key })
+    }
+
+    /// Whether the schema has redacted fields.
+    fn applies_to(&self, schema_id: &[u8; 32]) -> bool {
+        self.fields.contains_key(schema_id)
+    }
+
+    /// Hashes the redacted fields of the schema (HMAC-keccak256 under the redaction key), and replaces the omitted ones with `null` to keep the field positions
+    /// of `decoded_values`.
+    fn apply(&self, schema_id: &[u8; 32], decoded: Vec<(String, Value)>, options: &RenderOptions) -> Vec<(String, Value)> {
+        let Some(redactions) = self.fields.get(schema_id) else {
+            return decoded;
+        };
+        decoded
+            .into_iter()
+            .map(|(key, value)| {
+                let redaction = redactions.iter().find(|(field, _)| options.key_style.apply(field) == key);
+                let value = match redaction {
+                    Some((_, Redaction::Hash)) => {
+                        let plain = match &value {
+                            Value::String(string) => string.clone(),
+                            value => value.to_string(),
+                        };
+                        let key = self.key.as_deref().unwrap_or_default();
+                        Value::String(options.hex(&hmac_keccak256(key.as_bytes(), plain.as_bytes())))
                     }
+                    Some((_, Redaction::Omit)) => Value::Null,
+                    None => value,
+                };
+                (key, value)
+            })
+            .collect()
+    }
 
-                    None
-                })
+    /// Drops the omitted fields of the schema from the decoded fields.
+    fn omit(&self, schema_id: &[u8; 32], decoded: Vec<(String, Value)>, options: &RenderOptions) -> Vec<(String, Value)> {
+        let Some(redactions) = self.fields.get(schema_id) else {
+            return decoded;
+        };
+        decoded
+            .into_iter()
+            .filter(|(key, _)| {
+                !redactions
+                    .iter()
+                    .any(|(field, redaction)| *redaction == Redaction::Omit && options.key_style.apply(field) == *key)
             })
-            .collect(),
-    );
-}
-
-fn extract_revoked_offchains(blk: &eth::Block, events: &mut contract::Events) {
-    events.eas_revoked_offchains.append(
-        &mut blk
-            .receipts()
-            .flat_map(|view| {
-                view.receipt.logs.iter().filter(|log| log.address == EAS_TRACKED_CONTRACT).filter_map(|log| {
-                    if let Some(event) = abi::eas_contract::events::RevokedOffchain::match_and_decode(log) {
-                        return Some(contract::EasRevokedOffchain {
-                            evt_tx_hash: view.transaction.hash.clone(),
-                            evt_index: log.block_index,
-                            evt_block_time: Some(blk.timestamp().to_owned()),
-                            evt_block_number: blk.number,
-                            data: Vec::from(event.data),
-                            revoker: event.revoker,
-                            timestamp: event.timestamp.to_u64(),
-                        });
-                    }
+            .collect()
+    }
+}
+
+/// Divides a decimal integer string by `10^decimals`, without trailing zeros: `1500000` with 6 decimals is `1.5`.
+/// `None` when the value is not an integer.
+fn scale_decimal(integer: &str, decimals: u32) -> Option<String> {
+    let (sign, digits) = match integer.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", integer),
+    };
+    if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    let padded = format!("{:0>width$}", digits, width = decimals as usize + 1);
+    let (whole, fraction) = padded.split_at(padded.len() - decimals as usize);
+    Some(match fraction.trim_end_matches('0') {
+        "" => format!("{}{}", sign, whole),
+        fraction => format!("{}{}.{}", sign, whole, fraction),
+    })
+}
+
+/// How `map_events` handles attestations that cannot be enriched, e.g. undecodable RPC responses.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum EnrichmentPolicy {
+    /// Fail the block.
+    #[default]
+    Strict,
+    /// Emit the attestation with the fields known from the event only, flagged `enrichment_failed`.
+    Lenient,
+}
+
+impl FromStr for EnrichmentPolicy {
+    type Err = String;
+    fn from_str(policy: &str) -> Result<Self, Self::Err> {
+        match policy {
+            "strict" => Ok(EnrichmentPolicy::Strict),
+            "lenient" => Ok(EnrichmentPolicy::Lenient),
+            _ => Err(format!("Unsupported enrichment policy: {}", policy)),
+        }
+    }
+}
+
+/// Verbosity of the `map_events` logs, set with the `log_level` param, logging measurably slowing down busy chains.
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub enum LogLevel {
+    /// No logs.
+    Off,
+    /// The per-block decoding summary and the failed RPC reads.
+    #[default]
+    Info,
+    /// Also every parsed schema and decode failure.
+    Debug,
+}
+
+impl FromStr for LogLevel {
+    type Err = String;
+    fn from_str(level: &str) -> Result<Self, Self::Err> {
+        match level {
+            "off" => Ok(LogLevel::Off),
+            "info" => Ok(LogLevel::Info),
+            "debug" => Ok(LogLevel::Debug),
+            _ => Err(format!("Unsupported log level: {}", level)),
+        }
+    }
+}
+
+/// Compression of the decoded data of attestations, set with the `decoded_data_compression` param, for sinks archiving
+/// it rather than reading it.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum DataCompression {
+    /// `decoded_data` as a JSON string.
+    #[default]
+    None,
+    /// `decoded_data_compressed`, the zstd frame of the JSON string, in place of `decoded_data`.
+    Zstd,
+}
+
+impl FromStr for DataCompression {
+    type Err = String;
+    fn from_str(compression: &str) -> Result<Self, Self::Err> {
+        match compression {
+            "none" => Ok(DataCompression::None),
+            "zstd" => Ok(DataCompression::Zstd),
+            _ => Err(format!("Unsupported decoded data compression: {}", compression)),
+        }
+    }
+}
+
+/// `map_events` settings, parsed from the module params.
+#[derive(Debug, Clone, Default)]
+struct EventsConfig {
+    options: RenderOptions,
+    attestation_source: AttestationSource,
+    schema_source: SchemaSource,
+    schema_snapshot: SchemaSnapshot,
+    decode_filter: DecodeFilter,
+    scale_hints: ScaleHints,
+    enum_labels: EnumLabels,
+    attester_labels: AttesterLabels,
+    /// Minimum length of the `bool` and `address` arrays getting a compact companion, `0` (disabled) by default.
+    compact_arrays: usize,
+    redactions: Redactions,
+    enrichment_policy: EnrichmentPolicy,
+    /// Whether any decode anomaly fails the block, see `decode_anomaly`.
+    strict: bool,
+    /// Verbosity of the logs, `debug` when the `debug` param is set.
+    log_level: LogLevel,
+    decoded_values: bool,
+    decoded_data_compression: DataCompression,
+    raw_attestations: bool,
+    final_blocks_only: bool,
+    resolver_context: bool,
+    emit_empty_blocks: bool,
+    /// Inclusive `from_time`/`to_time` bounds, in unix seconds, of the blocks producing output.
+    from_time: Option<u64>,
+    to_time: Option<u64>,
+    sample_rate: u64,
+    /// Secret of the `pseudonym_key` param, replacing addresses with keyed pseudonyms when set.
+    pseudonym_key: Option<String>,
+    network: Network,
+    /// Contracts of the `extra_contracts` param emitting EAS-compatible events (e.g. forks), decoded like the EAS
+    /// contract of the network.
+    extra_contracts: Vec<ExtraContract>,
+}
+
+/// EAS-compatible contract of the `extra_contracts` param, with the schema registry its schemas are read from, the
+/// registry of the network when unset.
+#[derive(Debug, Clone, PartialEq)]
+struct ExtraContract {
+    eas: [u8; 20],
+    schema_registry: Option<[u8; 20]>,
+}
 
-                    None
-                })
+/// Parses the `extra_contracts` param: comma separated `<address>` or `<address>:<schema_registry>` entries.
+fn parse_extra_contracts(values: &[&str]) -> Result<Vec<ExtraContract>, substreams::errors::Error> {
+    values
+        .iter()
+        .map(|value| {
+            let (eas, schema_registry) = match value.split_once(':') {
+                Some((eas, schema_registry)) => (eas, Some(schema_registry)),
+                None => (*value, None),
+            };
+            let mut addresses = parse_addresses("extra_contracts", &[eas.trim()])?.into_iter();
+            Ok(ExtraContract {
+                eas: addresses.next().unwrap_or_default(),
+                schema_registry: match schema_registry {
+                    Some(schema_registry) => parse_addresses("extra_contracts", &[schema_registry.trim()])?.into_iter().next(),
+                    None => None,
+                },
             })
-            .collect(),
-    );
-}
-
-fn extract_timestampeds(blk: &eth::Block, events: &mut contract::Events) {
-    events.eas_timestampeds.append(
-        &mut blk
-            .receipts()
-            .flat_map(|view| {
-                view.receipt.logs.iter().filter(|log| log.address == EAS_TRACKED_CONTRACT).filter_map(|log| {
-                    if let Some(event) = abi::eas_contract::events::Timestamped::match_and_decode(log) {
-                        return Some(contract::EasTimestamped {
-                            evt_tx_hash: view.transaction.hash.clone(),
-                            evt_index: log.block_index,
-                            evt_block_time: Some(blk.timestamp().to_owned()),
-                            evt_block_number: blk.number,
-                            data: Vec::from(event.data),
-                            timestamp: event.timestamp.to_u64(),
-                        });
+        })
+        .collect()
+}
+
+impl EventsConfig {
+    /// Schema registry of the contract that emitted an event, `None` for the registry of the network.
+    fn schema_registry(&self, contract: &[u8]) -> Option<[u8; 20]> {
+        self.extra_contracts
+            .iter()
+            .find(|extra| extra.eas == contract)
+            .and_then(|extra| extra.schema_registry)
+    }
+    /// Whether an event is kept by the `sample_rate` sampling, 1-in-N events chosen by their key (attestation UID,
+    /// offchain revoked or timestamped data), a hash, so that an attestation and its revocation are kept together.
+    fn sampled(&self, key: &[u8]) -> bool {
+        let hash = key
+            .get(..8)
+            .and_then(|prefix| prefix.try_into().ok())
+            .map(u64::from_be_bytes)
+            .unwrap_or_default();
+        self.sample_rate <= 1 || hash % self.sample_rate == 0
+    }
+
+    /// Replaces an address with its pseudonym, the first 20 bytes of its HMAC-keccak256 under the `pseudonym_key`
+    /// secret: the same address always gets the same pseudonym, which cannot be reversed without the secret. Empty
+    /// addresses stay empty.
+    fn pseudonymize(&self, address: &mut Vec<u8>) {
+        if let (Some(key), false) = (&self.pseudonym_key, address.is_empty()) {
+            *address = hmac_keccak256(key.as_bytes(), address)[..20].to_vec();
+        }
+    }
+
+    /// Replaces a transaction hash or attestation UID with its pseudonym, its HMAC-keccak256 under the `pseudonym_key`
+    /// secret, so that an attestation and the revocation or attestations referencing it keep the same pseudonym. Empty
+    /// identifiers stay empty.
+    fn pseudonymize_id(&self, id: &mut Vec<u8>) {
+        if let (Some(key), false) = (&self.pseudonym_key, id.is_empty()) {
+            *id = hmac_keccak256(key.as_bytes(), id).to_vec();
+        }
+    }
+
+    /// Whether the decoded data of the schema is rendered as decoded, without `decoded_values` nor any param adding,
+    /// redacting or omitting fields, so that it can be written straight from the decoded tokens.
+    fn renders_plain(&self, schema_id: &[u8; 32]) -> bool {
+        !self.decoded_values
+            && self.compact_arrays == 0
+            && !self.redactions.applies_to(schema_id)
+            && !self.scale_hints.applies_to(schema_id)
+            && !self.enum_labels.applies_to(schema_id)
+    }
+
+    /// Whether a block at the given timestamp lies within the `from_time`/`to_time` window.
+    fn in_time_window(&self, timestamp_seconds: u64) -> bool {
+        self.from_time.is_none_or(|from_time| timestamp_seconds >= from_time) && self.to_time.is_none_or(|to_time| timestamp_seconds <= to_time)
+    }
+
+    fn from_params(params: &str) -> Result<Self, substreams::errors::Error> {
+        let params = Params::parse(params);
+        Ok(EventsConfig {
+            options: RenderOptions {
+                key_style: params.get_or("json_keys", KeyStyle::AsIs)?,
+                binary_encoding: params.get_or("binary_encoding", BinaryEncoding::Hex)?,
+                hex_prefix: params.get_or("hex_prefix", true)?,
+            },
+            attestation_source: params.get_or("attestation_source", AttestationSource::Rpc)?,
+            schema_source: params.get_or("schema_source", SchemaSource::Rpc)?,
+            schema_snapshot: SchemaSnapshot::from_params(&params)?,
+            decode_filter: DecodeFilter::from_params(&params)?,
+            scale_hints: ScaleHints::from_params(&params)?,
+            enum_labels: EnumLabels::from_params(&params)?,
+            attester_labels: AttesterLabels::from_params(&params)?,
+            compact_arrays: params.get_or("compact_arrays", 0)?,
+            redactions: Redactions::from_params(&params)?,
+            enrichment_policy: params.get_or("enrichment", EnrichmentPolicy::Strict)?,
+            strict: params.get_or("strict", false)?,
+            log_level: match params.get_or("debug", false)? {
+                true => LogLevel::Debug,
+                false => params.get_or("log_level", LogLevel::Info)?,
+            },
+            decoded_values: params.get_or("decoded_values", false)?,
+            decoded_data_compression: params.get_or("decoded_data_compression", DataCompression::None)?,
+            raw_attestations: params.get_or("raw_attestations", false)?,
+            final_blocks_only: params.get_or("final_blocks_only", false)?,
+            resolver_context: params.get_or("resolver_context", false)?,
+            emit_empty_blocks: params.get_or("emit_empty_blocks", false)?,
+            from_time: params.get_parsed("from_time")?,
+            to_time: params.get_parsed("to_time")?,
+            sample_rate: params.get_or("sample_rate", 1)?,
+            pseudonym_key: params.get("pseudonym_key").map(str::to_string),
+            network: networks::from_params(&params)?,
+            extra_contracts: parse_extra_contracts(&params.get_list("extra_contracts"))?,
+        })
+    }
+}
+
+/// Logs emitted by the EAS contract and the `extra_contracts`, with their transaction. Read through `blk.logs()`,
+/// which only needs the transactions and logs carried by `base` detail level blocks.
+fn eas_logs<'a>(blk: &'a eth::Block, config: &'a EventsConfig) -> impl Iterator<Item = (&'a eth::TransactionTrace, &'a eth::Log)> {
+    blk.logs()
+        .filter(|log| log.address() == config.network.contracts.eas || config.extra_contracts.iter().any(|extra| log.address() == extra.eas))
+        .map(|log| (log.receipt.transaction, log.log))
+}
+
+/// 0-based position of each EAS event among the EAS events of the same contract in its transaction, keyed by log
+/// block index, dense even where log indexes are sparse, so that the events of a `multiAttest` call keep a
+/// deterministic order. Computed in a single pass over the EAS logs of the block; other logs of the contract, such as
+/// proxy admin events, are not counted.
+fn tx_ordinals(blk: &eth::Block, config: &EventsConfig) -> BTreeMap<u32, u32> {
+    let mut ordinals = BTreeMap::new();
+    let mut positions: BTreeMap<(&[u8], &[u8]), u32> = BTreeMap::new();
+    for (trx, log) in eas_logs(blk, config).filter(|(_, log)| is_eas_event(log)) {
+        let position = positions.entry((trx.hash.as_slice(), log.address.as_slice())).or_default();
+        ordinals.insert(log.block_index, *position);
+        *position += 1;
+    }
+    ordinals
+}
+
+fn is_eas_event(log: &eth::Log) -> bool {
+    abi::eas_contract::events::Attested::match_log(log)
+        || abi::eas_contract::events::Revoked::match_log(log)
+        || abi::eas_contract::events::RevokedOffchain::match_log(log)
+        || abi::eas_contract::events::Timestamped::match_log(log)
+}
+
+/// First anomaly of an attestation failing the block in `strict` mode: an unresolved attestation or schema, an
+/// unparsable schema, data not matching its schema, or an attestation record not matching the event or the block time.
+fn decode_anomaly(
+    event: &abi::eas_contract::events::Attested,
+    attestation: Option<&Attestation>,
+    resolved: bool,
+    parsed_schema: Option<&ParsedSchema>,
+    decoded: bool,
+    timestamp_seconds: u64,
+) -> Option<String> {
+    if !resolved {
+        return Some("attestation or schema not resolved".to_string());
+    }
+    if let Some(Err(error)) = parsed_schema {
+        return Some(format!("invalid schema: {}", error));
+    }
+    if !decoded {
+        return Some("data does not match its schema".to_string());
+    }
+    let attestation = attestation?;
+    if attestation.uid != event.uid
+        || attestation.schema != event.schema
+        || attestation.attester[..] != event.attester[..]
+        || attestation.recipient[..] != event.recipient[..]
+    {
+        return Some("attestation record does not match the event".to_string());
+    }
+    (attestation.time != timestamp_seconds).then(|| format!("attestation time {} is not the block time", attestation.time))
+}
+
+fn extract_attesteds(
+    blk: &eth::Block,
+    config: &EventsConfig,
+    rpc: &impl EasRpc,
+    ordinals: &BTreeMap<u32, u32>,
+    events: &mut contract::Events,
+) -> Result<DecodeStats, substreams::errors::Error> {
+    let traces = calls::has_call_traces(blk);
+    let attested_events: Vec<_> = eas_logs(blk, config)
+        .filter_map(|(trx, log)| abi::eas_contract::events::Attested::match_and_decode(log).map(|event| (trx, log, event)))
+        .filter(|(_, _, event)| config.sampled(&event.uid))
+        .collect();
+
+    let mut attestations: Vec<Option<Attestation>> = attested_events
+        .iter()
+        .map(|(trx, log, event)| match config.attestation_source {
+            AttestationSource::Calldata if traces => calls::attestation_from_calldata(trx, log, &log.address, event, blk.timestamp_seconds()),
+            _ => None,
+        })
+        .collect();
+
+    // failed reads of attestations and schemas, reported in the enrichment errors
+    let mut failures: Vec<RpcFailure> = vec![];
+    let mut attestation_reads = 0;
+    // attestations are read from the contract that emitted them, one batch per contract
+    let contracts: BTreeSet<&[u8]> = attested_events
+        .iter()
+        .zip(&attestations)
+        .filter(|(_, attestation)| attestation.is_none())
+        .map(|((_, log, _), _)| log.address.as_slice())
+        .collect();
+    for contract in contracts {
+        let uids: Vec<[u8; 32]> = attested_events
+            .iter()
+            .zip(&attestations)
+            .filter(|((_, log, _), attestation)| attestation.is_none() && log.address == contract)
+            .map(|((_, _, event), _)| event.uid)
+            .collect();
+        attestation_reads += uids.len();
+        let eas = <[u8; 20]>::try_from(contract).expect("log addresses should be 20 bytes");
+        let mut fetched = rpc.get_attestations_at(&eas, &uids).into_iter();
+        for (attestation, _) in attestations
+            .iter_mut()
+            .zip(&attested_events)
+            .filter(|(attestation, (_, log, _))| attestation.is_none() && log.address == contract)
+        {
+            *attestation = fetched.next().and_then(|fetched| fetched.map_err(|failure| failures.push(failure)).ok());
+        }
+    }
+
+    // schemas are read from the registry of the contract that emitted the attestation, sorted so that the RPC calls,
+    // and the logs of their failures, come in the same order on every run
+    let mut registry_schema_ids: BTreeMap<Option<[u8; 20]>, BTreeSet<[u8; 32]>> = BTreeMap::new();
+    for ((_, log, _), attestation) in attested_events.iter().zip(&attestations) {
+        if let Some(attestation) = attestation
+            .as_ref()
+            .filter(|attestation| config.decode_filter.should_decode(&attestation.schema))
+        {
+            registry_schema_ids
+                .entry(config.schema_registry(&log.address))
+                .or_default()
+                .insert(attestation.schema);
+        }
+    }
+
+    let mut schema_reads = 0;
+    // schema UIDs hash the schema record, so the same UID stands for the same record in every registry
+    let mut schema_records: BTreeMap<[u8; 32], Schema> = BTreeMap::new();
+    for (registry, schema_ids) in registry_schema_ids {
+        let schema_ids: Vec<_> = schema_ids.into_iter().collect();
+        schema_reads += schema_ids.len();
+        let schemas = match registry {
+            Some(registry) => rpc.get_schemas_at(&registry, &schema_ids),
+            None => rpc.get_schemas(&schema_ids),
+        };
+        for schema in schemas {
+            match schema {
+                Ok(schema) => {
+                    schema_records.insert(schema.uid_id, schema);
+                }
+                Err(failure) => failures.push(failure),
+            }
+        }
+    }
+    if config.log_level >= LogLevel::Info {
+        for failure in &failures {
+            substreams::log::info!("block {}: {}", blk.number, failure);
+        }
+    }
+
+    let mut stats = DecodeStats {
+        rpc_calls: attestation_reads + schema_reads,
+        ..Default::default()
+    };
+    let mut parsed_schemas: BTreeMap<[u8; 32], ParsedSchema> = BTreeMap::new();
+    for ((trx, log, event), attestation) in attested_events.into_iter().zip(attestations) {
+        let schema_record = schema_records.get(&event.schema);
+        let schema = schema_record.map(|schema| &schema.schema);
+        let resolved = attestation.is_some() && (schema.is_some() || !config.decode_filter.should_decode(&event.schema));
+        if !resolved && config.enrichment_policy == EnrichmentPolicy::Strict {
+            let reason = failures
+                .iter()
+                .find(|failure| failure.uid == event.uid || failure.uid == event.schema)
+                .map(|failure| format!(": {}", failure))
+                .unwrap_or_default();
+            return Err(substreams::errors::Error::msg(format!(
+                "failed to enrich attestation {}{} (set enrichment=lenient to skip)",
+                Hex(&event.uid),
+                reason
+            )));
+        }
+
+        // attestations of schemas excluded from decoding keep their raw data only
+        let (schema, decoded_data, decoded_values, extra_data, missing_fields, decoded) = match (&attestation, schema) {
+            (Some(attestation), Some(schema)) => {
+                let schema_fields = match parsed_schemas.entry(event.schema) {
+                    Entry::Occupied(entry) => {
+                        stats.cache_hits += 1;
+                        entry.into_mut()
+                    }
+                    // signatures parsed in previous blocks are read back from `store_parsed_schemas`
+                    Entry::Vacant(entry) => match rpc.parsed_fields(schema) {
+                        Some(fields) => {
+                            stats.cache_hits += 1;
+                            entry.insert(Ok(fields))
+                        }
+                        None => {
+                            stats.schemas_parsed += 1;
+                            let fields = parse_schema(schema);
+                            if config.log_level >= LogLevel::Debug {
+                                substreams::log::debug!("parsed schema {} ({}): {:?}", Hex(&event.schema), schema, fields);
+                            }
+                            entry.insert(fields)
+                        }
+                    },
+                };
+                stats.decoded_bytes += attestation.data.len();
+                let decoded = match &*schema_fields {
+                    Ok(fields) => decode_fields(&attestation.data, fields, &config.options).map(|decoded| (fields, decoded)),
+                    Err(_) => Err("Invalid schema"),
+                };
+                match decoded {
+                    Ok((schema_fields, decoded)) => {
+                        let (decoded_data, decoded_values) = match config.renders_plain(&event.schema) {
+                            // written straight from the tokens, without building the `Value` trees
+                            true => (schema_parser::fields_to_json(schema_fields, &decoded.tokens, &config.options), String::new()),
+                            false => {
+                                let fields = decoded.fields(schema_fields, &config.options);
+                                let fields = config.redactions.apply(&event.schema, fields, &config.options);
+                                let decoded_values = match config.decoded_values {
+                                    true => Value::Array(fields.iter().map(|(_, value)| value.clone()).collect()).to_string(),
+                                    false => String::new(),
+                                };
+                                let fields = config.redactions.omit(&event.schema, fields, &config.options);
+                                let fields = config.scale_hints.apply(&event.schema, fields, &config.options);
+                                let fields = config.enum_labels.apply(&event.schema, fields, &config.options);
+                                let fields = match config.compact_arrays {
+                                    0 => fields,
+                                    min_len => compact_arrays(schema_fields, fields, min_len, &config.options),
+                                };
+                                (Value::Object(Map::from_iter(fields)).to_string(), decoded_values)
+                            }
+                        };
+                        // truncated data still flags the attestation, with the fields that could be decoded
+                        let complete = decoded.missing_fields.is_empty();
+                        (
+                            schema.to_string(),
+                            decoded_data,
+                            decoded_values,
+                            decoded.extra_data,
+                            decoded.missing_fields,
+                            complete,
+                        )
                     }
+                    Err(error) => {
+                        stats.decode_failures += 1;
+                        if config.log_level >= LogLevel::Debug {
+                            substreams::log::debug!("failed to decode attestation {} with schema {}: {}", Hex(&event.uid), schema, error);
+                        }
+                        (
+                            schema.to_string(),
+                            serde_json::json!({ "error": error }).to_string(),
+                            String::new(),
+                            vec![],
+                            vec![],
+                            false,
+                        )
+                    }
+                }
+            }
+            _ => (String::new(), String::new(), String::new(), vec![], vec![], true),
+        };
+        if config.strict {
+            let parsed_schema = parsed_schemas.get(&event.schema).filter(|_| !schema.is_empty());
+            let anomaly = decode_anomaly(&event, attestation.as_ref(), resolved, parsed_schema, decoded, blk.timestamp_seconds());
+            if let Some(anomaly) = anomaly {
+                return Err(substreams::errors::Error::msg(format!(
+                    "decode anomaly in attestation {}: {} (strict=true)",
+                    Hex(&event.uid),
+                    anomaly
+                )));
+            }
+        }
+        let aa_sender = traces.then(|| calls::user_operation_sender(trx, log)).flatten();
+        // resolver misbehavior: the hook rejected the attestation, yet it succeeded
+        let resolver_call_rejected = traces && calls::resolver_call_rejected(trx, log, &log.address).unwrap_or(false);
+        // unresolved attestations have no data to hash
+        let data_keccak = attestation
+            .as_ref()
+            .map(|attestation| keccak256(&attestation.data).to_vec())
+            .unwrap_or_default();
+        let attestation_time = attestation.as_ref().map(|attestation| attestation.time).unwrap_or_default();
+        let expiration_time = attestation.as_ref().map(|attestation| attestation.expiration_time).unwrap_or_default();
+        let ref_uid = attestation.as_ref().map(|attestation| attestation.ref_uid);
+        let redacted = config.redactions.applies_to(&event.schema);
+        let raw_attestation = attestation
+            .as_ref()
+            .filter(|_| config.raw_attestations)
+            .map(|attestation| attestation.to_raw(&config.options, redacted));
+        // EAS rejects revocable attestations of non-revocable schemas, a conflict points at inconsistent data
+        let revocability_conflict = attestation
+            .as_ref()
+            .zip(schema_record)
+            .is_some_and(|(attestation, schema)| attestation.revocable && !schema.revocable);
+        // canonical ABI types of the schema fields, e.g. `uint256` or `(address,bool)[]`, empty for undecoded schemas
+        let schema_field_types: Vec<String> = match (schema.is_empty(), parsed_schemas.get(&event.schema)) {
+            (false, Some(Ok(fields))) => fields
+                .iter()
+                .map(|(field_type, _)| schema_parser::fieldtype_to_paramtype(field_type).to_string())
+                .collect(),
+            _ => vec![],
+        };
+        let schema_shape_id = match (schema.is_empty(), parsed_schemas.get(&event.schema)) {
+            (false, Some(Ok(fields))) => schema_shape_id(fields).to_vec(),
+            _ => vec![],
+        };
 
-                    None
-                })
-            })
-            .collect(),
-    );
+        events.eas_attesteds.push(contract::EasAttested {
+            evt_tx_hash: trx.hash.clone(),
+            evt_index: log.block_index,
+            evt_address: log.address.clone(),
+            evt_tx_ordinal: ordinals.get(&log.block_index).copied().unwrap_or_default(),
+            evt_block_time: Some(blk.timestamp().to_owned()),
+            evt_block_timestamp_seconds: blk.timestamp_seconds(),
+            evt_block_number: blk.number,
+            attester_label: config.attester_labels.label(&event.attester),
+            attester: event.attester,
+            recipient: event.recipient,
+            schema_id: Vec::from(event.schema),
+            uid: Vec::from(event.uid),
+            data: match redacted {
+                true => vec![],
+                false => attestation.map(|attestation| attestation.data).unwrap_or_default(),
+            },
+            schema,
+            decoded_data,
+            decoded_values,
+            bundler: aa_sender.as_ref().map(|_| trx.from.clone()).unwrap_or_default(),
+            aa_sender: aa_sender.unwrap_or_default(),
+            enrichment_failed: !resolved || !decoded,
+            data_keccak,
+            attestation_time,
+            // 0 when the attestation does not expire
+            expiration_time,
+            // EAS rejects expiration times not in the future, so this only flags an RPC or calldata record that does
+            // not match the chain
+            is_expired_at_emission: expiration_time != 0 && expiration_time <= blk.timestamp_seconds(),
+            time_mismatch: attestation_time != 0 && attestation_time != blk.timestamp_seconds(),
+            schema_field_count: schema_field_types.len() as u32,
+            schema_field_types,
+            schema_shape_id,
+            extra_data_bytes: extra_data.len() as u32,
+            extra_data_hex: match extra_data.is_empty() {
+                true => String::new(),
+                false => config.options.hex(&extra_data),
+            },
+            missing_fields,
+            // empty when the attestation does not reference another one (zero refUID)
+            ref_uid: ref_uid.filter(|ref_uid| *ref_uid != [0; 32]).map(Vec::from).unwrap_or_default(),
+            revocability_conflict,
+            resolver_call_rejected,
+            // assigned by `map_schema_dictionary_events`
+            schema_handle: 0,
+            raw_attestation,
+            // set by `compress_decoded_data`
+            decoded_data_compressed: vec![],
+            tx_nonce: trx.nonce,
+            // selector of the function called by the transaction: EAS `attest*`, a multicall, a bundler, ...
+            tx_method_selector: trx.input.get(..4).map(Vec::from).unwrap_or_default(),
+            // joined from the schema store by `map_events`
+            schema_registered_block: 0,
+            schema_registered_time: None,
+            module_version: MODULE_VERSION.to_string(),
+            decoding_ruleset_version: DECODING_RULESET_VERSION,
+        });
+    }
+
+    if !events.eas_attesteds.is_empty() && config.log_level >= LogLevel::Info {
+        substreams::log::info!(
+            "block {}: {} attestations, {} schemas parsed, {} cache hits, {} decode failures",
+            blk.number,
+            events.eas_attesteds.len(),
+            stats.schemas_parsed,
+            stats.cache_hits,
+            stats.decode_failures
+        );
+    }
+
+    Ok(stats)
+}
+
+fn extract_revokeds(blk: &eth::Block, config: &EventsConfig, ordinals: &BTreeMap<u32, u32>, events: &mut contract::Events) {
+    let traces = calls::has_call_traces(blk);
+    events.eas_revokeds.extend(eas_logs(blk, config).filter_map(|(trx, log)| {
+        let event = abi::eas_contract::events::Revoked::match_and_decode(log).filter(|event| config.sampled(&event.uid))?;
+        Some(contract::EasRevoked {
+            evt_tx_hash: trx.hash.clone(),
+            evt_index: log.block_index,
+            evt_address: log.address.clone(),
+            evt_tx_ordinal: ordinals.get(&log.block_index).copied().unwrap_or_default(),
+            evt_block_time: Some(blk.timestamp().to_owned()),
+            evt_block_timestamp_seconds: blk.timestamp_seconds(),
+            evt_block_number: blk.number,
+            attester_label: config.attester_labels.label(&event.attester),
+            attester: event.attester,
+            recipient: event.recipient,
+            schema: Vec::from(event.schema),
+            delegation_signer: traces
+                .then(|| calls::revocation_delegation_signer(trx, log, &event.uid))
+                .flatten()
+                .unwrap_or_default(),
+            uid: Vec::from(event.uid),
+            resolver_context: match config.resolver_context && traces {
+                true => calls::resolver_logs(trx, log, &log.address)
+                    .into_iter()
+                    .map(|resolver_log| contract::ResolverLog {
+                        address: resolver_log.address.clone(),
+                        topics: resolver_log.topics.iter().map(|topic| config.options.hex(topic)).collect(),
+                        data: config.options.hex(&resolver_log.data),
+                    })
+                    .collect(),
+                false => vec![],
+            },
+            module_version: MODULE_VERSION.to_string(),
+        })
+    }));
+}
+
+fn extract_revoked_offchains(blk: &eth::Block, config: &EventsConfig, ordinals: &BTreeMap<u32, u32>, events: &mut contract::Events) {
+    events.eas_revoked_offchains.extend(eas_logs(blk, config).filter_map(|(trx, log)| {
+        let event = abi::eas_contract::events::RevokedOffchain::match_and_decode(log).filter(|event| config.sampled(&event.data))?;
+        Some(contract::EasRevokedOffchain {
+            evt_tx_hash: trx.hash.clone(),
+            evt_index: log.block_index,
+            evt_address: log.address.clone(),
+            evt_tx_ordinal: ordinals.get(&log.block_index).copied().unwrap_or_default(),
+            evt_block_time: Some(blk.timestamp().to_owned()),
+            evt_block_timestamp_seconds: blk.timestamp_seconds(),
+            evt_block_number: blk.number,
+            data: Vec::from(event.data),
+            revoker: event.revoker,
+            timestamp: event.timestamp.to_u64(),
+            module_version: MODULE_VERSION.to_string(),
+        })
+    }));
+}
+
+fn extract_timestampeds(blk: &eth::Block, config: &EventsConfig, ordinals: &BTreeMap<u32, u32>, events: &mut contract::Events) {
+    let traces = calls::has_call_traces(blk);
+    events.eas_timestampeds.extend(eas_logs(blk, config).filter_map(|(trx, log)| {
+        let event = abi::eas_contract::events::Timestamped::match_and_decode(log).filter(|event| config.sampled(&event.data))?;
+        let (batch_index, batch_size) = if traces { timestamp_batch_position(trx, log, &log.address) } else { (0, 1) };
+        Some(contract::EasTimestamped {
+            evt_tx_hash: trx.hash.clone(),
+            evt_index: log.block_index,
+            evt_address: log.address.clone(),
+            evt_tx_ordinal: ordinals.get(&log.block_index).copied().unwrap_or_default(),
+            evt_block_time: Some(blk.timestamp().to_owned()),
+            evt_block_timestamp_seconds: blk.timestamp_seconds(),
+            evt_block_number: blk.number,
+            data: Vec::from(event.data),
+            timestamp: event.timestamp.to_u64(),
+            batch_index,
+            batch_size,
+            module_version: MODULE_VERSION.to_string(),
+        })
+    }));
+}
+
+/// Returns the `(batch_index, batch_size)` of a `Timestamped` log within the `timestamp`/`multiTimestamp` call that emitted it.
+/// Falls back to a batch of one when the block carries no call traces.
+fn timestamp_batch_position(trx: &eth::TransactionTrace, log: &eth::Log, eas: &[u8]) -> (u32, u32) {
+    let Some(call) = calls::emitting_call(trx, log) else {
+        return (0, 1);
+    };
+
+    let batch_index = call
+        .logs
+        .iter()
+        .filter(|call_log| call_log.address == eas && abi::eas_contract::events::Timestamped::match_log(call_log))
+        .position(|call_log| call_log.block_index == log.block_index)
+        .unwrap_or(0);
+    let batch_size = MultiTimestamp::match_and_decode(call).map(|multi| multi.data.len()).unwrap_or(1);
+
+    (batch_index as u32, batch_size as u32)
 }
+
+/// `emit_empty_blocks` (param, default `false`) controls whether blocks without any EAS event produce an `Events`
+/// message without events, e.g. for sinks detecting gaps, or no output at all. The module runs without block filter
+/// so that these blocks reach it. Blocks outside the `from_time`/`to_time` window produce no output.
 #[substreams::handlers::map]
-fn map_events(blk: eth::Block) -> Result<contract::Events, substreams::errors::Error> {
+fn map_events(
+    params: String,
+    blk: eth::Block,
+    schemas: StoreGetProto<contract::Schema>,
+    parsed_schemas: StoreGetProto<contract::SchemaFields>,
+) -> Result<Option<contract::Events>, substreams::errors::Error> {
+    let config = EventsConfig::from_params(&params)?;
+    config.network.check_block(blk.number)?;
+    if !config.in_time_window(blk.timestamp_seconds()) {
+        return Ok(None);
+    }
+    let rpc = SchemaResolver {
+        rpc: rpc::SubstreamsRpc {
+            contracts: config.network.contracts,
+        },
+        store: &schemas,
+        snapshot: &config.schema_snapshot,
+        primary: config.schema_source,
+        parsed_schemas: Some(&parsed_schemas),
+    };
+    let mut events = extract_events(&params, &blk, &rpc)?;
+    if !config.emit_empty_blocks && !has_events(&events) {
+        return Ok(None);
+    }
+    schemas::set_schema_registrations(&mut events, &schemas);
+    Ok(Some(events))
+}
+
+/// Whether the block holds any EAS event.
+fn has_events(events: &contract::Events) -> bool {
+    !events.eas_attesteds.is_empty() || !events.eas_revokeds.is_empty() || !events.eas_revoked_offchains.is_empty() || !events.eas_timestampeds.is_empty()
+}
+
+fn extract_events(params: &str, blk: &eth::Block, rpc: &impl EasRpc) -> Result<contract::Events, substreams::errors::Error> {
+    let config = EventsConfig::from_params(params)?;
+
     let mut events = contract::Events::default();
-    extract_attesteds(&blk, &mut events);
-    extract_revokeds(&blk, &mut events);
-    extract_revoked_offchains(&blk, &mut events);
-    extract_timestampeds(&blk, &mut events);
+    let ordinals = tx_ordinals(blk, &config);
+    let stats = extract_attesteds(blk, &config, rpc, &ordinals, &mut events)?;
+    extract_revokeds(blk, &config, &ordinals, &mut events);
+    extract_revoked_offchains(blk, &config, &ordinals, &mut events);
+    extract_timestampeds(blk, &config, &ordinals, &mut events);
+    pseudonymize_addresses(&config, &mut events);
+    compress_decoded_data(&config, &mut events);
+    events.touched_keys = touched_keys(&events);
+    // echo of the param: the module cannot tell whether the run serves final blocks only
+    events.final_blocks_only = config.final_blocks_only;
+    events.chain_id = config.network.chain_id;
+    events.metrics = Some(contract::PipelineMetrics {
+        block_number: blk.number,
+        attested_total: events.eas_attesteds.len() as u64,
+        revoked_total: events.eas_revokeds.len() as u64,
+        revoked_offchain_total: events.eas_revoked_offchains.len() as u64,
+        timestamped_total: events.eas_timestampeds.len() as u64,
+        rpc_calls: stats.rpc_calls as u64,
+        decode_failures: stats.decode_failures as u64,
+        schemas_parsed: stats.schemas_parsed as u64,
+        schema_cache_hits: stats.cache_hits as u64,
+        eas_logs: eas_logs(blk, &config).count() as u64,
+        decoded_bytes: stats.decoded_bytes as u64,
+    });
     Ok(events)
 }
+
+/// Moves the decoded data of the attestations to `decoded_data_compressed`, zstd-compressed at the fastest level, when
+/// `decoded_data_compression` is `zstd`. Attestations without decoded data are left as is.
+fn compress_decoded_data(config: &EventsConfig, events: &mut contract::Events) {
+    if config.decoded_data_compression != DataCompression::Zstd {
+        return;
+    }
+    for attested in events.eas_attesteds.iter_mut().filter(|attested| !attested.decoded_data.is_empty()) {
+        let decoded_data = std::mem::take(&mut attested.decoded_data);
+        attested.decoded_data_compressed = ruzstd::encoding::compress_to_vec(decoded_data.as_bytes(), ruzstd::encoding::CompressionLevel::Fastest);
+    }
+}
+
+/// Replaces the attester, recipient, revoker, smart account, bundler and delegation signer addresses of the events (and
+/// of the raw attestations) with their pseudonyms when `pseudonym_key` is set, as well as the transaction hashes and
+/// attestation UIDs, which would otherwise link the events back to the chain records holding the addresses. Block
+/// numbers and log indexes, and the addresses within the decoded data, are left as is.
+fn pseudonymize_addresses(config: &EventsConfig, events: &mut contract::Events) {
+    if config.pseudonym_key.is_none() {
+        return;
+    }
+    for attested in events.eas_attesteds.iter_mut() {
+        for address in [&mut attested.attester, &mut attested.recipient, &mut attested.aa_sender, &mut attested.bundler] {
+            config.pseudonymize(address);
+        }
+        for id in [&mut attested.evt_tx_hash, &mut attested.uid, &mut attested.ref_uid] {
+            config.pseudonymize_id(id);
+        }
+        if let Some(raw_attestation) = attested.raw_attestation.as_mut() {
+            raw_attestation.attester = config.options.hex(&attested.attester);
+            raw_attestation.recipient = config.options.hex(&attested.recipient);
+            raw_attestation.uid = config.options.hex(&attested.uid);
+            if !attested.ref_uid.is_empty() {
+                raw_attestation.ref_uid = config.options.hex(&attested.ref_uid);
+            }
+        }
+    }
+    for revoked in events.eas_revokeds.iter_mut() {
+        for address in [&mut revoked.attester, &mut revoked.recipient, &mut revoked.delegation_signer] {
+            config.pseudonymize(address);
+        }
+        for id in [&mut revoked.evt_tx_hash, &mut revoked.uid] {
+            config.pseudonymize_id(id);
+        }
+    }
+    for revocation in events.eas_revoked_offchains.iter_mut() {
+        config.pseudonymize(&mut revocation.revoker);
+        config.pseudonymize_id(&mut revocation.evt_tx_hash);
+    }
+    for timestamped in events.eas_timestampeds.iter_mut() {
+        config.pseudonymize_id(&mut timestamped.evt_tx_hash);
+    }
+}
+
+/// Sorted, deduplicated keys of the schemas (`schema:<uid>`), attesters (`attester:<address>`) and recipients
+/// (`recipient:<address>`) touched by the attestations and revocations of the block, so that downstream modules can
+/// skip blocks without going through every event.
+fn touched_keys(events: &contract::Events) -> Vec<String> {
+    let attested = events
+        .eas_attesteds
+        .iter()
+        .map(|attested| (&attested.schema_id, &attested.attester, &attested.recipient));
+    let revoked = events
+        .eas_revokeds
+        .iter()
+        .map(|revoked| (&revoked.schema, &revoked.attester, &revoked.recipient));
+
+    attested
+        .chain(revoked)
+        .flat_map(|(schema, attester, recipient)| {
+            [
+                format!("schema:{}", Hex(schema)),
+                activity::activity_key(contract::AddressRole::Attester, attester),
+                activity::activity_key(contract::AddressRole::Recipient, recipient),
+            ]
+        })
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
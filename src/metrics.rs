@@ -0,0 +1,8 @@
+use crate::pb::contract::v1 as contract;
+
+/// Emits the pipeline metrics of the block (event counts, RPC calls, decode failures and cost hints) reported by
+/// `map_events`, for an operator-side exporter to scrape or push, e.g. as Prometheus counters.
+#[substreams::handlers::map]
+fn map_pipeline_metrics(events: contract::Events) -> Result<contract::PipelineMetrics, substreams::errors::Error> {
+    Ok(events.metrics.unwrap_or_default())
+}
@@ -0,0 +1,143 @@
+use crate::params::Params;
+use crate::{EAS_SCHEMA_REGISTRY_CONTRACT, EAS_TRACKED_CONTRACT};
+use hex_literal::hex;
+use std::str::FromStr;
+use substreams::errors::Error;
+
+/// Addresses of the EAS and schema registry contracts read by the modules.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Contracts {
+    pub eas: [u8; 20],
+    pub schema_registry: [u8; 20],
+}
+
+impl Default for Contracts {
+    fn default() -> Self {
+        ChainFamily::OpStack.contracts()
+    }
+}
+
+/// Chain family of the network, set with the `chain_family` param, which determines the contracts to read.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ChainFamily {
+    /// OP-stack chains, where EAS and the schema registry are predeploys at the same addresses on every chain.
+    #[default]
+    OpStack,
+}
+
+impl ChainFamily {
+    pub const fn contracts(&self) -> Contracts {
+        match self {
+            ChainFamily::OpStack => Contracts {
+                eas: EAS_TRACKED_CONTRACT,
+                schema_registry: EAS_SCHEMA_REGISTRY_CONTRACT,
+            },
+        }
+    }
+}
+
+impl FromStr for ChainFamily {
+    type Err = String;
+    fn from_str(family: &str) -> Result<Self, Self::Err> {
+        match family {
+            "op-stack" => Ok(ChainFamily::OpStack),
+            _ => Err(format!("Unsupported chain family: {}", family)),
+        }
+    }
+}
+
+/// EAS deployment on a network: contracts, chain ID and the block at which the EAS contract was deployed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Network {
+    pub name: &'static str,
+    pub chain_id: u64,
+    pub contracts: Contracts,
+    pub deployment_block: u64,
+}
+
+impl Default for Network {
+    /// Unnamed OP-stack chain.
+    fn default() -> Self {
+        op_stack("", 0)
+    }
+}
+
+impl Network {
+    /// Fails on blocks preceding the EAS deployment, which can only produce empty output, pointing at the start
+    /// block to use instead.
+    pub fn check_block(&self, block_number: u64) -> Result<(), Error> {
+        if block_number < self.deployment_block {
+            return Err(Error::msg(format!(
+                "block {} precedes the EAS deployment on {} at block {}, start the modules at block {} or later",
+                block_number, self.name, self.deployment_block, self.deployment_block
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Predeploys, part of the OP-stack chains since their genesis (or Bedrock migration).
+const fn op_stack(name: &'static str, chain_id: u64) -> Network {
+    Network {
+        name,
+        chain_id,
+        contracts: ChainFamily::OpStack.contracts(),
+        deployment_block: 0,
+    }
+}
+
+/// Known EAS deployments, selected with the `chain` param.
+pub const NETWORKS: [Network; 7] = [
+    op_stack("optimism", 10),
+    op_stack("base", 8453),
+    op_stack("zora", 7_777_777),
+    op_stack("mode", 34443),
+    op_stack("fraxtal", 252),
+    Network {
+        name: "polygon",
+        chain_id: 137,
+        contracts: Contracts {
+            eas: hex!("5e634ef5355f45a855d02d66ecd687b1502af790"),
+            schema_registry: hex!("7876eef51a891e737af8ba5a5e0f0fd29073d5a7"),
+        },
+        deployment_block: 51_279_760,
+    },
+    Network {
+        name: "arbitrum-one",
+        chain_id: 42161,
+        contracts: Contracts {
+            eas: hex!("bd75f629a22dc1ced33dda0b68c546a1c035c458"),
+            schema_registry: hex!("a310da9c5b885e7fb3fba9d66e9ba6df512b78eb"),
+        },
+        deployment_block: 64_528_380,
+    },
+];
+
+/// Block at which EAS was deployed on the given chain of [`NETWORKS`], `None` for unknown chains. Runtimes pick the
+/// start block of a chain from it, blocks before it producing errors rather than empty output.
+pub fn deployment_block(chain: &str) -> Option<u64> {
+    NETWORKS.iter().find(|network| network.name == chain).map(|network| network.deployment_block)
+}
+
+/// Resolves the network from the `chain_family` and `chain` params. Without `chain`, the contracts of the chain
+/// family (OP-stack by default) are read, with no chain ID and no deployment block check.
+pub fn from_params(params: &Params) -> Result<Network, Error> {
+    let chain_family = params.get("chain_family").map(ChainFamily::from_str).transpose().map_err(Error::msg)?;
+    let Some(chain) = params.get("chain") else {
+        return Ok(Network {
+            contracts: chain_family.unwrap_or_default().contracts(),
+            ..Default::default()
+        });
+    };
+
+    let network = NETWORKS
+        .iter()
+        .find(|network| network.name == chain)
+        .ok_or_else(|| Error::msg(format!("unknown chain '{}'", chain)))?;
+    if let Some(chain_family) = chain_family {
+        if chain_family.contracts() != network.contracts {
+            return Err(Error::msg(format!("chain '{}' is not part of chain family {:?}", chain, chain_family)));
+        }
+    }
+    Ok(*network)
+}
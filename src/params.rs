@@ -0,0 +1,53 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use substreams::errors::Error;
+
+/// Module params given as a query string: `key1=value1&key2=value2`.
+/// List values are comma separated: `key=a,b,c`.
+#[derive(Debug, Default, Clone)]
+pub struct Params<'a> {
+    values: BTreeMap<&'a str, &'a str>,
+}
+
+impl<'a> Params<'a> {
+    pub fn parse(params: &'a str) -> Self {
+        let values = params
+            .split('&')
+            .map(str::trim)
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| match pair.split_once('=') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => (pair, ""),
+            })
+            .collect();
+
+        Params { values }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&'a str> {
+        self.values.get(key).copied().filter(|value| !value.is_empty())
+    }
+
+    /// Returns the comma separated values of `key`, empty when the param is absent.
+    pub fn get_list(&self, key: &str) -> Vec<&'a str> {
+        self.get(key)
+            .map(|value| value.split(',').map(str::trim).filter(|item| !item.is_empty()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Parses the value of `key`, returning `default` when the param is absent.
+    pub fn get_or<T: FromStr>(&self, key: &str, default: T) -> Result<T, Error> {
+        Ok(self.get_parsed(key)?.unwrap_or(default))
+    }
+
+    /// Parses the value of `key`, `None` when the param is absent.
+    pub fn get_parsed<T: FromStr>(&self, key: &str) -> Result<Option<T>, Error> {
+        self.get(key)
+            .map(|value| {
+                value
+                    .parse::<T>()
+                    .map_err(|_| Error::msg(format!("invalid value '{}' for param '{}'", value, key)))
+            })
+            .transpose()
+    }
+}
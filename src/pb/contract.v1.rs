@@ -11,6 +11,14 @@ pub struct Events {
     pub eas_revoked_offchains: ::prost::alloc::vec::Vec<EasRevokedOffchain>,
     #[prost(message, repeated, tag="4")]
     pub eas_timestampeds: ::prost::alloc::vec::Vec<EasTimestamped>,
+    #[prost(string, repeated, tag="5")]
+    pub touched_keys: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(bool, tag="6")]
+    pub final_blocks_only: bool,
+    #[prost(uint64, tag="7")]
+    pub chain_id: u64,
+    #[prost(message, optional, tag="8")]
+    pub metrics: ::core::option::Option<PipelineMetrics>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -37,6 +45,68 @@ pub struct EasAttested {
     pub schema: ::prost::alloc::string::String,
     #[prost(string, tag="11")]
     pub decoded_data: ::prost::alloc::string::String,
+    #[prost(uint64, tag="12")]
+    pub evt_block_timestamp_seconds: u64,
+    #[prost(bytes="vec", tag="13")]
+    pub aa_sender: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="14")]
+    pub bundler: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bool, tag="15")]
+    pub enrichment_failed: bool,
+    #[prost(string, tag="16")]
+    pub decoded_values: ::prost::alloc::string::String,
+    #[prost(bytes="vec", tag="17")]
+    pub data_keccak: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag="18")]
+    pub schema_registered_block: u64,
+    #[prost(message, optional, tag="19")]
+    pub schema_registered_time: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint64, tag="20")]
+    pub attestation_time: u64,
+    #[prost(bool, tag="21")]
+    pub time_mismatch: bool,
+    #[prost(uint32, tag="22")]
+    pub schema_field_count: u32,
+    #[prost(string, repeated, tag="23")]
+    pub schema_field_types: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(uint64, tag="24")]
+    pub tx_nonce: u64,
+    #[prost(bytes="vec", tag="25")]
+    pub tx_method_selector: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint32, tag="26")]
+    pub extra_data_bytes: u32,
+    #[prost(string, tag="27")]
+    pub extra_data_hex: ::prost::alloc::string::String,
+    #[prost(string, repeated, tag="28")]
+    pub missing_fields: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(bytes="vec", tag="29")]
+    pub ref_uid: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="30")]
+    pub schema_shape_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag="31")]
+    pub module_version: ::prost::alloc::string::String,
+    #[prost(uint32, tag="32")]
+    pub decoding_ruleset_version: u32,
+    #[prost(bool, tag="33")]
+    pub revocability_conflict: bool,
+    #[prost(bytes="vec", tag="34")]
+    pub evt_address: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag="35")]
+    pub expiration_time: u64,
+    #[prost(bool, tag="36")]
+    pub resolver_call_rejected: bool,
+    #[prost(uint64, tag="37")]
+    pub schema_handle: u64,
+    #[prost(uint32, tag="38")]
+    pub evt_tx_ordinal: u32,
+    #[prost(bool, tag="39")]
+    pub is_expired_at_emission: bool,
+    #[prost(string, tag="40")]
+    pub attester_label: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="41")]
+    pub raw_attestation: ::core::option::Option<RawAttestation>,
+    #[prost(bytes="vec", tag="42")]
+    pub decoded_data_compressed: ::prost::alloc::vec::Vec<u8>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -57,6 +127,20 @@ pub struct EasRevoked {
     pub uid: ::prost::alloc::vec::Vec<u8>,
     #[prost(bytes="vec", tag="8")]
     pub schema: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag="9")]
+    pub evt_block_timestamp_seconds: u64,
+    #[prost(bytes="vec", tag="10")]
+    pub delegation_signer: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, repeated, tag="11")]
+    pub resolver_context: ::prost::alloc::vec::Vec<ResolverLog>,
+    #[prost(string, tag="12")]
+    pub module_version: ::prost::alloc::string::String,
+    #[prost(bytes="vec", tag="13")]
+    pub evt_address: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint32, tag="14")]
+    pub evt_tx_ordinal: u32,
+    #[prost(string, tag="15")]
+    pub attester_label: ::prost::alloc::string::String,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -75,6 +159,14 @@ pub struct EasRevokedOffchain {
     pub data: ::prost::alloc::vec::Vec<u8>,
     #[prost(uint64, tag="7")]
     pub timestamp: u64,
+    #[prost(uint64, tag="8")]
+    pub evt_block_timestamp_seconds: u64,
+    #[prost(string, tag="9")]
+    pub module_version: ::prost::alloc::string::String,
+    #[prost(bytes="vec", tag="10")]
+    pub evt_address: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint32, tag="11")]
+    pub evt_tx_ordinal: u32,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -91,5 +183,1377 @@ pub struct EasTimestamped {
     pub data: ::prost::alloc::vec::Vec<u8>,
     #[prost(uint64, tag="6")]
     pub timestamp: u64,
+    #[prost(uint32, tag="7")]
+    pub batch_index: u32,
+    #[prost(uint32, tag="8")]
+    pub batch_size: u32,
+    #[prost(uint64, tag="9")]
+    pub evt_block_timestamp_seconds: u64,
+    #[prost(string, tag="10")]
+    pub module_version: ::prost::alloc::string::String,
+    #[prost(bytes="vec", tag="11")]
+    pub evt_address: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint32, tag="12")]
+    pub evt_tx_ordinal: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OffchainRevocationLinks {
+    #[prost(message, repeated, tag="1")]
+    pub offchain_revocation_links: ::prost::alloc::vec::Vec<OffchainRevocationLink>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OffchainRevocationLink {
+    #[prost(bytes="vec", tag="1")]
+    pub evt_tx_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint32, tag="2")]
+    pub evt_index: u32,
+    #[prost(message, optional, tag="3")]
+    pub evt_block_time: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint64, tag="4")]
+    pub evt_block_number: u64,
+    #[prost(bytes="vec", tag="5")]
+    pub revoker: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="6")]
+    pub data: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag="7")]
+    pub timestamp: u64,
+    #[prost(bytes="vec", tag="8")]
+    pub first_evt_tx_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag="9")]
+    pub first_evt_block_number: u64,
+    #[prost(message, optional, tag="10")]
+    pub first_evt_block_time: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint64, tag="11")]
+    pub first_timestamp: u64,
+    #[prost(bool, tag="12")]
+    pub is_repeated: bool,
+    #[prost(bool, tag="13")]
+    pub is_conflicting: bool,
+    #[prost(uint64, tag="14")]
+    pub evt_block_timestamp_seconds: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AddressActivities {
+    #[prost(message, repeated, tag="1")]
+    pub address_activities: ::prost::alloc::vec::Vec<AddressActivity>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AddressActivity {
+    #[prost(enumeration="AddressRole", tag="1")]
+    pub role: i32,
+    #[prost(bytes="vec", tag="2")]
+    pub address: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag="3")]
+    pub first_seen_block_number: u64,
+    #[prost(uint64, tag="4")]
+    pub last_seen_block_number: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SchemaAdoptions {
+    #[prost(message, repeated, tag="1")]
+    pub schema_adoptions: ::prost::alloc::vec::Vec<SchemaAdoption>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SchemaAdoption {
+    #[prost(bytes="vec", tag="1")]
+    pub evt_tx_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint32, tag="2")]
+    pub evt_index: u32,
+    #[prost(message, optional, tag="3")]
+    pub evt_block_time: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint64, tag="4")]
+    pub evt_block_number: u64,
+    #[prost(bytes="vec", tag="5")]
+    pub schema_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="6")]
+    pub uid: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag="7")]
+    pub milestone: u64,
+    #[prost(uint64, tag="8")]
+    pub evt_block_timestamp_seconds: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HighlyAttestedRecipients {
+    #[prost(message, repeated, tag="1")]
+    pub highly_attested_recipients: ::prost::alloc::vec::Vec<HighlyAttestedRecipient>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HighlyAttestedRecipient {
+    #[prost(bytes="vec", tag="1")]
+    pub evt_tx_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint32, tag="2")]
+    pub evt_index: u32,
+    #[prost(message, optional, tag="3")]
+    pub evt_block_time: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint64, tag="4")]
+    pub evt_block_number: u64,
+    #[prost(bytes="vec", tag="5")]
+    pub recipient: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="6")]
+    pub schema_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag="7")]
+    pub distinct_schema_count: u64,
+    #[prost(uint64, tag="8")]
+    pub evt_block_timestamp_seconds: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AttestationStatus {
+    #[prost(bytes="vec", tag="1")]
+    pub uid: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="2")]
+    pub schema_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="3")]
+    pub attester: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="4")]
+    pub recipient: ::prost::alloc::vec::Vec<u8>,
+    #[prost(enumeration="AttestationState", tag="5")]
+    pub state: i32,
+    #[prost(uint64, tag="6")]
+    pub updated_block_number: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ValiditySnapshot {
+    #[prost(uint64, tag="1")]
+    pub block_number: u64,
+    #[prost(message, optional, tag="2")]
+    pub block_time: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint64, tag="3")]
+    pub block_timestamp_seconds: u64,
+    #[prost(message, repeated, tag="4")]
+    pub schema_active_counts: ::prost::alloc::vec::Vec<SchemaActiveCount>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SchemaActiveCount {
+    #[prost(bytes="vec", tag="1")]
+    pub schema_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(int64, tag="2")]
+    pub active_attestations: i64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Schemas {
+    #[prost(message, repeated, tag="1")]
+    pub schemas: ::prost::alloc::vec::Vec<Schema>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Schema {
+    #[prost(bytes="vec", tag="1")]
+    pub evt_tx_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint32, tag="2")]
+    pub evt_index: u32,
+    #[prost(message, optional, tag="3")]
+    pub evt_block_time: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint64, tag="4")]
+    pub evt_block_timestamp_seconds: u64,
+    #[prost(uint64, tag="5")]
+    pub evt_block_number: u64,
+    #[prost(bytes="vec", tag="6")]
+    pub uid: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="7")]
+    pub registerer: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag="8")]
+    pub schema: ::prost::alloc::string::String,
+    #[prost(bool, tag="9")]
+    pub revocable: bool,
+    #[prost(bytes="vec", tag="10")]
+    pub registered_resolver: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="11")]
+    pub current_resolver: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="13")]
+    pub schema_shape_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag="14")]
+    pub canonical_schema: ::prost::alloc::string::String,
+    #[prost(bytes="vec", tag="15")]
+    pub evt_address: ::prost::alloc::vec::Vec<u8>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SchemaDivergences {
+    #[prost(message, repeated, tag="1")]
+    pub schema_divergences: ::prost::alloc::vec::Vec<SchemaDivergence>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SchemaDivergence {
+    #[prost(bytes="vec", tag="1")]
+    pub evt_tx_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag="2")]
+    pub evt_block_number: u64,
+    #[prost(bytes="vec", tag="3")]
+    pub uid: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag="4")]
+    pub schema: ::prost::alloc::string::String,
+    #[prost(bytes="vec", tag="5")]
+    pub sibling_registry: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bool, tag="6")]
+    pub sibling_registered: bool,
+    #[prost(string, tag="7")]
+    pub sibling_schema: ::prost::alloc::string::String,
+    #[prost(bool, tag="8")]
+    pub is_divergent: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AttestationCheckpoint {
+    #[prost(uint64, tag="1")]
+    pub block_number: u64,
+    #[prost(message, optional, tag="2")]
+    pub block_time: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint64, tag="3")]
+    pub block_timestamp_seconds: u64,
+    #[prost(int64, tag="4")]
+    pub total_attestations: i64,
+    #[prost(message, repeated, tag="5")]
+    pub schema_attestation_counts: ::prost::alloc::vec::Vec<SchemaAttestationCount>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SchemaAttestationCount {
+    #[prost(bytes="vec", tag="1")]
+    pub schema_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(int64, tag="2")]
+    pub attestations: i64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResolverLog {
+    #[prost(bytes="vec", tag="1")]
+    pub address: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, repeated, tag="2")]
+    pub topics: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(string, tag="3")]
+    pub data: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FailedAttestations {
+    #[prost(message, repeated, tag="1")]
+    pub failed_attestations: ::prost::alloc::vec::Vec<FailedAttestation>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FailedAttestation {
+    #[prost(bytes="vec", tag="1")]
+    pub tx_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint32, tag="2")]
+    pub call_index: u32,
+    #[prost(message, optional, tag="3")]
+    pub block_time: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint64, tag="4")]
+    pub block_timestamp_seconds: u64,
+    #[prost(uint64, tag="5")]
+    pub block_number: u64,
+    #[prost(bytes="vec", tag="6")]
+    pub tx_from: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="7")]
+    pub caller: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag="8")]
+    pub method: ::prost::alloc::string::String,
+    #[prost(bytes="vec", repeated, tag="9")]
+    pub schema_ids: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    #[prost(string, tag="10")]
+    pub revert_reason: ::prost::alloc::string::String,
+    #[prost(bool, tag="11")]
+    pub tx_failed: bool,
+    #[prost(bool, tag="12")]
+    pub resolver_call_succeeded: bool,
+    #[prost(bool, tag="13")]
+    pub resolver_call_rejected: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResolverUsages {
+    #[prost(message, repeated, tag="1")]
+    pub resolver_usages: ::prost::alloc::vec::Vec<ResolverUsage>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResolverUsage {
+    #[prost(bytes="vec", tag="1")]
+    pub resolver: ::prost::alloc::vec::Vec<u8>,
+    #[prost(int64, tag="2")]
+    pub schemas: i64,
+    #[prost(int64, tag="3")]
+    pub attestations: i64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Revocations {
+    #[prost(message, repeated, tag="1")]
+    pub revocations: ::prost::alloc::vec::Vec<Revocation>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Revocation {
+    #[prost(enumeration="RevocationKind", tag="1")]
+    pub kind: i32,
+    #[prost(bytes="vec", tag="2")]
+    pub evt_tx_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint32, tag="3")]
+    pub evt_index: u32,
+    #[prost(message, optional, tag="4")]
+    pub evt_block_time: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint64, tag="5")]
+    pub evt_block_timestamp_seconds: u64,
+    #[prost(uint64, tag="6")]
+    pub evt_block_number: u64,
+    #[prost(bytes="vec", tag="7")]
+    pub revoker: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="8")]
+    pub uid: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag="9")]
+    pub time: u64,
+    #[prost(bytes="vec", tag="10")]
+    pub schema_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="11")]
+    pub recipient: ::prost::alloc::vec::Vec<u8>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EventsHex {
+    #[prost(message, repeated, tag="1")]
+    pub eas_attesteds: ::prost::alloc::vec::Vec<EasAttestedHex>,
+    #[prost(message, repeated, tag="2")]
+    pub eas_revokeds: ::prost::alloc::vec::Vec<EasRevokedHex>,
+    #[prost(message, repeated, tag="3")]
+    pub eas_revoked_offchains: ::prost::alloc::vec::Vec<EasRevokedOffchainHex>,
+    #[prost(message, repeated, tag="4")]
+    pub eas_timestampeds: ::prost::alloc::vec::Vec<EasTimestampedHex>,
+    #[prost(string, repeated, tag="5")]
+    pub touched_keys: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(bool, tag="6")]
+    pub final_blocks_only: bool,
+    #[prost(uint64, tag="7")]
+    pub chain_id: u64,
+    #[prost(message, optional, tag="8")]
+    pub metrics: ::core::option::Option<PipelineMetrics>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EasAttestedHex {
+    #[prost(string, tag="1")]
+    pub evt_tx_hash: ::prost::alloc::string::String,
+    #[prost(uint32, tag="2")]
+    pub evt_index: u32,
+    #[prost(message, optional, tag="3")]
+    pub evt_block_time: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint64, tag="4")]
+    pub evt_block_number: u64,
+    #[prost(string, tag="5")]
+    pub recipient: ::prost::alloc::string::String,
+    #[prost(string, tag="6")]
+    pub attester: ::prost::alloc::string::String,
+    #[prost(string, tag="7")]
+    pub uid: ::prost::alloc::string::String,
+    #[prost(string, tag="8")]
+    pub schema_id: ::prost::alloc::string::String,
+    #[prost(string, tag="9")]
+    pub data: ::prost::alloc::string::String,
+    #[prost(string, tag="10")]
+    pub schema: ::prost::alloc::string::String,
+    #[prost(string, tag="11")]
+    pub decoded_data: ::prost::alloc::string::String,
+    #[prost(uint64, tag="12")]
+    pub evt_block_timestamp_seconds: u64,
+    #[prost(string, tag="13")]
+    pub aa_sender: ::prost::alloc::string::String,
+    #[prost(string, tag="14")]
+    pub bundler: ::prost::alloc::string::String,
+    #[prost(bool, tag="15")]
+    pub enrichment_failed: bool,
+    #[prost(string, tag="16")]
+    pub decoded_values: ::prost::alloc::string::String,
+    #[prost(string, tag="17")]
+    pub data_keccak: ::prost::alloc::string::String,
+    #[prost(uint64, tag="18")]
+    pub schema_registered_block: u64,
+    #[prost(message, optional, tag="19")]
+    pub schema_registered_time: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint64, tag="20")]
+    pub attestation_time: u64,
+    #[prost(bool, tag="21")]
+    pub time_mismatch: bool,
+    #[prost(uint32, tag="22")]
+    pub schema_field_count: u32,
+    #[prost(string, repeated, tag="23")]
+    pub schema_field_types: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(uint64, tag="24")]
+    pub tx_nonce: u64,
+    #[prost(string, tag="25")]
+    pub tx_method_selector: ::prost::alloc::string::String,
+    #[prost(uint32, tag="26")]
+    pub extra_data_bytes: u32,
+    #[prost(string, tag="27")]
+    pub extra_data_hex: ::prost::alloc::string::String,
+    #[prost(string, repeated, tag="28")]
+    pub missing_fields: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(string, tag="29")]
+    pub ref_uid: ::prost::alloc::string::String,
+    #[prost(string, tag="30")]
+    pub schema_shape_id: ::prost::alloc::string::String,
+    #[prost(string, tag="31")]
+    pub module_version: ::prost::alloc::string::String,
+    #[prost(uint32, tag="32")]
+    pub decoding_ruleset_version: u32,
+    #[prost(bool, tag="33")]
+    pub revocability_conflict: bool,
+    #[prost(string, tag="34")]
+    pub evt_address: ::prost::alloc::string::String,
+    #[prost(uint64, tag="35")]
+    pub expiration_time: u64,
+    #[prost(bool, tag="36")]
+    pub resolver_call_rejected: bool,
+    #[prost(uint64, tag="37")]
+    pub schema_handle: u64,
+    #[prost(uint32, tag="38")]
+    pub evt_tx_ordinal: u32,
+    #[prost(bool, tag="39")]
+    pub is_expired_at_emission: bool,
+    #[prost(string, tag="40")]
+    pub attester_label: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="41")]
+    pub raw_attestation: ::core::option::Option<RawAttestation>,
+    #[prost(string, tag="42")]
+    pub decoded_data_compressed: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EasRevokedHex {
+    #[prost(string, tag="1")]
+    pub evt_tx_hash: ::prost::alloc::string::String,
+    #[prost(uint32, tag="2")]
+    pub evt_index: u32,
+    #[prost(message, optional, tag="3")]
+    pub evt_block_time: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint64, tag="4")]
+    pub evt_block_number: u64,
+    #[prost(string, tag="5")]
+    pub recipient: ::prost::alloc::string::String,
+    #[prost(string, tag="6")]
+    pub attester: ::prost::alloc::string::String,
+    #[prost(string, tag="7")]
+    pub uid: ::prost::alloc::string::String,
+    #[prost(string, tag="8")]
+    pub schema: ::prost::alloc::string::String,
+    #[prost(uint64, tag="9")]
+    pub evt_block_timestamp_seconds: u64,
+    #[prost(string, tag="10")]
+    pub delegation_signer: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag="11")]
+    pub resolver_context: ::prost::alloc::vec::Vec<ResolverLogHex>,
+    #[prost(string, tag="12")]
+    pub module_version: ::prost::alloc::string::String,
+    #[prost(string, tag="13")]
+    pub evt_address: ::prost::alloc::string::String,
+    #[prost(uint32, tag="14")]
+    pub evt_tx_ordinal: u32,
+    #[prost(string, tag="15")]
+    pub attester_label: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EasRevokedOffchainHex {
+    #[prost(string, tag="1")]
+    pub evt_tx_hash: ::prost::alloc::string::String,
+    #[prost(uint32, tag="2")]
+    pub evt_index: u32,
+    #[prost(message, optional, tag="3")]
+    pub evt_block_time: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint64, tag="4")]
+    pub evt_block_number: u64,
+    #[prost(string, tag="5")]
+    pub revoker: ::prost::alloc::string::String,
+    #[prost(string, tag="6")]
+    pub data: ::prost::alloc::string::String,
+    #[prost(uint64, tag="7")]
+    pub timestamp: u64,
+    #[prost(uint64, tag="8")]
+    pub evt_block_timestamp_seconds: u64,
+    #[prost(string, tag="9")]
+    pub module_version: ::prost::alloc::string::String,
+    #[prost(string, tag="10")]
+    pub evt_address: ::prost::alloc::string::String,
+    #[prost(uint32, tag="11")]
+    pub evt_tx_ordinal: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EasTimestampedHex {
+    #[prost(string, tag="1")]
+    pub evt_tx_hash: ::prost::alloc::string::String,
+    #[prost(uint32, tag="2")]
+    pub evt_index: u32,
+    #[prost(message, optional, tag="3")]
+    pub evt_block_time: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint64, tag="4")]
+    pub evt_block_number: u64,
+    #[prost(string, tag="5")]
+    pub data: ::prost::alloc::string::String,
+    #[prost(uint64, tag="6")]
+    pub timestamp: u64,
+    #[prost(uint32, tag="7")]
+    pub batch_index: u32,
+    #[prost(uint32, tag="8")]
+    pub batch_size: u32,
+    #[prost(uint64, tag="9")]
+    pub evt_block_timestamp_seconds: u64,
+    #[prost(string, tag="10")]
+    pub module_version: ::prost::alloc::string::String,
+    #[prost(string, tag="11")]
+    pub evt_address: ::prost::alloc::string::String,
+    #[prost(uint32, tag="12")]
+    pub evt_tx_ordinal: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResolverLogHex {
+    #[prost(string, tag="1")]
+    pub address: ::prost::alloc::string::String,
+    #[prost(string, repeated, tag="2")]
+    pub topics: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(string, tag="3")]
+    pub data: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AttestationBlock {
+    #[prost(uint64, tag="1")]
+    pub block_number: u64,
+    #[prost(uint64, tag="2")]
+    pub block_timestamp_seconds: u64,
+    #[prost(bytes="vec", tag="3")]
+    pub schema_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="4")]
+    pub attester: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="5")]
+    pub recipient: ::prost::alloc::vec::Vec<u8>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RefLatencies {
+    #[prost(message, repeated, tag="1")]
+    pub ref_latencies: ::prost::alloc::vec::Vec<RefLatency>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RefLatency {
+    #[prost(bytes="vec", tag="1")]
+    pub evt_tx_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint32, tag="2")]
+    pub evt_index: u32,
+    #[prost(message, optional, tag="3")]
+    pub evt_block_time: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint64, tag="4")]
+    pub evt_block_timestamp_seconds: u64,
+    #[prost(uint64, tag="5")]
+    pub evt_block_number: u64,
+    #[prost(bytes="vec", tag="6")]
+    pub uid: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="7")]
+    pub ref_uid: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag="8")]
+    pub ref_block_number: u64,
+    #[prost(uint64, tag="9")]
+    pub ref_latency_blocks: u64,
+    #[prost(uint64, tag="10")]
+    pub ref_latency_seconds: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SchemaAttesters {
+    #[prost(message, repeated, tag="1")]
+    pub schema_attesters: ::prost::alloc::vec::Vec<SchemaAttester>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SchemaAttester {
+    #[prost(bytes="vec", tag="1")]
+    pub schema_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="2")]
+    pub first_attester: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag="3")]
+    pub first_attested_block_number: u64,
+    #[prost(bytes="vec", tag="4")]
+    pub latest_attester: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag="5")]
+    pub latest_attested_block_number: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GroupedEvents {
+    #[prost(message, repeated, tag="1")]
+    pub attesteds: ::prost::alloc::vec::Vec<GroupedAttested>,
+    #[prost(message, repeated, tag="2")]
+    pub revokeds: ::prost::alloc::vec::Vec<GroupedRevoked>,
+    #[prost(message, repeated, tag="3")]
+    pub revoked_offchains: ::prost::alloc::vec::Vec<GroupedRevokedOffchain>,
+    #[prost(message, repeated, tag="4")]
+    pub timestampeds: ::prost::alloc::vec::Vec<GroupedTimestamped>,
+    #[prost(uint64, tag="5")]
+    pub chain_id: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GroupedAttested {
+    #[prost(message, optional, tag="1")]
+    pub event: ::core::option::Option<EasAttested>,
+    #[prost(string, repeated, tag="2")]
+    pub groups: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GroupedRevoked {
+    #[prost(message, optional, tag="1")]
+    pub event: ::core::option::Option<EasRevoked>,
+    #[prost(string, repeated, tag="2")]
+    pub groups: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GroupedRevokedOffchain {
+    #[prost(message, optional, tag="1")]
+    pub event: ::core::option::Option<EasRevokedOffchain>,
+    #[prost(string, repeated, tag="2")]
+    pub groups: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GroupedTimestamped {
+    #[prost(message, optional, tag="1")]
+    pub event: ::core::option::Option<EasTimestamped>,
+    #[prost(string, repeated, tag="2")]
+    pub groups: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WebhookEvents {
+    #[prost(message, repeated, tag="1")]
+    pub webhook_events: ::prost::alloc::vec::Vec<WebhookEvent>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WebhookEvent {
+    #[prost(string, tag="1")]
+    pub event_type: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub chain: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="4")]
+    pub occurred_at: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(string, tag="5")]
+    pub payload: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct KeyedEvents {
+    #[prost(message, repeated, tag="1")]
+    pub keyed_events: ::prost::alloc::vec::Vec<KeyedEvent>,
+    #[prost(uint64, tag="2")]
+    pub chain_id: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct KeyedEvent {
+    #[prost(string, tag="1")]
+    pub partition_key: ::prost::alloc::string::String,
+    #[prost(uint32, tag="2")]
+    pub evt_index: u32,
+    #[prost(message, optional, tag="3")]
+    pub attested: ::core::option::Option<EasAttested>,
+    #[prost(message, optional, tag="4")]
+    pub revoked: ::core::option::Option<EasRevoked>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PipelineMetrics {
+    #[prost(uint64, tag="1")]
+    pub block_number: u64,
+    #[prost(uint64, tag="2")]
+    pub attested_total: u64,
+    #[prost(uint64, tag="3")]
+    pub revoked_total: u64,
+    #[prost(uint64, tag="4")]
+    pub revoked_offchain_total: u64,
+    #[prost(uint64, tag="5")]
+    pub timestamped_total: u64,
+    #[prost(uint64, tag="6")]
+    pub rpc_calls: u64,
+    #[prost(uint64, tag="7")]
+    pub decode_failures: u64,
+    #[prost(uint64, tag="8")]
+    pub schemas_parsed: u64,
+    #[prost(uint64, tag="9")]
+    pub schema_cache_hits: u64,
+    #[prost(uint64, tag="10")]
+    pub eas_logs: u64,
+    #[prost(uint64, tag="11")]
+    pub decoded_bytes: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SchemaCatalog {
+    #[prost(uint64, tag="1")]
+    pub block_number: u64,
+    #[prost(message, repeated, tag="2")]
+    pub schemas: ::prost::alloc::vec::Vec<Schema>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SchemaAliases {
+    #[prost(message, repeated, tag="1")]
+    pub schema_aliases: ::prost::alloc::vec::Vec<SchemaAlias>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SchemaAlias {
+    #[prost(bytes="vec", tag="1")]
+    pub evt_tx_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint32, tag="2")]
+    pub evt_index: u32,
+    #[prost(message, optional, tag="3")]
+    pub evt_block_time: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint64, tag="4")]
+    pub evt_block_timestamp_seconds: u64,
+    #[prost(uint64, tag="5")]
+    pub evt_block_number: u64,
+    #[prost(bytes="vec", tag="6")]
+    pub uid: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="7")]
+    pub schema_shape_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag="8")]
+    pub canonical_schema: ::prost::alloc::string::String,
+    #[prost(bytes="vec", repeated, tag="9")]
+    pub alias_uids: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RecipientEvent {
+    #[prost(bytes="vec", tag="1")]
+    pub uid: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag="2")]
+    pub block_number: u64,
+    #[prost(uint64, tag="3")]
+    pub block_timestamp_seconds: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AttestationChurns {
+    #[prost(message, repeated, tag="1")]
+    pub attestation_churns: ::prost::alloc::vec::Vec<AttestationChurn>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AttestationChurn {
+    #[prost(bytes="vec", tag="1")]
+    pub evt_tx_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint32, tag="2")]
+    pub evt_index: u32,
+    #[prost(message, optional, tag="3")]
+    pub evt_block_time: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint64, tag="4")]
+    pub evt_block_timestamp_seconds: u64,
+    #[prost(uint64, tag="5")]
+    pub evt_block_number: u64,
+    #[prost(bytes="vec", tag="6")]
+    pub schema_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="7")]
+    pub recipient: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="8")]
+    pub revoked_uid: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="9")]
+    pub new_uid: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag="10")]
+    pub gap_seconds: u64,
+    #[prost(bool, tag="11")]
+    pub attested_first: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CurrentCredential {
+    #[prost(bytes="vec", tag="1")]
+    pub uid: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="2")]
+    pub schema_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="3")]
+    pub recipient: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="4")]
+    pub attester: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag="5")]
+    pub decoded_data: ::prost::alloc::string::String,
+    #[prost(uint64, tag="6")]
+    pub block_number: u64,
+    #[prost(uint64, tag="7")]
+    pub block_timestamp_seconds: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CurrentCredentialChanges {
+    #[prost(message, repeated, tag="1")]
+    pub current_credential_changes: ::prost::alloc::vec::Vec<CurrentCredentialChange>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CurrentCredentialChange {
+    #[prost(string, tag="1")]
+    pub key: ::prost::alloc::string::String,
+    #[prost(bool, tag="2")]
+    pub removed: bool,
+    #[prost(message, optional, tag="3")]
+    pub credential: ::core::option::Option<CurrentCredential>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Supersessions {
+    #[prost(message, repeated, tag="1")]
+    pub supersessions: ::prost::alloc::vec::Vec<Supersession>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Supersession {
+    #[prost(bytes="vec", tag="1")]
+    pub evt_tx_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint32, tag="2")]
+    pub evt_index: u32,
+    #[prost(message, optional, tag="3")]
+    pub evt_block_time: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint64, tag="4")]
+    pub evt_block_timestamp_seconds: u64,
+    #[prost(uint64, tag="5")]
+    pub evt_block_number: u64,
+    #[prost(bytes="vec", tag="6")]
+    pub uid: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="7")]
+    pub superseded_by: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="8")]
+    pub schema_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="9")]
+    pub recipient: ::prost::alloc::vec::Vec<u8>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RevocationCorrections {
+    #[prost(message, repeated, tag="1")]
+    pub revocation_corrections: ::prost::alloc::vec::Vec<RevocationCorrection>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RevocationCorrection {
+    #[prost(uint64, tag="1")]
+    pub block_number: u64,
+    #[prost(bytes="vec", tag="2")]
+    pub uid: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="3")]
+    pub schema_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="4")]
+    pub attester: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="5")]
+    pub recipient: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag="6")]
+    pub revocation_time: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AdminEvents {
+    #[prost(message, repeated, tag="1")]
+    pub admin_events: ::prost::alloc::vec::Vec<AdminEvent>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AdminEvent {
+    #[prost(bytes="vec", tag="1")]
+    pub evt_tx_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint32, tag="2")]
+    pub evt_index: u32,
+    #[prost(message, optional, tag="3")]
+    pub evt_block_time: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint64, tag="4")]
+    pub evt_block_timestamp_seconds: u64,
+    #[prost(uint64, tag="5")]
+    pub evt_block_number: u64,
+    #[prost(bytes="vec", tag="6")]
+    pub contract: ::prost::alloc::vec::Vec<u8>,
+    #[prost(enumeration="AdminEventKind", tag="7")]
+    pub kind: i32,
+    #[prost(bytes="vec", tag="8")]
+    pub previous_address: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="9")]
+    pub new_address: ::prost::alloc::vec::Vec<u8>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FirstAttestations {
+    #[prost(message, repeated, tag="1")]
+    pub first_attestations: ::prost::alloc::vec::Vec<FirstAttestation>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FirstAttestation {
+    #[prost(bytes="vec", tag="1")]
+    pub evt_tx_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint32, tag="2")]
+    pub evt_index: u32,
+    #[prost(message, optional, tag="3")]
+    pub evt_block_time: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint64, tag="4")]
+    pub evt_block_timestamp_seconds: u64,
+    #[prost(uint64, tag="5")]
+    pub evt_block_number: u64,
+    #[prost(bytes="vec", tag="6")]
+    pub schema_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="7")]
+    pub uid: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="8")]
+    pub attester: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="9")]
+    pub recipient: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag="10")]
+    pub schema: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DuplicateAttestations {
+    #[prost(message, repeated, tag="1")]
+    pub duplicate_attestations: ::prost::alloc::vec::Vec<DuplicateAttestation>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DuplicateAttestation {
+    #[prost(bytes="vec", tag="1")]
+    pub evt_tx_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint32, tag="2")]
+    pub evt_index: u32,
+    #[prost(message, optional, tag="3")]
+    pub evt_block_time: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint64, tag="4")]
+    pub evt_block_timestamp_seconds: u64,
+    #[prost(uint64, tag="5")]
+    pub evt_block_number: u64,
+    #[prost(bytes="vec", tag="6")]
+    pub uid: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="7")]
+    pub schema_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="8")]
+    pub recipient: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="9")]
+    pub attester: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="10")]
+    pub content_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="11")]
+    pub duplicate_of_uid: ::prost::alloc::vec::Vec<u8>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ActiveAttesters {
+    #[prost(uint64, tag="1")]
+    pub day: u64,
+    #[prost(uint64, tag="2")]
+    pub block_number: u64,
+    #[prost(uint64, tag="3")]
+    pub daily_active_attesters: u64,
+    #[prost(uint64, tag="4")]
+    pub weekly_active_attesters: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CredentialPortfolios {
+    #[prost(message, repeated, tag="1")]
+    pub credential_portfolios: ::prost::alloc::vec::Vec<CredentialPortfolio>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CredentialPortfolio {
+    #[prost(bytes="vec", tag="1")]
+    pub recipient: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag="2")]
+    pub block_number: u64,
+    #[prost(message, repeated, tag="3")]
+    pub credentials: ::prost::alloc::vec::Vec<PortfolioCredential>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PortfolioCredential {
+    #[prost(bytes="vec", tag="1")]
+    pub schema_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="2")]
+    pub uid: ::prost::alloc::vec::Vec<u8>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExpiringAttestations {
+    #[prost(message, repeated, tag="1")]
+    pub expiring_attestations: ::prost::alloc::vec::Vec<ExpiringAttestation>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExpiringAttestation {
+    #[prost(bytes="vec", tag="1")]
+    pub uid: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="2")]
+    pub schema_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="3")]
+    pub attester: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="4")]
+    pub recipient: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag="5")]
+    pub expiration_time: u64,
+    #[prost(uint64, tag="6")]
+    pub seconds_until_expiration: u64,
+    #[prost(uint64, tag="7")]
+    pub block_number: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DormantSchemas {
+    #[prost(message, repeated, tag="1")]
+    pub dormant_schemas: ::prost::alloc::vec::Vec<SchemaDormant>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SchemaDormant {
+    #[prost(bytes="vec", tag="1")]
+    pub schema_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag="2")]
+    pub block_number: u64,
+    #[prost(uint64, tag="3")]
+    pub last_active_day: u64,
+    #[prost(uint64, tag="4")]
+    pub dormant_days: u64,
+    #[prost(uint64, tag="5")]
+    pub attestation_count: u64,
+    #[prost(double, tag="6")]
+    pub average_daily_attestations: f64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SchemaReferences {
+    #[prost(message, repeated, tag="1")]
+    pub schema_references: ::prost::alloc::vec::Vec<SchemaReference>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SchemaReference {
+    #[prost(bytes="vec", tag="1")]
+    pub child_schema_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="2")]
+    pub parent_schema_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag="3")]
+    pub reference_count: u64,
+    #[prost(uint64, tag="4")]
+    pub block_number: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SchemaDictionaryEvents {
+    #[prost(message, repeated, tag="1")]
+    pub schema_dictionary: ::prost::alloc::vec::Vec<SchemaDictionaryEntry>,
+    #[prost(message, optional, tag="2")]
+    pub events: ::core::option::Option<Events>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SchemaDictionaryEntry {
+    #[prost(uint64, tag="1")]
+    pub handle: u64,
+    #[prost(bytes="vec", tag="2")]
+    pub schema_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag="3")]
+    pub schema: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StatusDeltas {
+    #[prost(message, repeated, tag="1")]
+    pub status_deltas: ::prost::alloc::vec::Vec<StatusDelta>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StatusDelta {
+    #[prost(string, tag="1")]
+    pub key: ::prost::alloc::string::String,
+    #[prost(uint64, tag="2")]
+    pub ordinal: u64,
+    #[prost(message, optional, tag="3")]
+    pub old_status: ::core::option::Option<AttestationStatus>,
+    #[prost(message, optional, tag="4")]
+    pub new_status: ::core::option::Option<AttestationStatus>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SchemaFieldStats {
+    #[prost(uint64, tag="1")]
+    pub block_number: u64,
+    #[prost(message, repeated, tag="2")]
+    pub field_stats: ::prost::alloc::vec::Vec<SchemaFieldStat>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SchemaFieldStat {
+    #[prost(bytes="vec", tag="1")]
+    pub schema_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag="2")]
+    pub field: ::prost::alloc::string::String,
+    #[prost(uint64, tag="3")]
+    pub decoded_attestations: u64,
+    #[prost(uint64, tag="4")]
+    pub zero_values: u64,
+    #[prost(double, tag="5")]
+    pub zero_rate: f64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RawAttestation {
+    #[prost(string, tag="1")]
+    pub uid: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub schema: ::prost::alloc::string::String,
+    #[prost(uint64, tag="3")]
+    pub time: u64,
+    #[prost(uint64, tag="4")]
+    pub expiration_time: u64,
+    #[prost(uint64, tag="5")]
+    pub revocation_time: u64,
+    #[prost(string, tag="6")]
+    pub ref_uid: ::prost::alloc::string::String,
+    #[prost(string, tag="7")]
+    pub recipient: ::prost::alloc::string::String,
+    #[prost(string, tag="8")]
+    pub attester: ::prost::alloc::string::String,
+    #[prost(bool, tag="9")]
+    pub revocable: bool,
+    #[prost(string, tag="10")]
+    pub data: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SchemaFields {
+    #[prost(message, repeated, tag="1")]
+    pub fields: ::prost::alloc::vec::Vec<SchemaField>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SchemaField {
+    #[prost(string, tag="1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub primitive: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag="3")]
+    pub components: ::prost::alloc::vec::Vec<SchemaField>,
+    #[prost(uint64, repeated, tag="4")]
+    pub array_lengths: ::prost::alloc::vec::Vec<u64>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EventsBySchema {
+    #[prost(message, repeated, tag="1")]
+    pub schemas: ::prost::alloc::vec::Vec<SchemaAttesteds>,
+    #[prost(uint64, tag="2")]
+    pub chain_id: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SchemaAttesteds {
+    #[prost(bytes="vec", tag="1")]
+    pub schema_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag="2")]
+    pub schema: ::prost::alloc::string::String,
+    #[prost(uint32, tag="3")]
+    pub schema_field_count: u32,
+    #[prost(string, repeated, tag="4")]
+    pub schema_field_types: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(bytes="vec", tag="5")]
+    pub schema_shape_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag="6")]
+    pub schema_registered_block: u64,
+    #[prost(message, optional, tag="7")]
+    pub schema_registered_time: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(message, repeated, tag="8")]
+    pub attesteds: ::prost::alloc::vec::Vec<EasAttested>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SchemaDescription {
+    #[prost(bytes="vec", tag="1")]
+    pub schema_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="2")]
+    pub attestation_uid: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="3")]
+    pub attester: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag="4")]
+    pub description: ::prost::alloc::string::String,
+    #[prost(uint32, tag="5")]
+    pub evt_index: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DescribedSchemas {
+    #[prost(message, repeated, tag="1")]
+    pub described_schemas: ::prost::alloc::vec::Vec<DescribedSchema>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DescribedSchema {
+    #[prost(message, optional, tag="1")]
+    pub schema: ::core::option::Option<Schema>,
+    #[prost(message, optional, tag="2")]
+    pub description: ::core::option::Option<SchemaDescription>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SchemaDictionary {
+    #[prost(uint64, tag="1")]
+    pub block_number: u64,
+    #[prost(message, repeated, tag="2")]
+    pub schema_dictionary: ::prost::alloc::vec::Vec<SchemaDictionaryEntry>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SchemaDescriptionRevocation {
+    #[prost(bytes="vec", tag="1")]
+    pub attestation_uid: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint32, tag="2")]
+    pub evt_index: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SchemaDescriptionChanges {
+    #[prost(message, repeated, tag="1")]
+    pub schema_descriptions: ::prost::alloc::vec::Vec<SchemaDescription>,
+    #[prost(message, repeated, tag="2")]
+    pub revocations: ::prost::alloc::vec::Vec<SchemaDescriptionRevocation>,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum AddressRole {
+    Unspecified = 0,
+    Attester = 1,
+    Recipient = 2,
+}
+impl AddressRole {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            AddressRole::Unspecified => "ADDRESS_ROLE_UNSPECIFIED",
+            AddressRole::Attester => "ADDRESS_ROLE_ATTESTER",
+            AddressRole::Recipient => "ADDRESS_ROLE_RECIPIENT",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "ADDRESS_ROLE_UNSPECIFIED" => Some(Self::Unspecified),
+            "ADDRESS_ROLE_ATTESTER" => Some(Self::Attester),
+            "ADDRESS_ROLE_RECIPIENT" => Some(Self::Recipient),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum AttestationState {
+    Unspecified = 0,
+    Active = 1,
+    Revoked = 2,
+    Superseded = 3,
+}
+impl AttestationState {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            AttestationState::Unspecified => "ATTESTATION_STATE_UNSPECIFIED",
+            AttestationState::Active => "ATTESTATION_STATE_ACTIVE",
+            AttestationState::Revoked => "ATTESTATION_STATE_REVOKED",
+            AttestationState::Superseded => "ATTESTATION_STATE_SUPERSEDED",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "ATTESTATION_STATE_UNSPECIFIED" => Some(Self::Unspecified),
+            "ATTESTATION_STATE_ACTIVE" => Some(Self::Active),
+            "ATTESTATION_STATE_REVOKED" => Some(Self::Revoked),
+            "ATTESTATION_STATE_SUPERSEDED" => Some(Self::Superseded),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum RevocationKind {
+    Unspecified = 0,
+    Onchain = 1,
+    Offchain = 2,
+}
+impl RevocationKind {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            RevocationKind::Unspecified => "REVOCATION_KIND_UNSPECIFIED",
+            RevocationKind::Onchain => "REVOCATION_KIND_ONCHAIN",
+            RevocationKind::Offchain => "REVOCATION_KIND_OFFCHAIN",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "REVOCATION_KIND_UNSPECIFIED" => Some(Self::Unspecified),
+            "REVOCATION_KIND_ONCHAIN" => Some(Self::Onchain),
+            "REVOCATION_KIND_OFFCHAIN" => Some(Self::Offchain),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum AdminEventKind {
+    Unspecified = 0,
+    Upgraded = 1,
+    AdminChanged = 2,
+    OwnershipTransferred = 3,
+}
+impl AdminEventKind {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            AdminEventKind::Unspecified => "ADMIN_EVENT_KIND_UNSPECIFIED",
+            AdminEventKind::Upgraded => "ADMIN_EVENT_KIND_UPGRADED",
+            AdminEventKind::AdminChanged => "ADMIN_EVENT_KIND_ADMIN_CHANGED",
+            AdminEventKind::OwnershipTransferred => "ADMIN_EVENT_KIND_OWNERSHIP_TRANSFERRED",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "ADMIN_EVENT_KIND_UNSPECIFIED" => Some(Self::Unspecified),
+            "ADMIN_EVENT_KIND_UPGRADED" => Some(Self::Upgraded),
+            "ADMIN_EVENT_KIND_ADMIN_CHANGED" => Some(Self::AdminChanged),
+            "ADMIN_EVENT_KIND_OWNERSHIP_TRANSFERRED" => Some(Self::OwnershipTransferred),
+            _ => None,
+        }
+    }
 }
 // @@protoc_insertion_point(module)
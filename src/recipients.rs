@@ -0,0 +1,513 @@
+use crate::params::Params;
+use crate::pb::contract::v1 as contract;
+use std::collections::BTreeSet;
+use substreams::pb::substreams::store_delta::Operation;
+use substreams::pb::substreams::Clock;
+use substreams::store::{
+    DeltaInt64, DeltaProto, Deltas, StoreAdd, StoreAddInt64, StoreAppend, StoreDelete, StoreGet, StoreGetArray, StoreGetProto, StoreSet, StoreSetIfNotExists,
+    StoreSetIfNotExistsInt64, StoreSetProto,
+};
+use substreams::Hex;
+
+/// Store key of a schema attested to a recipient: `<recipient>:<schema_id>`.
+pub fn recipient_schema_key(recipient: &[u8], schema_id: &[u8]) -> String {
+    format!("{}:{}", Hex(recipient), Hex(schema_id))
+}
+
+/// `(ordinal, key, block number)` writes of the attestations of the block, in log order.
+fn recipient_schema_writes(events: &contract::Events) -> Vec<(u64, String, i64)> {
+    events
+        .eas_attesteds
+        .iter()
+        .map(|attested| {
+            (
+                attested.evt_index as u64,
+                recipient_schema_key(&attested.recipient, &attested.schema_id),
+                attested.evt_block_number as i64,
+            )
+        })
+        .collect()
+}
+
+/// Recipient index: records the block of the first attestation of each schema to each recipient.
+#[substreams::handlers::store]
+fn store_recipient_schemas(events: contract::Events, store: StoreSetIfNotExistsInt64) {
+    for (ordinal, key, block_number) in recipient_schema_writes(&events) {
+        store.set_if_not_exists(ordinal, key, &block_number);
+    }
+}
+
+/// `(ordinal, recipient key)` increments of the recipient and schema pairs created in `store_recipient_schemas`.
+fn recipient_schema_count_increments(deltas: &Deltas<DeltaInt64>) -> Vec<(u64, String)> {
+    deltas
+        .deltas
+        .iter()
+        .filter(|delta| delta.operation == Operation::Create)
+        .map(|delta| (delta.ordinal, substreams::key::first_segment(&delta.key).to_string()))
+        .collect()
+}
+
+/// Counts the distinct schemas attested to each recipient, keyed by the hex-encoded recipient.
+#[substreams::handlers::store]
+fn store_recipient_schema_counts(deltas: Deltas<DeltaInt64>, store: StoreAddInt64) {
+    for (ordinal, key) in recipient_schema_count_increments(&deltas) {
+        store.add(ordinal, key, 1);
+    }
+}
+
+/// Emits a record each time a recipient holding attestations from more than `min_schemas` distinct schemas
+/// collects attestations from a new schema.
+#[substreams::handlers::map]
+fn map_highly_attested_recipients(
+    params: String,
+    events: contract::Events,
+    deltas: Deltas<DeltaInt64>,
+) -> Result<contract::HighlyAttestedRecipients, substreams::errors::Error> {
+    let min_schemas = Params::parse(&params).get_or("min_schemas", 5)?;
+
+    let highly_attested_recipients = deltas
+        .deltas
+        .iter()
+        .filter(|delta| delta.new_value > min_schemas)
+        .filter_map(|delta| {
+            let attested = events.eas_attesteds.iter().find(|attested| attested.evt_index as u64 == delta.ordinal)?;
+
+            Some(contract::HighlyAttestedRecipient {
+                evt_tx_hash: attested.evt_tx_hash.clone(),
+                evt_index: attested.evt_index,
+                evt_block_time: attested.evt_block_time,
+                evt_block_timestamp_seconds: attested.evt_block_timestamp_seconds,
+                evt_block_number: attested.evt_block_number,
+                recipient: attested.recipient.clone(),
+                schema_id: attested.schema_id.clone(),
+                distinct_schema_count: delta.new_value as u64,
+            })
+        })
+        .collect();
+
+    Ok(contract::HighlyAttestedRecipients { highly_attested_recipients })
+}
+/// `(ordinal, key, event)` writes of the attestations and revocations of the block, in ordinal order.
+fn recipient_latest_event_writes(events: &contract::Events) -> Vec<(u64, String, contract::RecipientEvent)> {
+    let attested = events.eas_attesteds.iter().map(|attested| {
+        (
+            attested.evt_index as u64,
+            format!("attested:{}", recipient_schema_key(&attested.recipient, &attested.schema_id)),
+            contract::RecipientEvent {
+                uid: attested.uid.clone(),
+                block_number: attested.evt_block_number,
+                block_timestamp_seconds: attested.evt_block_timestamp_seconds,
+            },
+        )
+    });
+    let revoked = events.eas_revokeds.iter().map(|revoked| {
+        (
+            revoked.evt_index as u64,
+            format!("revoked:{}", recipient_schema_key(&revoked.recipient, &revoked.schema)),
+            contract::RecipientEvent {
+                uid: revoked.uid.clone(),
+                block_number: revoked.evt_block_number,
+                block_timestamp_seconds: revoked.evt_block_timestamp_seconds,
+            },
+        )
+    });
+
+    let mut writes: Vec<_> = attested.chain(revoked).collect();
+    writes.sort_by_key(|(ordinal, _, _)| *ordinal);
+    writes
+}
+
+/// Keeps the latest attestation and the latest revocation of each schema to each recipient, keyed by
+/// `attested:<recipient>:<schema_id>` and `revoked:<recipient>:<schema_id>`.
+#[substreams::handlers::store]
+fn store_recipient_latest_events(events: contract::Events, store: StoreSetProto<contract::RecipientEvent>) {
+    for (ordinal, key, event) in recipient_latest_event_writes(&events) {
+        store.set(ordinal, key, &event);
+    }
+}
+
+/// Emits a churn record when an attestation of a schema to a recipient is revoked and another one issued within
+/// `window_seconds` (param, default `86400`), in either order: revoked then re-attested, or re-attested then the
+/// previous one revoked, e.g. KYC refreshes or score updates.
+#[substreams::handlers::map]
+fn map_attestation_churns(
+    params: String,
+    events: contract::Events,
+    latest: StoreGetProto<contract::RecipientEvent>,
+) -> Result<contract::AttestationChurns, substreams::errors::Error> {
+    let window_seconds = Params::parse(&params).get_or("window_seconds", 86_400u64)?;
+    let attestation_churns = attestation_churns(&events, window_seconds, |ordinal, key| latest.get_at(ordinal, key));
+
+    Ok(contract::AttestationChurns { attestation_churns })
+}
+
+/// Churns of the attestations and revocations of the block, in ordinal order, `latest` reading the latest events of
+/// `store_recipient_latest_events` at an ordinal.
+fn attestation_churns(
+    events: &contract::Events,
+    window_seconds: u64,
+    latest: impl Fn(u64, String) -> Option<contract::RecipientEvent>,
+) -> Vec<contract::AttestationChurn> {
+    let within_window = |earlier: &contract::RecipientEvent, seconds: u64| seconds.saturating_sub(earlier.block_timestamp_seconds) <= window_seconds;
+
+    // store reads are made at the ordinal of the event, so that later events of the block are not seen
+    let reattested = events.eas_attesteds.iter().filter_map(|attested| {
+        let key = format!("revoked:{}", recipient_schema_key(&attested.recipient, &attested.schema_id));
+        let revocation = latest(attested.evt_index as u64, key)?;
+        within_window(&revocation, attested.evt_block_timestamp_seconds).then(|| contract::AttestationChurn {
+            evt_tx_hash: attested.evt_tx_hash.clone(),
+            evt_index: attested.evt_index,
+            evt_block_time: attested.evt_block_time,
+            evt_block_timestamp_seconds: attested.evt_block_timestamp_seconds,
+            evt_block_number: attested.evt_block_number,
+            schema_id: attested.schema_id.clone(),
+            recipient: attested.recipient.clone(),
+            revoked_uid: revocation.uid,
+            new_uid: attested.uid.clone(),
+            gap_seconds: attested.evt_block_timestamp_seconds.saturating_sub(revocation.block_timestamp_seconds),
+            attested_first: false,
+        })
+    });
+    let superseded = events.eas_revokeds.iter().filter_map(|revoked| {
+        let key = format!("attested:{}", recipient_schema_key(&revoked.recipient, &revoked.schema));
+        let attestation = latest(revoked.evt_index as u64, key)?;
+        // revoking the latest attestation itself is no churn
+        (attestation.uid != revoked.uid && within_window(&attestation, revoked.evt_block_timestamp_seconds)).then(|| contract::AttestationChurn {
+            evt_tx_hash: revoked.evt_tx_hash.clone(),
+            evt_index: revoked.evt_index,
+            evt_block_time: revoked.evt_block_time,
+            evt_block_timestamp_seconds: revoked.evt_block_timestamp_seconds,
+            evt_block_number: revoked.evt_block_number,
+            schema_id: revoked.schema.clone(),
+            recipient: revoked.recipient.clone(),
+            revoked_uid: revoked.uid.clone(),
+            new_uid: attestation.uid,
+            gap_seconds: revoked.evt_block_timestamp_seconds.saturating_sub(attestation.block_timestamp_seconds),
+            attested_first: true,
+        })
+    });
+
+    let mut attestation_churns: Vec<_> = reattested.chain(superseded).collect();
+    attestation_churns.sort_by_key(|churn| churn.evt_index);
+    attestation_churns
+}
+
+/// `(ordinal, key, credential)` writes of the attestations and revocations of the block, in ordinal order, a revocation
+/// of the current attestation, per `latest` reading `store_recipient_latest_events` at an ordinal, removing the key.
+fn current_credential_writes(
+    events: contract::Events,
+    latest: impl Fn(u64, String) -> Option<contract::RecipientEvent>,
+) -> Vec<(u64, String, Option<contract::CurrentCredential>)> {
+    let attested = events.eas_attesteds.into_iter().map(|attested| {
+        let credential = contract::CurrentCredential {
+            uid: attested.uid,
+            schema_id: attested.schema_id,
+            recipient: attested.recipient,
+            attester: attested.attester,
+            decoded_data: attested.decoded_data,
+            block_number: attested.evt_block_number,
+            block_timestamp_seconds: attested.evt_block_timestamp_seconds,
+        };
+        (
+            attested.evt_index as u64,
+            recipient_schema_key(&credential.recipient, &credential.schema_id),
+            Some(credential),
+        )
+    });
+    // only the revocation of the current attestation, per the latest attestation store at the revocation ordinal
+    let revoked = events.eas_revokeds.into_iter().filter_map(|revoked| {
+        let current = latest(
+            revoked.evt_index as u64,
+            format!("attested:{}", recipient_schema_key(&revoked.recipient, &revoked.schema)),
+        )?;
+        (current.uid == revoked.uid).then(|| (revoked.evt_index as u64, recipient_schema_key(&revoked.recipient, &revoked.schema), None))
+    });
+
+    let mut writes: Vec<_> = attested.chain(revoked).collect();
+    writes.sort_by_key(|(ordinal, _, _)| *ordinal);
+    writes
+}
+
+/// Keeps the most recent valid attestation of each schema to each recipient, with its decoded data, keyed by
+/// `<recipient>:<schema_id>`. Revoking that attestation removes the key, without falling back to older ones.
+#[substreams::handlers::store]
+fn store_current_credentials(events: contract::Events, latest: StoreGetProto<contract::RecipientEvent>, store: StoreSetProto<contract::CurrentCredential>) {
+    for (ordinal, key, credential) in current_credential_writes(events, |ordinal, key| latest.get_at(ordinal, key)) {
+        match credential {
+            Some(credential) => store.set(ordinal, key, &credential),
+            None => store.delete_prefix(ordinal as i64, &key),
+        }
+    }
+}
+
+/// Emits the current credential changes of the block: set on new attestations, removed on the revocation of the
+/// current one, for key-value sinks.
+#[substreams::handlers::map]
+fn map_current_credentials(deltas: Deltas<DeltaProto<contract::CurrentCredential>>) -> Result<contract::CurrentCredentialChanges, substreams::errors::Error> {
+    Ok(contract::CurrentCredentialChanges {
+        current_credential_changes: deltas
+            .deltas
+            .into_iter()
+            .map(|delta| {
+                let removed = delta.operation == Operation::Delete;
+                contract::CurrentCredentialChange {
+                    key: delta.key,
+                    removed,
+                    credential: Some(match removed {
+                        true => delta.old_value,
+                        false => delta.new_value,
+                    }),
+                }
+            })
+            .collect(),
+    })
+}
+
+/// Lists the schemas attested to each recipient, keyed by the hex-encoded recipient, each schema once (on the creation
+/// of its `store_recipient_schemas` key). Stores cannot list keys by prefix, so the portfolio of a recipient is read
+/// from the current credential of each of its schemas; the list grows with the distinct schemas of the recipient only.
+#[substreams::handlers::store]
+fn store_recipient_credential_schemas(deltas: Deltas<DeltaInt64>, store: StoreAppend<String>) {
+    for delta in deltas.deltas.iter().filter(|delta| delta.operation == Operation::Create) {
+        let (recipient, schema_id) = (substreams::key::first_segment(&delta.key), substreams::key::last_segment(&delta.key));
+        store.append(delta.ordinal, recipient, schema_id.to_string());
+    }
+}
+
+/// Current credentials of the recipient by schema, in schema order, read with `current` from the hex-encoded schema
+/// UIDs attested to it: those without current credential (revoked) are left out.
+fn portfolio_credentials(schema_ids: Vec<String>, current: impl Fn(&str) -> Option<contract::CurrentCredential>) -> Vec<contract::PortfolioCredential> {
+    let schema_ids: BTreeSet<String> = schema_ids.into_iter().collect();
+    schema_ids
+        .iter()
+        .filter_map(|schema_id| current(schema_id))
+        .map(|credential| contract::PortfolioCredential {
+            schema_id: credential.schema_id,
+            uid: credential.uid,
+        })
+        .collect()
+}
+
+/// Emits the portfolio of every recipient whose current credentials changed in the block: the UID of its current
+/// attestation of each schema, so that identity wallets can follow the credentials of their users.
+#[substreams::handlers::map]
+fn map_credential_portfolios(
+    clock: Clock,
+    changes: contract::CurrentCredentialChanges,
+    credential_schemas: StoreGetArray<String>,
+    current_credentials: StoreGetProto<contract::CurrentCredential>,
+) -> Result<contract::CredentialPortfolios, substreams::errors::Error> {
+    let recipients: BTreeSet<Vec<u8>> = changes
+        .current_credential_changes
+        .into_iter()
+        .filter_map(|change| Some(change.credential?.recipient))
+        .collect();
+
+    let credential_portfolios = recipients
+        .into_iter()
+        .map(|recipient| {
+            let schema_ids = credential_schemas.get_last(Hex(&recipient).to_string()).unwrap_or_default();
+            let current = |schema_id: &str| {
+                let schema_id = hex::decode(schema_id).ok()?;
+                current_credentials.get_last(recipient_schema_key(&recipient, &schema_id))
+            };
+            contract::CredentialPortfolio {
+                credentials: portfolio_credentials(schema_ids, current),
+                recipient,
+                block_number: clock.number,
+            }
+        })
+        .collect();
+
+    Ok(contract::CredentialPortfolios { credential_portfolios })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn attested(evt_index: u32, uid: u8, schema_id: u8, timestamp: u64) -> contract::EasAttested {
+        contract::EasAttested {
+            evt_index,
+            evt_block_number: 9,
+            evt_block_timestamp_seconds: timestamp,
+            uid: vec![uid; 32],
+            schema_id: vec![schema_id; 32],
+            recipient: vec![0x44; 20],
+            ..Default::default()
+        }
+    }
+
+    fn revoked(evt_index: u32, uid: u8, schema_id: u8, timestamp: u64) -> contract::EasRevoked {
+        contract::EasRevoked {
+            evt_index,
+            evt_block_number: 9,
+            evt_block_timestamp_seconds: timestamp,
+            uid: vec![uid; 32],
+            schema: vec![schema_id; 32],
+            recipient: vec![0x44; 20],
+            ..Default::default()
+        }
+    }
+
+    fn key(schema_id: u8) -> String {
+        format!("{}:{}", "44".repeat(20), Hex(&[schema_id; 32]))
+    }
+
+    fn count_delta(operation: Operation, ordinal: u64, key: String, new_value: i64) -> DeltaInt64 {
+        DeltaInt64 {
+            operation,
+            ordinal,
+            key,
+            old_value: 0,
+            new_value,
+        }
+    }
+
+    #[test]
+    fn recipient_schemas_are_keyed_by_recipient_then_schema() {
+        let events = contract::Events {
+            eas_attesteds: vec![attested(1, 0x01, 0x55, 100), attested(3, 0x02, 0x66, 100)],
+            ..Default::default()
+        };
+        assert_eq!(recipient_schema_writes(&events), [(1, key(0x55), 9), (3, key(0x66), 9)]);
+
+        let deltas = Deltas {
+            deltas: vec![
+                count_delta(Operation::Create, 1, key(0x55), 9),
+                count_delta(Operation::Update, 2, key(0x55), 9),
+                count_delta(Operation::Create, 3, key(0x66), 9),
+            ],
+        };
+        let recipient = "44".repeat(20);
+        assert_eq!(recipient_schema_count_increments(&deltas), [(1, recipient.clone()), (3, recipient)]);
+    }
+
+    #[test]
+    fn highly_attested_recipients_are_emitted_on_the_attestation_of_a_new_schema() {
+        let events = contract::Events {
+            eas_attesteds: vec![attested(1, 0x01, 0x55, 100), attested(3, 0x02, 0x66, 100)],
+            ..Default::default()
+        };
+        let deltas = Deltas {
+            deltas: vec![
+                count_delta(Operation::Update, 1, "44".repeat(20), 2),
+                count_delta(Operation::Update, 3, "44".repeat(20), 3),
+            ],
+        };
+
+        let recipients: Vec<_> = map_highly_attested_recipients("min_schemas=2".to_string(), events, deltas)
+            .unwrap()
+            .highly_attested_recipients
+            .into_iter()
+            .map(|recipient| (recipient.evt_index, recipient.schema_id[0], recipient.distinct_schema_count))
+            .collect();
+        assert_eq!(recipients, [(3, 0x66, 3)]);
+    }
+
+    #[test]
+    fn churns_pair_events_within_the_window_as_of_their_ordinal() {
+        let events = contract::Events {
+            eas_attesteds: vec![attested(2, 0x02, 0x55, 200_000), attested(4, 0x04, 0x66, 200_000)],
+            eas_revokeds: vec![revoked(3, 0x03, 0x77, 200_000)],
+            ..Default::default()
+        };
+        let writes: Vec<_> = recipient_latest_event_writes(&events)
+            .into_iter()
+            .map(|(ordinal, key, _)| (ordinal, key))
+            .collect();
+        assert_eq!(
+            writes,
+            [
+                (2, format!("attested:{}", key(0x55))),
+                (3, format!("revoked:{}", key(0x77))),
+                (4, format!("attested:{}", key(0x66))),
+            ]
+        );
+
+        // schema 0x55 was revoked 100 seconds before, schema 0x66 two days before; the revoked attestation of schema
+        // 0x77 is not its latest one
+        let event = |uid: u8, block_timestamp_seconds: u64| contract::RecipientEvent {
+            uid: vec![uid; 32],
+            block_timestamp_seconds,
+            ..Default::default()
+        };
+        let store = BTreeMap::from([
+            (format!("revoked:{}", key(0x55)), event(0x01, 199_900)),
+            (format!("revoked:{}", key(0x66)), event(0x01, 200_000 - 2 * 86_400)),
+            (format!("attested:{}", key(0x77)), event(0x05, 199_950)),
+        ]);
+        let latest = |_: u64, key: String| store.get(&key).cloned();
+        let churns: Vec<_> = attestation_churns(&events, 86_400, latest)
+            .into_iter()
+            .map(|churn| (churn.evt_index, churn.revoked_uid[0], churn.new_uid[0], churn.gap_seconds, churn.attested_first))
+            .collect();
+        assert_eq!(churns, [(2, 0x01, 0x02, 100, false), (3, 0x03, 0x05, 50, true)]);
+    }
+
+    #[test]
+    fn current_credentials_are_removed_by_the_revocation_of_the_current_attestation_only() {
+        let events = contract::Events {
+            eas_attesteds: vec![attested(2, 0x02, 0x55, 1_000)],
+            eas_revokeds: vec![revoked(1, 0x01, 0x55, 1_000), revoked(3, 0x03, 0x66, 1_000)],
+            ..Default::default()
+        };
+        // the latest attestation of schema 0x55 is 0x01 until 0x02, schema 0x66 has 0x04
+        let event = |uid: u8| contract::RecipientEvent {
+            uid: vec![uid; 32],
+            ..Default::default()
+        };
+        let store = BTreeMap::from([
+            (format!("attested:{}", key(0x55)), event(0x01)),
+            (format!("attested:{}", key(0x66)), event(0x04)),
+        ]);
+        let latest = |_: u64, key: String| store.get(&key).cloned();
+
+        let writes: Vec<_> = current_credential_writes(events, latest)
+            .into_iter()
+            .map(|(ordinal, key, credential)| (ordinal, key, credential.map(|credential| credential.uid[0])))
+            .collect();
+        assert_eq!(writes, [(1, key(0x55), None), (2, key(0x55), Some(0x02))]);
+
+        let removed = |operation| DeltaProto {
+            operation,
+            ordinal: 1,
+            key: key(0x55),
+            old_value: contract::CurrentCredential {
+                uid: vec![0x01; 32],
+                ..Default::default()
+            },
+            new_value: Default::default(),
+        };
+        let changes = map_current_credentials(Deltas {
+            deltas: vec![removed(Operation::Delete)],
+        })
+        .unwrap()
+        .current_credential_changes;
+        assert!(changes[0].removed);
+        assert_eq!(
+            (changes[0].key.as_str(), changes[0].credential.as_ref().unwrap().uid[0]),
+            (key(0x55).as_str(), 0x01)
+        );
+    }
+
+    #[test]
+    fn portfolios_hold_the_current_credential_of_each_schema() {
+        let schema_ids = ["bb", "aa", "cc", "aa"].map(String::from).to_vec();
+        // the credential of schema `cc` was revoked
+        let current = |schema_id: &str| {
+            (schema_id != "cc").then(|| contract::CurrentCredential {
+                schema_id: hex::decode(schema_id).unwrap(),
+                uid: hex::decode(schema_id.repeat(2)).unwrap(),
+                ..Default::default()
+            })
+        };
+
+        let credentials: Vec<_> = portfolio_credentials(schema_ids, current)
+            .into_iter()
+            .map(|credential| (credential.schema_id, credential.uid))
+            .collect();
+        assert_eq!(credentials, [(vec![0xaa], vec![0xaa, 0xaa]), (vec![0xbb], vec![0xbb, 0xbb])]);
+    }
+}
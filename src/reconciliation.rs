@@ -0,0 +1,128 @@
+use crate::networks;
+use crate::params::Params;
+use crate::pb::contract::v1 as contract;
+use crate::rpc::{EasRpc, SubstreamsRpc};
+use contract::AttestationState;
+use substreams::pb::substreams::store_delta::Operation;
+use substreams::pb::substreams::Clock;
+use substreams::store::{DeltaProto, Deltas, StoreAppend, StoreGet, StoreGetArray, StoreGetProto, StoreSetIfNotExists, StoreSetIfNotExistsProto};
+use substreams::Hex;
+
+/// Number of UID buckets, one per value of the first UID byte, checked in turn by the reconciliation.
+const UID_BUCKETS: u64 = 256;
+
+/// Store key of the bucket of an attestation UID: the hex-encoded first byte of the UID.
+fn uid_bucket_key(uid: &[u8]) -> String {
+    Hex(uid.get(..1).unwrap_or_default()).to_string()
+}
+
+/// Buckets every attestation UID by its first byte, so that the reconciliation can go through them a bucket at a time.
+#[substreams::handlers::store]
+fn store_uid_buckets(events: contract::Events, store: StoreAppend<String>) {
+    for attested in events.eas_attesteds {
+        store.append(attested.evt_index as u64, uid_bucket_key(&attested.uid), Hex(&attested.uid).to_string());
+    }
+}
+
+/// Every `interval` blocks (param, default `0`, disabled), re-reads through RPC the attestations of the next UID
+/// bucket that the status store holds as active, and emits a correction for every one revoked on chain, e.g. by a
+/// revocation that emitted no log. A full cycle over the 256 buckets takes `256 * interval` blocks. The EAS contract
+/// is selected with the `chain_family` and `chain` params, like for `map_events`. The status store cannot take the
+/// corrections back (it is an input of this module), so a revoked UID is re-read and re-emitted on every cycle:
+/// `map_revocation_corrections` keeps the first correction of every UID only.
+#[substreams::handlers::map]
+fn map_reconciliations(
+    params: String,
+    clock: Clock,
+    uid_buckets: StoreGetArray<String>,
+    status: StoreGetProto<contract::AttestationStatus>,
+) -> Result<contract::RevocationCorrections, substreams::errors::Error> {
+    let params = Params::parse(&params);
+    let interval = params.get_or("interval", 0u64)?;
+    if interval == 0 || !clock.number.is_multiple_of(interval) {
+        return Ok(contract::RevocationCorrections::default());
+    }
+    let network = networks::from_params(&params)?;
+
+    let bucket = (clock.number / interval) % UID_BUCKETS;
+    let active: Vec<contract::AttestationStatus> = uid_buckets
+        .get_last(format!("{:02x}", bucket))
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|uid| status.get_last(uid))
+        .filter(|status| status.state == AttestationState::Active as i32)
+        .collect();
+    let uids: Vec<[u8; 32]> = active
+        .iter()
+        .map(|status| status.uid.as_slice().try_into().expect("attestation UIDs should be 32 bytes"))
+        .collect();
+
+    let revocation_corrections = active
+        .into_iter()
+        .zip(SubstreamsRpc { contracts: network.contracts }.get_attestations(&uids))
+        .filter_map(|(status, attestation)| {
+            let attestation = attestation.ok()?;
+            (attestation.revocation_time != 0).then_some(contract::RevocationCorrection {
+                block_number: clock.number,
+                uid: status.uid,
+                schema_id: status.schema_id,
+                attester: status.attester,
+                recipient: status.recipient,
+                revocation_time: attestation.revocation_time,
+            })
+        })
+        .collect();
+
+    Ok(contract::RevocationCorrections { revocation_corrections })
+}
+
+/// Keeps the first correction of every attestation, keyed by hex-encoded UID.
+#[substreams::handlers::store]
+fn store_revocation_corrections(corrections: contract::RevocationCorrections, store: StoreSetIfNotExistsProto<contract::RevocationCorrection>) {
+    for (ordinal, correction) in corrections.revocation_corrections.into_iter().enumerate() {
+        store.set_if_not_exists(ordinal as u64, Hex(&correction.uid).to_string(), &correction);
+    }
+}
+
+/// Emits the corrections of `map_reconciliations` once per attestation: those the store keeps for the first time in
+/// the block, the ones re-emitted by later cycles being left out.
+#[substreams::handlers::map]
+fn map_revocation_corrections(
+    deltas: Deltas<DeltaProto<contract::RevocationCorrection>>,
+) -> Result<contract::RevocationCorrections, substreams::errors::Error> {
+    let revocation_corrections = deltas
+        .deltas
+        .into_iter()
+        .filter(|delta| delta.operation == Operation::Create)
+        .map(|delta| delta.new_value)
+        .collect();
+
+    Ok(contract::RevocationCorrections { revocation_corrections })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn corrections_are_emitted_once_per_uid() {
+        let delta = |operation, uid: u8| DeltaProto {
+            operation,
+            ordinal: 0,
+            key: Hex(&[uid; 32]).to_string(),
+            old_value: contract::RevocationCorrection::default(),
+            new_value: contract::RevocationCorrection {
+                uid: vec![uid; 32],
+                revocation_time: 1_700_000_000,
+                ..Default::default()
+            },
+        };
+
+        let corrections = map_revocation_corrections(Deltas {
+            deltas: vec![delta(Operation::Create, 1), delta(Operation::Update, 2)],
+        })
+        .unwrap();
+        let uids: Vec<_> = corrections.revocation_corrections.iter().map(|correction| correction.uid[0]).collect();
+        assert_eq!(uids, [1]);
+    }
+}
@@ -0,0 +1,190 @@
+use crate::pb::contract::v1 as contract;
+use std::collections::BTreeMap;
+use substreams::pb::substreams::Clock;
+use substreams::store::{
+    DeltaInt64, Deltas, StoreAdd, StoreAddInt64, StoreGet, StoreGetInt64, StoreGetProto, StoreSetIfNotExists, StoreSetIfNotExistsInt64,
+    StoreSetIfNotExistsProto,
+};
+use substreams::Hex;
+
+/// Keeps the block number, timestamp, schema, attester and recipient of each attestation, keyed by the hex-encoded
+/// attestation UID.
+#[substreams::handlers::store]
+fn store_attestation_blocks(events: contract::Events, store: StoreSetIfNotExistsProto<contract::AttestationBlock>) {
+    for attested in events.eas_attesteds {
+        store.set_if_not_exists(
+            attested.evt_index as u64,
+            Hex(&attested.uid).to_string(),
+            &contract::AttestationBlock {
+                block_number: attested.evt_block_number,
+                block_timestamp_seconds: attested.evt_block_timestamp_seconds,
+                schema_id: attested.schema_id,
+                attester: attested.attester,
+                recipient: attested.recipient,
+            },
+        );
+    }
+}
+
+/// Keeps the block number of the revocation of each attestation, keyed by the hex-encoded attestation UID.
+#[substreams::handlers::store]
+fn store_revoked_attestations(events: contract::Events, store: StoreSetIfNotExistsInt64) {
+    for revoked in events.eas_revokeds {
+        store.set_if_not_exists(revoked.evt_index as u64, Hex(&revoked.uid).to_string(), &(revoked.evt_block_number as i64));
+    }
+}
+
+/// Returns the attestations of the block superseding a prior attestation, i.e. referencing (`ref_uid`) an attestation
+/// of the same schema to the same recipient that is not revoked yet, with the referenced attestation, in log order.
+/// `attestation_block` reads an attestation of `store_attestation_blocks` and `revocation_block` the revocation of an
+/// attestation in `store_revoked_attestations`, both at an ordinal.
+pub fn supersessions(
+    events: &contract::Events,
+    attestation_block: impl Fn(u64, String) -> Option<contract::AttestationBlock>,
+    revocation_block: impl Fn(u64, String) -> Option<i64>,
+) -> Vec<(&contract::EasAttested, contract::AttestationBlock)> {
+    events
+        .eas_attesteds
+        .iter()
+        .filter(|attested| !attested.ref_uid.is_empty())
+        .filter_map(|attested| {
+            let ordinal = attested.evt_index as u64;
+            // a revoked attestation stays revoked, superseding it changes nothing
+            if revocation_block(ordinal, Hex(&attested.ref_uid).to_string()).is_some() {
+                return None;
+            }
+            let referenced = attestation_block(ordinal, Hex(&attested.ref_uid).to_string())?;
+            (referenced.schema_id == attested.schema_id && referenced.recipient == attested.recipient).then_some((attested, referenced))
+        })
+        .collect()
+}
+
+/// Emits the block and time elapsed between each attestation of the block and the attestation it references.
+#[substreams::handlers::map]
+fn map_ref_latencies(events: contract::Events, store: StoreGetProto<contract::AttestationBlock>) -> Result<contract::RefLatencies, substreams::errors::Error> {
+    let ref_latencies = events
+        .eas_attesteds
+        .into_iter()
+        .filter(|attested| !attested.ref_uid.is_empty())
+        .filter_map(|attested| {
+            let referenced = store.get_last(Hex(&attested.ref_uid).to_string())?;
+            Some(contract::RefLatency {
+                ref_block_number: referenced.block_number,
+                ref_latency_blocks: attested.evt_block_number.saturating_sub(referenced.block_number),
+                ref_latency_seconds: attested.evt_block_timestamp_seconds.saturating_sub(referenced.block_timestamp_seconds),
+                evt_tx_hash: attested.evt_tx_hash,
+                evt_index: attested.evt_index,
+                evt_block_time: attested.evt_block_time,
+                evt_block_timestamp_seconds: attested.evt_block_timestamp_seconds,
+                evt_block_number: attested.evt_block_number,
+                uid: attested.uid,
+                ref_uid: attested.ref_uid,
+            })
+        })
+        .collect();
+
+    Ok(contract::RefLatencies { ref_latencies })
+}
+/// Emits a `Superseded` lifecycle event for every attestation superseded by an attestation of the block, which
+/// references it and shares its schema and recipient, the usual way credentials get updated. Attestations revoked
+/// before are left out.
+#[substreams::handlers::map]
+fn map_supersessions(
+    events: contract::Events,
+    attestation_blocks: StoreGetProto<contract::AttestationBlock>,
+    revoked_attestations: StoreGetInt64,
+) -> Result<contract::Supersessions, substreams::errors::Error> {
+    let supersessions = supersessions(
+        &events,
+        |ordinal, key| attestation_blocks.get_at(ordinal, key),
+        |ordinal, key| revoked_attestations.get_at(ordinal, key),
+    )
+    .into_iter()
+    .map(|(attested, referenced)| contract::Supersession {
+        evt_tx_hash: attested.evt_tx_hash.clone(),
+        evt_index: attested.evt_index,
+        evt_block_time: attested.evt_block_time,
+        evt_block_timestamp_seconds: attested.evt_block_timestamp_seconds,
+        evt_block_number: attested.evt_block_number,
+        uid: attested.ref_uid.clone(),
+        superseded_by: attested.uid.clone(),
+        schema_id: referenced.schema_id,
+        recipient: referenced.recipient,
+    })
+    .collect();
+
+    Ok(contract::Supersessions { supersessions })
+}
+
+/// Store key of a reference edge between schemas: `<child_schema_id>:<parent_schema_id>`, the child being the schema of
+/// the referencing attestation.
+fn schema_reference_key(child_schema_id: &[u8], parent_schema_id: &[u8]) -> String {
+    format!("{}:{}", Hex(child_schema_id), Hex(parent_schema_id))
+}
+
+/// Counts the references (`ref_uid`) between attestations by pair of schemas, keyed by
+/// `<child_schema_id>:<parent_schema_id>`. References to attestations unknown to `store_attestation_blocks` are left out.
+#[substreams::handlers::store]
+fn store_schema_reference_counts(events: contract::Events, attestation_blocks: StoreGetProto<contract::AttestationBlock>, store: StoreAddInt64) {
+    for attested in events.eas_attesteds.iter().filter(|attested| !attested.ref_uid.is_empty()) {
+        let ordinal = attested.evt_index as u64;
+        if let Some(referenced) = attestation_blocks.get_at(ordinal, Hex(&attested.ref_uid).to_string()) {
+            store.add(ordinal, schema_reference_key(&attested.schema_id, &referenced.schema_id), 1);
+        }
+    }
+}
+
+/// Emits the reference count of every pair of schemas referenced in the block, i.e. the edges of the schema reference
+/// graph updated by the block.
+#[substreams::handlers::map]
+fn map_schema_references(clock: Clock, deltas: Deltas<DeltaInt64>) -> Result<contract::SchemaReferences, substreams::errors::Error> {
+    // the last count of each edge
+    let counts: BTreeMap<String, i64> = deltas.deltas.into_iter().map(|delta| (delta.key, delta.new_value)).collect();
+
+    let schema_references = counts
+        .into_iter()
+        .filter_map(|(key, reference_count)| {
+            let (child_schema_id, parent_schema_id) = key.split_once(':')?;
+            Some(contract::SchemaReference {
+                child_schema_id: hex::decode(child_schema_id).ok()?,
+                parent_schema_id: hex::decode(parent_schema_id).ok()?,
+                reference_count: reference_count.max(0) as u64,
+                block_number: clock.number,
+            })
+        })
+        .collect();
+
+    Ok(contract::SchemaReferences { schema_references })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use substreams::pb::substreams::store_delta::Operation;
+
+    #[test]
+    fn schema_references_carry_the_last_count_of_each_edge() {
+        let delta = |ordinal, child: u8, parent: u8, new_value| DeltaInt64 {
+            operation: Operation::Update,
+            ordinal,
+            key: schema_reference_key(&[child; 32], &[parent; 32]),
+            old_value: new_value - 1,
+            new_value,
+        };
+        let clock = Clock {
+            number: 42,
+            ..Default::default()
+        };
+        let deltas = Deltas {
+            deltas: vec![delta(1, 0xaa, 0xbb, 3), delta(2, 0xcc, 0xbb, 1), delta(3, 0xaa, 0xbb, 4)],
+        };
+
+        let edges: Vec<_> = map_schema_references(clock, deltas)
+            .unwrap()
+            .schema_references
+            .iter()
+            .map(|edge| (edge.child_schema_id[0], edge.parent_schema_id[0], edge.reference_count, edge.block_number))
+            .collect();
+        assert_eq!(edges, [(0xaa, 0xbb, 4, 42), (0xcc, 0xbb, 1, 42)]);
+    }
+}
@@ -0,0 +1,114 @@
+use crate::pb::contract::v1 as contract;
+use crate::schema_resolver::SchemaLookup;
+use std::collections::BTreeMap;
+use substreams::store::{StoreAdd, StoreAddInt64, StoreGet, StoreGetInt64, StoreGetProto};
+use substreams::Hex;
+
+/// Store key of a resolver counter: `schemas:<resolver>` or `attestations:<resolver>`.
+fn usage_key(counter: &str, resolver: &[u8]) -> String {
+    format!("{}:{}", counter, Hex(resolver))
+}
+
+/// Returns the `(ordinal, counter, resolver)` increments of the block: one per schema registered with a resolver and
+/// one per attestation of such a schema. Schemas without resolver (zero address) are left out.
+fn resolver_increments(schemas: &contract::Schemas, events: &contract::Events, schema_store: &impl SchemaLookup) -> Vec<(u64, &'static str, Vec<u8>)> {
+    let registered = schemas
+        .schemas
+        .iter()
+        .map(|schema| (schema.evt_index as u64, "schemas", schema.current_resolver.clone()));
+    let attested = events.eas_attesteds.iter().filter_map(|attested| {
+        let schema = schema_store.lookup(attested.schema_id.as_slice().try_into().ok()?)?;
+        Some((attested.evt_index as u64, "attestations", schema.resolver))
+    });
+
+    let mut increments: Vec<_> = registered
+        .chain(attested)
+        .filter(|(_, _, resolver)| resolver.iter().any(|byte| *byte != 0))
+        .collect();
+    increments.sort_by_key(|(ordinal, _, _)| *ordinal);
+    increments
+}
+
+/// Counts, per resolver contract, the schemas registered with it and the attestations of those schemas.
+#[substreams::handlers::store]
+fn store_resolver_usage(schemas: contract::Schemas, events: contract::Events, schema_store: StoreGetProto<contract::Schema>, store: StoreAddInt64) {
+    for (ordinal, counter, resolver) in resolver_increments(&schemas, &events, &schema_store) {
+        store.add(ordinal, usage_key(counter, &resolver), 1);
+    }
+}
+
+/// Emits the cumulative schema and attestation counts of every resolver used in the block.
+#[substreams::handlers::map]
+fn map_resolver_usage(
+    schemas: contract::Schemas,
+    events: contract::Events,
+    schema_store: StoreGetProto<contract::Schema>,
+    usage: StoreGetInt64,
+) -> Result<contract::ResolverUsages, substreams::errors::Error> {
+    let resolvers: BTreeMap<String, Vec<u8>> = resolver_increments(&schemas, &events, &schema_store)
+        .into_iter()
+        .map(|(_, _, resolver)| (Hex(&resolver).to_string(), resolver))
+        .collect();
+
+    let resolver_usages = resolvers
+        .into_values()
+        .map(|resolver| contract::ResolverUsage {
+            schemas: usage.get_last(usage_key("schemas", &resolver)).unwrap_or(0),
+            attestations: usage.get_last(usage_key("attestations", &resolver)).unwrap_or(0),
+            resolver,
+        })
+        .collect();
+
+    Ok(contract::ResolverUsages { resolver_usages })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::FakeRpc;
+    use crate::Schema;
+
+    #[test]
+    fn resolvers_count_their_schemas_and_the_attestations_of_those() {
+        let schemas = contract::Schemas {
+            schemas: vec![
+                contract::Schema {
+                    evt_index: 4,
+                    current_resolver: vec![0xaa; 20],
+                    ..Default::default()
+                },
+                contract::Schema {
+                    evt_index: 1,
+                    current_resolver: vec![0; 20],
+                    ..Default::default()
+                },
+            ],
+        };
+        let attested = |evt_index: u32, schema_id: u8| contract::EasAttested {
+            evt_index,
+            schema_id: vec![schema_id; 32],
+            ..Default::default()
+        };
+        let events = contract::Events {
+            eas_attesteds: vec![attested(2, 0x55), attested(3, 0x66), attested(5, 0x77)],
+            ..Default::default()
+        };
+        let schema = |uid: u8, resolver: u8| Schema {
+            uid_id: [uid; 32],
+            resolver: vec![resolver; 20],
+            revocable: true,
+            schema: String::new(),
+        };
+        // schema 0x66 has no resolver, schema 0x77 is unknown
+        let schema_store = FakeRpc::default().with_schema(schema(0x55, 0xbb)).with_schema(schema(0x66, 0));
+
+        let increments: Vec<_> = resolver_increments(&schemas, &events, &schema_store)
+            .into_iter()
+            .map(|(ordinal, counter, resolver)| (ordinal, usage_key(counter, &resolver)))
+            .collect();
+        assert_eq!(
+            increments,
+            [(2, format!("attestations:{}", "bb".repeat(20))), (4, format!("schemas:{}", "aa".repeat(20))),]
+        );
+    }
+}
@@ -0,0 +1,146 @@
+use crate::pb::contract::v1 as contract;
+use substreams::store::{StoreGet, StoreGetProto, StoreSetIfNotExists, StoreSetIfNotExistsProto};
+use substreams::Hex;
+
+/// Store key of an offchain revocation: `<revoker>:<data>`, where data is the revoked offchain attestation UID.
+pub fn offchain_revocation_key(revoker: &[u8], data: &[u8]) -> String {
+    format!("{}:{}", Hex(revoker), Hex(data))
+}
+
+/// `(ordinal, key, revocation)` writes of the offchain revocations of the block, in log order.
+fn offchain_revocation_writes(events: contract::Events) -> Vec<(u64, String, contract::EasRevokedOffchain)> {
+    events
+        .eas_revoked_offchains
+        .into_iter()
+        .map(|revocation| {
+            (
+                revocation.evt_index as u64,
+                offchain_revocation_key(&revocation.revoker, &revocation.data),
+                revocation,
+            )
+        })
+        .collect()
+}
+
+/// Keeps the first `RevokedOffchain` event seen for each revoker and offchain attestation UID.
+#[substreams::handlers::store]
+fn store_offchain_revocations(events: contract::Events, store: StoreSetIfNotExistsProto<contract::EasRevokedOffchain>) {
+    for (ordinal, key, revocation) in offchain_revocation_writes(events) {
+        store.set_if_not_exists(ordinal, key, &revocation);
+    }
+}
+
+/// Links an offchain revocation to `first`, the first revocation recorded for the same revoker and UID, itself when
+/// unrecorded.
+fn offchain_revocation_link(revocation: contract::EasRevokedOffchain, first: Option<contract::EasRevokedOffchain>) -> contract::OffchainRevocationLink {
+    let first = first.unwrap_or_else(|| revocation.clone());
+    let is_repeated = first.evt_block_number != revocation.evt_block_number || first.evt_index != revocation.evt_index;
+
+    contract::OffchainRevocationLink {
+        is_conflicting: is_repeated && first.timestamp != revocation.timestamp,
+        is_repeated,
+        first_evt_tx_hash: first.evt_tx_hash,
+        first_evt_block_number: first.evt_block_number,
+        first_evt_block_time: first.evt_block_time,
+        first_timestamp: first.timestamp,
+        evt_tx_hash: revocation.evt_tx_hash,
+        evt_index: revocation.evt_index,
+        evt_block_time: revocation.evt_block_time,
+        evt_block_timestamp_seconds: revocation.evt_block_timestamp_seconds,
+        evt_block_number: revocation.evt_block_number,
+        revoker: revocation.revoker,
+        data: revocation.data,
+        timestamp: revocation.timestamp,
+    }
+}
+
+/// Joins each offchain revocation of the block with the first revocation recorded for the same revoker and UID,
+/// flagging repeated revocations and the ones carrying a different revocation timestamp than the first.
+#[substreams::handlers::map]
+fn map_offchain_revocation_links(
+    events: contract::Events,
+    store: StoreGetProto<contract::EasRevokedOffchain>,
+) -> Result<contract::OffchainRevocationLinks, substreams::errors::Error> {
+    let offchain_revocation_links = events
+        .eas_revoked_offchains
+        .into_iter()
+        .map(|revocation| {
+            let first = store.get_last(offchain_revocation_key(&revocation.revoker, &revocation.data));
+            offchain_revocation_link(revocation, first)
+        })
+        .collect();
+
+    Ok(contract::OffchainRevocationLinks { offchain_revocation_links })
+}
+
+/// Emits the onchain and offchain revocations of the block as a single stream, in log order. `uid` is the revoked
+/// attestation UID (the `data` of offchain revocations) and `time` the revocation time: the block timestamp onchain,
+/// the timestamp given by the revoker offchain. `schema_id` and `recipient` are only known for onchain revocations.
+#[substreams::handlers::map]
+fn map_revocations(events: contract::Events) -> Result<contract::Revocations, substreams::errors::Error> {
+    let onchain = events.eas_revokeds.into_iter().map(|revoked| contract::Revocation {
+        kind: contract::RevocationKind::Onchain as i32,
+        evt_tx_hash: revoked.evt_tx_hash,
+        evt_index: revoked.evt_index,
+        evt_block_time: revoked.evt_block_time,
+        evt_block_timestamp_seconds: revoked.evt_block_timestamp_seconds,
+        evt_block_number: revoked.evt_block_number,
+        revoker: revoked.attester,
+        uid: revoked.uid,
+        time: revoked.evt_block_timestamp_seconds,
+        schema_id: revoked.schema,
+        recipient: revoked.recipient,
+    });
+    let offchain = events.eas_revoked_offchains.into_iter().map(|revocation| contract::Revocation {
+        kind: contract::RevocationKind::Offchain as i32,
+        evt_tx_hash: revocation.evt_tx_hash,
+        evt_index: revocation.evt_index,
+        evt_block_time: revocation.evt_block_time,
+        evt_block_timestamp_seconds: revocation.evt_block_timestamp_seconds,
+        evt_block_number: revocation.evt_block_number,
+        revoker: revocation.revoker,
+        uid: revocation.data,
+        time: revocation.timestamp,
+        schema_id: vec![],
+        recipient: vec![],
+    });
+
+    let mut revocations: Vec<_> = onchain.chain(offchain).collect();
+    revocations.sort_by_key(|revocation| revocation.evt_index);
+    Ok(contract::Revocations { revocations })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn revocation(evt_block_number: u64, evt_index: u32, timestamp: u64) -> contract::EasRevokedOffchain {
+        contract::EasRevokedOffchain {
+            evt_block_number,
+            evt_index,
+            revoker: vec![0x11; 20],
+            data: vec![0x66; 32],
+            timestamp,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn offchain_revocations_link_to_the_first_one_of_their_revoker_and_uid() {
+        let events = contract::Events {
+            eas_revoked_offchains: vec![revocation(9, 3, 100), revocation(9, 5, 100)],
+            ..Default::default()
+        };
+        let writes: Vec<_> = offchain_revocation_writes(events).into_iter().map(|(ordinal, key, _)| (ordinal, key)).collect();
+        let key = format!("{}:{}", "11".repeat(20), "66".repeat(32));
+        assert_eq!(writes, [(3, key.clone()), (5, key)]);
+
+        let first = offchain_revocation_link(revocation(9, 3, 100), None);
+        assert!(!first.is_repeated && !first.is_conflicting);
+        let repeated = offchain_revocation_link(revocation(9, 5, 100), Some(revocation(9, 3, 100)));
+        assert!(repeated.is_repeated && !repeated.is_conflicting);
+        let conflicting = offchain_revocation_link(revocation(12, 0, 200), Some(revocation(9, 3, 100)));
+        assert!(conflicting.is_repeated && conflicting.is_conflicting);
+        assert_eq!((conflicting.first_evt_block_number, conflicting.first_timestamp), (9, 100));
+    }
+}
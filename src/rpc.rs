@@ -0,0 +1,266 @@
+use crate::abi::eas_contract::functions::GetAttestation;
+use crate::abi::eas_schema_registry_contract::functions::GetSchema;
+use crate::networks::Contracts;
+use crate::{Attestation, FieldType, Schema};
+use std::fmt;
+use substreams::Hex;
+use substreams_ethereum::pb::eth::rpc::{RpcCall, RpcCalls, RpcResponse};
+use substreams_ethereum::rpc::eth_call;
+
+/// Maximum number of calls per `eth_call` batch.
+const RPC_BATCH_SIZE: usize = 100;
+
+/// Onchain reads used to enrich events, behind a trait so that the enrichment can run against an in-memory fake.
+pub trait EasRpc {
+    /// Returns the attestations of the given UIDs, in order, with the reason of the failure for the ones that cannot
+    /// be read.
+    fn get_attestations(&self, uids: &[[u8; 32]]) -> Vec<Result<Attestation, RpcFailure>>;
+
+    /// Returns the attestations of the given UIDs read from the EAS-compatible contract at `eas`, e.g. a fork
+    /// tracked with the `extra_contracts` param. Sources not bound to a contract serve them like any other.
+    fn get_attestations_at(&self, eas: &[u8; 20], uids: &[[u8; 32]]) -> Vec<Result<Attestation, RpcFailure>> {
+        let _ = eas;
+        self.get_attestations(uids)
+    }
+
+    /// Returns the schema records of the given UIDs, in order, with the reason of the failure for the ones that cannot
+    /// be read.
+    fn get_schemas(&self, schema_ids: &[[u8; 32]]) -> Vec<Result<Schema, RpcFailure>>;
+
+    /// Returns the schema records of the given UIDs read from the schema registry at `registry`, e.g. the registry of
+    /// a fork tracked with the `extra_contracts` param. Sources not bound to a contract serve them like any other.
+    fn get_schemas_at(&self, registry: &[u8; 20], schema_ids: &[[u8; 32]]) -> Vec<Result<Schema, RpcFailure>> {
+        let _ = registry;
+        self.get_schemas(schema_ids)
+    }
+
+    /// Returns the fields of a schema signature parsed in a previous block, `None` when the signature has to be parsed.
+    fn parsed_fields(&self, signature: &str) -> Option<Vec<(FieldType, String)>> {
+        let _ = signature;
+        None
+    }
+}
+
+/// Onchain read whose response cannot be used, e.g. because of a wrong contract address or a proxy pointing to an
+/// incompatible implementation. Rendered with the call, UID, contract and return data length, so that
+/// misconfigurations can be diagnosed from the logs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RpcFailure {
+    /// Called function, `getAttestation` or `getSchema`.
+    pub call: &'static str,
+    /// Address of the called contract.
+    pub contract: Vec<u8>,
+    /// UID passed to the call.
+    pub uid: [u8; 32],
+    /// Length of the raw return data, in bytes.
+    pub return_data_len: usize,
+    pub reason: String,
+}
+
+impl fmt::Display for RpcFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}({}) on contract {} {} ({} bytes of return data)",
+            self.call,
+            Hex(&self.uid),
+            Hex(&self.contract),
+            self.reason,
+            self.return_data_len
+        )
+    }
+}
+
+/// Decodes the response of a `call` to `contract` for `uid`, telling reverted calls, empty responses (no contract at
+/// the address) and responses of another shape apart.
+fn decode_response<R>(
+    call: &'static str,
+    contract: &[u8],
+    uid: [u8; 32],
+    response: &RpcResponse,
+    decode: impl FnOnce(&[u8]) -> Result<R, String>,
+) -> Result<R, RpcFailure> {
+    let failure = |reason: String| RpcFailure {
+        call,
+        contract: contract.to_vec(),
+        uid,
+        return_data_len: response.raw.len(),
+        reason,
+    };
+    if response.failed {
+        return Err(failure("reverted".to_string()));
+    }
+    if response.raw.is_empty() {
+        return Err(failure("returned no data, is the contract deployed at this address?".to_string()));
+    }
+    decode(&response.raw).map_err(|error| failure(format!("returned data that cannot be decoded: {}", error)))
+}
+
+/// [`EasRpc`] implementation issuing batched `eth_call`s through substreams to the given contracts.
+#[derive(Debug, Default)]
+pub struct SubstreamsRpc {
+    pub contracts: Contracts,
+}
+
+impl EasRpc for SubstreamsRpc {
+    fn get_attestations(&self, uids: &[[u8; 32]]) -> Vec<Result<Attestation, RpcFailure>> {
+        fetch_attestations(&self.contracts.eas, uids)
+    }
+
+    fn get_attestations_at(&self, eas: &[u8; 20], uids: &[[u8; 32]]) -> Vec<Result<Attestation, RpcFailure>> {
+        fetch_attestations(eas, uids)
+    }
+
+    fn get_schemas(&self, schema_ids: &[[u8; 32]]) -> Vec<Result<Schema, RpcFailure>> {
+        fetch_schemas_at(&self.contracts.schema_registry, schema_ids)
+    }
+
+    fn get_schemas_at(&self, registry: &[u8; 20], schema_ids: &[[u8; 32]]) -> Vec<Result<Schema, RpcFailure>> {
+        fetch_schemas_at(registry, schema_ids)
+    }
+}
+
+/// Output of the `getAttestation` RPC call:
+/// `(uid, schema, time, expirationTime, revocationTime, refUID, recipient, attester, revocable, data)`.
+type GetAttestationOutput = (
+    [u8; 32],
+    [u8; 32],
+    substreams::scalar::BigInt,
+    substreams::scalar::BigInt,
+    substreams::scalar::BigInt,
+    [u8; 32],
+    Vec<u8>,
+    Vec<u8>,
+    bool,
+    Vec<u8>,
+);
+
+impl Attestation {
+    fn from_rpc_output(output: GetAttestationOutput) -> Result<Self, String> {
+        let (uid, schema, time, expiration_time, revocation_time, ref_uid, recipient, attester, revocable, data) = output;
+        let address =
+            |name: &str, address: Vec<u8>| <[u8; 20]>::try_from(address.as_slice()).map_err(|_| format!("{} is {} bytes, not an address", name, address.len()));
+        Ok(Attestation {
+            uid,
+            schema,
+            time: time.to_u64(),
+            expiration_time: expiration_time.to_u64(),
+            revocation_time: revocation_time.to_u64(),
+            ref_uid,
+            recipient: address("recipient", recipient)?,
+            attester: address("attester", attester)?,
+            revocable,
+            data,
+        })
+    }
+}
+
+/// Fetches the attestations of the given UIDs from the EAS contract at `eas`, failing the responses that cannot be
+/// decoded.
+fn fetch_attestations(eas: &[u8], uids: &[[u8; 32]]) -> Vec<Result<Attestation, RpcFailure>> {
+    fetch_batched(
+        "getAttestation",
+        eas,
+        uids,
+        |uid| GetAttestation { uid }.encode(),
+        |raw| GetAttestation::output(raw).and_then(Attestation::from_rpc_output),
+    )
+}
+
+/// Output of the `getSchema` RPC call: `(uid, resolver, revocable, schema)`.
+type GetSchemaOutput = ([u8; 32], Vec<u8>, bool, String);
+
+impl Schema {
+    fn from_rpc_output(output: GetSchemaOutput) -> Self {
+        let (uid_id, resolver, revocable, schema) = output;
+        Schema {
+            uid_id,
+            resolver,
+            revocable,
+            schema,
+        }
+    }
+}
+
+/// Fetches the schema records of the given UIDs from the schema registry deployed at `registry`, failing the
+/// responses that cannot be decoded.
+pub fn fetch_schemas_at(registry: &[u8], schema_ids: &[[u8; 32]]) -> Vec<Result<Schema, RpcFailure>> {
+    fetch_batched(
+        "getSchema",
+        registry,
+        schema_ids,
+        |uid| GetSchema { uid }.encode(),
+        |raw| GetSchema::output(raw).map(Schema::from_rpc_output),
+    )
+}
+
+/// Issues the `call` of every UID to `contract`, in `eth_call` batches of at most `RPC_BATCH_SIZE` calls, and decodes
+/// the responses in order. The batch buffer, call addresses included, is allocated once and refilled for every chunk,
+/// and the responses are decoded as each batch returns.
+fn fetch_batched<R>(
+    call: &'static str,
+    contract: &[u8],
+    uids: &[[u8; 32]],
+    encode: impl Fn([u8; 32]) -> Vec<u8>,
+    decode: impl Fn(&[u8]) -> Result<R, String>,
+) -> Vec<Result<R, RpcFailure>> {
+    let mut results = Vec::with_capacity(uids.len());
+    let mut batch = RpcCalls {
+        calls: Vec::with_capacity(uids.len().min(RPC_BATCH_SIZE)),
+    };
+    for chunk in uids.chunks(RPC_BATCH_SIZE) {
+        batch.calls.truncate(chunk.len());
+        for (i, uid) in chunk.iter().enumerate() {
+            match batch.calls.get_mut(i) {
+                Some(rpc_call) => rpc_call.data = encode(*uid),
+                None => batch.calls.push(RpcCall {
+                    to_addr: contract.to_vec(),
+                    data: encode(*uid),
+                }),
+            }
+        }
+        let responses = eth_call(&batch).responses;
+        if responses.len() != chunk.len() {
+            panic!(
+                "{} RPC batch on contract {} returned {} responses for {} calls",
+                call,
+                Hex(contract),
+                responses.len(),
+                chunk.len()
+            );
+        }
+        results.extend(
+            chunk
+                .iter()
+                .zip(&responses)
+                .map(|(uid, response)| decode_response(call, contract, *uid, response, &decode)),
+        );
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failures_report_the_call_contract_and_return_data() {
+        let decode = |raw: &[u8]| GetSchema::output(raw).map(Schema::from_rpc_output);
+        let response = |raw: Vec<u8>, failed: bool| RpcResponse { raw, failed };
+
+        let failure = decode_response("getSchema", &[0xaa; 20], [0x55; 32], &response(vec![], false), decode).unwrap_err();
+        assert_eq!(
+            failure.to_string(),
+            format!(
+                "getSchema({}) on contract {} returned no data, is the contract deployed at this address? (0 bytes of return data)",
+                "55".repeat(32),
+                "aa".repeat(20)
+            )
+        );
+        let failure = decode_response("getSchema", &[0xaa; 20], [0x55; 32], &response(vec![1; 32], false), decode).unwrap_err();
+        assert!(failure.reason.starts_with("returned data that cannot be decoded"));
+        assert_eq!(failure.return_data_len, 32);
+        let failure = decode_response("getSchema", &[0xaa; 20], [0x55; 32], &response(vec![1; 4], true), decode).unwrap_err();
+        assert_eq!(failure.reason, "reverted");
+    }
+}
@@ -1,8 +1,135 @@
+use base64::prelude::*;
 use ethabi::{ParamType, Token};
 use serde_json::{json, Value};
+use std::borrow::Cow;
+use std::fmt::Write;
 use std::str::FromStr;
 use substreams::Hex;
 
+/// Naming convention applied to the keys of decoded JSON objects.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum KeyStyle {
+    #[default]
+    AsIs,
+    Snake,
+    Camel,
+}
+
+impl FromStr for KeyStyle {
+    type Err = String;
+    fn from_str(style: &str) -> Result<Self, Self::Err> {
+        match style {
+            "asis" => Ok(KeyStyle::AsIs),
+            "snake" => Ok(KeyStyle::Snake),
+            "camel" => Ok(KeyStyle::Camel),
+            _ => Err(format!("Unsupported key style: {}", style)),
+        }
+    }
+}
+
+impl KeyStyle {
+    pub fn apply(&self, name: &str) -> String {
+        match self {
+            KeyStyle::AsIs => name.to_string(),
+            KeyStyle::Snake => split_words(name).iter().map(|word| word.to_lowercase()).collect::<Vec<_>>().join("_"),
+            KeyStyle::Camel => split_words(name)
+                .iter()
+                .enumerate()
+                .map(|(i, word)| {
+                    let word = word.to_lowercase();
+                    let mut chars = word.chars();
+                    match chars.next() {
+                        Some(first) if i > 0 => first.to_uppercase().chain(chars).collect(),
+                        _ => word,
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Splits a field name into words on underscores and case boundaries (`tokenID_value` -> `token`, `ID`, `value`).
+fn split_words(name: &str) -> Vec<String> {
+    let chars: Vec<char> = name.chars().collect();
+    let mut words = Vec::new();
+    let mut word = String::new();
+    for (i, c) in chars.iter().enumerate() {
+        if *c == '_' {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            continue;
+        }
+        let boundary = c.is_uppercase()
+            && i > 0
+            && (chars[i - 1].is_lowercase()
+                || chars[i - 1].is_ascii_digit()
+                || (chars[i - 1].is_uppercase() && chars.get(i + 1).is_some_and(|next| next.is_lowercase())));
+        if boundary && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        word.push(*c);
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+/// Encoding of `bytes` and `bytesN` values in decoded JSON.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum BinaryEncoding {
+    #[default]
+    Hex,
+    Base64,
+    /// Object holding both representations: `{"hex": "0x..", "base64": ".."}`.
+    Both,
+}
+
+impl FromStr for BinaryEncoding {
+    type Err = String;
+    fn from_str(encoding: &str) -> Result<Self, Self::Err> {
+        match encoding {
+            "hex" => Ok(BinaryEncoding::Hex),
+            "base64" => Ok(BinaryEncoding::Base64),
+            "both" => Ok(BinaryEncoding::Both),
+            _ => Err(format!("Unsupported binary encoding: {}", encoding)),
+        }
+    }
+}
+
+/// Rendering options of decoded attestation data.
+#[derive(Debug, Clone)]
+pub struct RenderOptions {
+    pub key_style: KeyStyle,
+    pub binary_encoding: BinaryEncoding,
+    /// Whether hex strings are `0x` prefixed.
+    pub hex_prefix: bool,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        RenderOptions {
+            key_style: KeyStyle::default(),
+            binary_encoding: BinaryEncoding::default(),
+            hex_prefix: true,
+        }
+    }
+}
+
+impl RenderOptions {
+    /// Renders bytes (addresses, UIDs, data) as a hex string, `0x` prefixed unless `hex_prefix` is disabled.
+    pub fn hex(&self, bytes: &[u8]) -> String {
+        match self.hex_prefix {
+            true => format!("0x{}", Hex::encode(bytes)),
+            false => Hex::encode(bytes),
+        }
+    }
+}
+
+/// Maximum nesting of tuples and of array dimensions in a schema, so that hostile schemas cannot exhaust the stack.
+const MAX_NESTING_DEPTH: usize = 32;
+
 #[derive(Debug, Clone)]
 pub enum FieldType {
     Primitive(ParamType),
@@ -14,107 +141,195 @@ pub enum FieldType {
 impl FromStr for FieldType {
     type Err = String;
     fn from_str(typ: &str) -> Result<Self, Self::Err> {
-        let typ = typ.trim();
-        if typ.starts_with("tuple(") && typ.ends_with(')') {
-            Ok(FieldType::Tuple(parse_schema_fields(&typ[6..typ.len() - 1])?))
-        } else if typ.starts_with('(') && typ.ends_with(')') && !typ.ends_with("[]") {
-            // Handle tuples in the format (type1 name1, type2 name2)
-            Ok(FieldType::Tuple(parse_schema_fields(&typ[1..typ.len() - 1])?))
-        } else if typ.ends_with("[]") {
-            // Handle dynamic arrays (ending with [])
-            Ok(FieldType::Array(Box::new(FieldType::from_str(&typ[..typ.len() - 2])?)))
-        } else if let Some(pos) = typ.rfind('[') {
-            if typ.ends_with(']') {
-                // Handle fixed-size arrays [N] and potentially nested arrays
-                let size_str = &typ[pos + 1..typ.len() - 1];
-                // If size_str is empty, it's a dynamic array ([]), which we already handled
-                if !size_str.is_empty() {
-                    let size = size_str.parse::<usize>().map_err(|_| format!("Invalid array size: {}", size_str))?;
-                    let base_type = &typ[0..pos];
-                    return Ok(FieldType::FixedArray(Box::new(FieldType::from_str(base_type)?), size));
-                }
-            }
-
-            // If we get here, it's an invalid array format
-            return Err(format!("Invalid array format: {}", typ));
-        } else {
-            let param_type = if typ == "bytes" {
-                ParamType::Bytes
-            } else if let Some(bits) = typ.strip_prefix("uint") {
-                ParamType::Uint(bits.parse::<usize>().map_err(|_| format!("Invalid uint size: {}", bits))?)
-            } else if let Some(bits) = typ.strip_prefix("int") {
-                ParamType::Int(bits.parse::<usize>().map_err(|_| format!("Invalid int size: {}", bits))?)
-            } else if let Some(bits) = typ.strip_prefix("bytes") {
-                ParamType::FixedBytes(bits.parse::<usize>().map_err(|_| format!("Invalid bytes size: {}", bits))?)
-            } else {
-                match typ {
-                    "bool" => ParamType::Bool,
-                    "string" => ParamType::String,
-                    "address" => ParamType::Address,
-                    _ => return Err(format!("Unsupported type: {}", typ)),
-                }
-            };
-            Ok(FieldType::Primitive(param_type))
+        let mut scanner = Scanner::new(typ.trim());
+        let field_type = scanner.field_type()?;
+        match scanner.peek() {
+            None => Ok(field_type),
+            Some(_) => Err(format!("Unexpected '{}' in type: {}", scanner.rest(), typ)),
         }
     }
 }
 
-pub fn parse_schema_fields(schema: &str) -> Result<Vec<(FieldType, String)>, String> {
-    let mut fields = Vec::new();
-    let mut depth = 0;
-    let mut start = 0;
-    let chars: Vec<_> = schema.chars().collect();
-    for (i, c) in chars.iter().enumerate() {
-        match c {
-            '(' => depth += 1,
-            ')' => depth -= 1,
-            ',' if depth == 0 => {
-                let field = &schema[start..i];
-                match parse_field(field.trim()) {
-                    Ok(parsed_field) => fields.push(parsed_field),
-                    Err(e) => return Err(format!("Failed to parse field '{}': {}", field.trim(), e)),
-                }
-                start = i + 1;
+/// Single pass scanner over the bytes of a schema signature, parsing it by recursive descent: every byte is read once,
+/// whatever the nesting of tuples and arrays. The delimiters being ASCII, field names are sliced out on character
+/// boundaries, multi-byte characters included.
+struct Scanner<'a> {
+    schema: &'a str,
+    pos: usize,
+    /// Nesting of the tuple being parsed.
+    depth: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(schema: &'a str) -> Self {
+        Scanner { schema, pos: 0, depth: 0 }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.schema.as_bytes().get(self.pos).copied()
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.schema[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|byte| byte.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    /// Position of the opening bracket of the next array suffix, which may be preceded by whitespace (e.g.
+    /// `uint256 [] a`), `None` when the type has no further dimension.
+    fn array_suffix(&self) -> Option<usize> {
+        let rest = self.rest().trim_start_matches(|c: char| c.is_ascii_whitespace());
+        rest.starts_with('[').then(|| self.schema.len() - rest.len())
+    }
+
+    /// Consumes the bytes matching `accept`, returning them.
+    fn take_while(&mut self, accept: impl Fn(u8) -> bool) -> &'a str {
+        let start = self.pos;
+        while self.peek().is_some_and(&accept) {
+            self.pos += 1;
+        }
+        &self.schema[start..self.pos]
+    }
+
+    /// Comma separated fields, up to the end of the schema at the top level, or up to the closing parenthesis (left
+    /// unconsumed) within a tuple. A trailing comma is allowed, empty fields are not.
+    fn fields(&mut self) -> Result<Vec<(FieldType, String)>, String> {
+        let mut fields = Vec::new();
+        loop {
+            match self.peek() {
+                None if self.depth > 0 => return Err("Unbalanced parentheses".to_string()),
+                None => return Ok(fields),
+                Some(b')') if self.depth > 0 => return Ok(fields),
+                Some(b')') => return Err("Unbalanced parentheses".to_string()),
+                _ => {}
+            }
+            let start = self.pos;
+            let field = self.field().map_err(|e| format!("Failed to parse field '{}': {}", self.field_text(start), e))?;
+            fields.push(field);
+            if self.peek() == Some(b',') {
+                self.pos += 1;
             }
-            _ => {}
         }
     }
-    // Last field
-    if start < schema.len() {
-        let field = &schema[start..];
-        match parse_field(field.trim()) {
-            Ok(parsed_field) => fields.push(parsed_field),
-            Err(e) => return Err(format!("Failed to parse field '{}': {}", field.trim(), e)),
+
+    /// Text of the field starting at `start`, up to the next top-level comma, for error messages.
+    fn field_text(&self, start: usize) -> &'a str {
+        let mut depth = 0usize;
+        let end = self.schema[start..]
+            .bytes()
+            .position(|byte| match byte {
+                b'(' => {
+                    depth += 1;
+                    false
+                }
+                b')' if depth == 0 => true,
+                b')' => {
+                    depth -= 1;
+                    false
+                }
+                b',' => depth == 0,
+                _ => false,
+            })
+            .map_or(self.schema.len(), |end| start + end);
+        self.schema[start..end].trim()
+    }
+
+    /// A type, then its name, `field` when unnamed, up to the next comma or closing parenthesis (left unconsumed).
+    fn field(&mut self) -> Result<(FieldType, String), String> {
+        self.skip_whitespace();
+        let field_type = self.field_type()?;
+        if self.peek().is_some_and(|byte| !byte.is_ascii_whitespace() && !matches!(byte, b',' | b')')) {
+            return Err(format!("Unexpected '{}' after type", self.rest().chars().next().unwrap_or_default()));
+        }
+        self.skip_whitespace();
+        let name = self.take_while(|byte| !byte.is_ascii_whitespace() && !matches!(byte, b',' | b'(' | b')'));
+        self.skip_whitespace();
+        match self.rest().chars().next() {
+            None | Some(',' | ')') => Ok((field_type, if name.is_empty() { "field" } else { name }.to_string())),
+            Some(unexpected) => Err(format!("Unexpected '{}'", unexpected)),
         }
     }
-    Ok(fields)
-}
 
-fn parse_field(field: &str) -> Result<(FieldType, String), String> {
-    let mut type_end = 0;
-    let mut inner_depth = 0;
-    for (j, ch) in field.char_indices().rev() {
-        match ch {
-            ')' => inner_depth += 1,
-            '(' => inner_depth -= 1,
-            ' ' if inner_depth == 0 => {
-                type_end = j;
-                break;
+    /// A tuple (`(..)` or `tuple(..)`) or primitive type, followed by its array dimensions.
+    fn field_type(&mut self) -> Result<FieldType, String> {
+        let mut field_type = match self.peek() {
+            Some(b'(') => self.tuple()?,
+            _ if self.rest().starts_with("tuple(") => {
+                self.pos += "tuple".len();
+                self.tuple()?
+            }
+            _ => FieldType::Primitive(primitive_type(self.take_while(|byte| byte.is_ascii_alphanumeric()))?),
+        };
+        let mut dimensions = 0;
+        while let Some(suffix) = self.array_suffix() {
+            dimensions += 1;
+            if dimensions > MAX_NESTING_DEPTH {
+                return Err("Too many array dimensions".to_string());
             }
-            _ => {}
+            self.pos = suffix + 1;
+            let size = self.take_while(|byte| byte != b']' && byte != b'[');
+            if self.peek() != Some(b']') {
+                return Err(format!("Invalid array format: [{}", size));
+            }
+            self.pos += 1;
+            field_type = match size {
+                "" => FieldType::Array(Box::new(field_type)),
+                _ => match size.parse::<usize>().map_err(|_| format!("Invalid array size: {}", size))? {
+                    // zero-sized values would let an array length drive an unbounded decoding loop
+                    0 => return Err("Invalid array size: 0".to_string()),
+                    size => FieldType::FixedArray(Box::new(field_type), size),
+                },
+            };
         }
+        Ok(field_type)
     }
-    let (typ, name) = if type_end > 0 {
-        (field[..type_end].trim(), field[type_end..].trim())
-    } else {
-        (field.trim(), "field")
-    };
-    match FieldType::from_str(typ) {
-        Ok(field_type) => Ok((field_type, name.to_string())),
-        Err(e) => Err(format!("Failed to parse type '{}': {}", typ, e)),
+
+    /// Parenthesized tuple fields, the scanner being on the opening parenthesis.
+    fn tuple(&mut self) -> Result<FieldType, String> {
+        if self.depth == MAX_NESTING_DEPTH {
+            return Err("Too many nested tuples".to_string());
+        }
+        self.pos += 1;
+        self.depth += 1;
+        let fields = self.fields()?;
+        self.depth -= 1;
+        // `fields` only returns within a tuple on its closing parenthesis
+        self.pos += 1;
+        if fields.is_empty() {
+            // zero-sized values would let an array length drive an unbounded decoding loop
+            return Err("Empty tuple".to_string());
+        }
+        Ok(FieldType::Tuple(fields))
     }
 }
 
+pub fn primitive_type(typ: &str) -> Result<ParamType, String> {
+    Ok(if typ == "bytes" {
+        ParamType::Bytes
+    } else if let Some(bits) = typ.strip_prefix("uint") {
+        ParamType::Uint(bits.parse::<usize>().map_err(|_| format!("Invalid uint size: {}", bits))?)
+    } else if let Some(bits) = typ.strip_prefix("int") {
+        ParamType::Int(bits.parse::<usize>().map_err(|_| format!("Invalid int size: {}", bits))?)
+    } else if let Some(bits) = typ.strip_prefix("bytes") {
+        ParamType::FixedBytes(bits.parse::<usize>().map_err(|_| format!("Invalid bytes size: {}", bits))?)
+    } else {
+        match typ {
+            "bool" => ParamType::Bool,
+            "string" => ParamType::String,
+            "address" => ParamType::Address,
+            _ => return Err(format!("Unsupported type: {}", typ)),
+        }
+    })
+}
+
+pub fn parse_schema_fields(schema: &str) -> Result<Vec<(FieldType, String)>, String> {
+    Scanner::new(schema).fields()
+}
+
 // Add a helper to convert FieldType to ParamType for ABI decoding
 pub fn fieldtype_to_paramtype(ft: &FieldType) -> ParamType {
     match ft {
@@ -125,34 +340,502 @@ pub fn fieldtype_to_paramtype(ft: &FieldType) -> ParamType {
     }
 }
 
-fn token_to_json(token: &Token) -> Value {
+/// Canonical text of parsed schema fields: types (tuples written `(..)` rather than `tuple(..)`) and names, single
+/// spaced and without spaces around separators, e.g. `uint256 score,(address who,bool ok)[] votes`.
+pub fn canonical_schema(fields: &[(FieldType, String)]) -> String {
+    fields
+        .iter()
+        .map(|(field_type, name)| format!("{} {}", canonical_type(field_type), name))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn canonical_type(field_type: &FieldType) -> String {
+    match field_type {
+        FieldType::Primitive(param_type) => param_type.to_string(),
+        FieldType::Tuple(fields) => format!("({})", canonical_schema(fields)),
+        FieldType::Array(inner) => format!("{}[]", canonical_type(inner)),
+        FieldType::FixedArray(inner, size) => format!("{}[{}]", canonical_type(inner), size),
+    }
+}
+
+fn bytes_to_json(bytes: &[u8], options: &RenderOptions) -> Value {
+    match options.binary_encoding {
+        BinaryEncoding::Hex => json!(options.hex(bytes)),
+        BinaryEncoding::Base64 => json!(BASE64_STANDARD.encode(bytes)),
+        BinaryEncoding::Both => json!({
+            "hex": options.hex(bytes),
+            "base64": BASE64_STANDARD.encode(bytes),
+        }),
+    }
+}
+
+fn token_to_json(token: &Token, options: &RenderOptions) -> Value {
     match token {
-        Token::Address(addr) => json!(format!("0x{}", Hex::encode(addr))),
-        Token::FixedBytes(bytes) | Token::Bytes(bytes) => {
-            json!(format!("0x{}", Hex::encode(bytes)))
-        }
+        Token::Address(addr) => json!(options.hex(addr.as_bytes())),
+        Token::FixedBytes(bytes) | Token::Bytes(bytes) => bytes_to_json(bytes, options),
         Token::Int(i) | Token::Uint(i) => json!(i.to_string()),
         Token::Bool(b) => json!(*b),
         Token::String(s) => json!(s),
-        Token::Array(arr) | Token::FixedArray(arr) => Value::Array(arr.iter().map(token_to_json).collect()),
-        Token::Tuple(tuple) => Value::Array(tuple.iter().map(token_to_json).collect()),
+        Token::Array(arr) | Token::FixedArray(arr) => Value::Array(arr.iter().map(|t| token_to_json(t, options)).collect()),
+        Token::Tuple(tuple) => Value::Array(tuple.iter().map(|t| token_to_json(t, options)).collect()),
     }
 }
 
-pub fn token_to_json_with_schema(ft: &FieldType, token: &Token) -> Value {
+pub fn token_to_json_with_schema(ft: &FieldType, token: &Token, options: &RenderOptions) -> Value {
     match (ft, token) {
-        (FieldType::Primitive(_), t) => token_to_json(t),
+        (FieldType::Primitive(_), t) => token_to_json(t, options),
         (FieldType::Tuple(fields), Token::Tuple(tokens)) => {
             let mut obj = serde_json::Map::new();
             for ((field, name), token) in fields.iter().zip(tokens.iter()) {
-                obj.insert(name.clone(), token_to_json_with_schema(field, token));
+                obj.insert(options.key_style.apply(name), token_to_json_with_schema(field, token, options));
             }
             Value::Object(obj)
         }
-        (FieldType::Array(inner_ft), Token::Array(tokens)) => Value::Array(tokens.iter().map(|t| token_to_json_with_schema(inner_ft, t)).collect()),
+        (FieldType::Array(inner_ft), Token::Array(tokens)) => Value::Array(tokens.iter().map(|t| token_to_json_with_schema(inner_ft, t, options)).collect()),
         (FieldType::FixedArray(inner_ft, _), Token::FixedArray(tokens)) => {
-            Value::Array(tokens.iter().map(|t| token_to_json_with_schema(inner_ft, t)).collect())
+            Value::Array(tokens.iter().map(|t| token_to_json_with_schema(inner_ft, t, options)).collect())
         }
         _ => Value::Null, // fallback for mismatches
     }
 }
+
+/// Writes decoded fields as the JSON object their `token_to_json_with_schema` values render to, straight from the
+/// tokens, without building `Value` trees: keys sorted as in a `serde_json::Map`, the last of duplicate keys kept, and
+/// the fields without token (missing from truncated data) `null`.
+pub fn fields_to_json(fields: &[(FieldType, String)], tokens: &[Token], options: &RenderOptions) -> String {
+    let mut json = String::with_capacity(64 * fields.len());
+    write_object(&mut json, fields, tokens, options);
+    json
+}
+
+fn write_object(json: &mut String, fields: &[(FieldType, String)], tokens: &[Token], options: &RenderOptions) {
+    let mut keys: Vec<(Cow<str>, usize)> = fields
+        .iter()
+        .enumerate()
+        .map(|(i, (_, name))| match options.key_style {
+            KeyStyle::AsIs => (Cow::Borrowed(name.as_str()), i),
+            key_style => (Cow::Owned(key_style.apply(name)), i),
+        })
+        .collect();
+    // stable, duplicate keys stay in field order
+    keys.sort_by(|(a, _), (b, _)| a.cmp(b));
+    json.push('{');
+    let mut first = true;
+    for (n, (key, i)) in keys.iter().enumerate() {
+        if keys.get(n + 1).is_some_and(|(next, _)| next == key) {
+            continue;
+        }
+        if !first {
+            json.push(',');
+        }
+        first = false;
+        write_string(json, key);
+        json.push(':');
+        match tokens.get(*i) {
+            Some(token) => write_value(json, &fields[*i].0, token, options),
+            None => json.push_str("null"),
+        }
+    }
+    json.push('}');
+}
+
+/// Writes the value `token_to_json_with_schema` renders.
+fn write_value(json: &mut String, ft: &FieldType, token: &Token, options: &RenderOptions) {
+    match (ft, token) {
+        (FieldType::Primitive(_), token) => write_token(json, token, options),
+        (FieldType::Tuple(fields), Token::Tuple(tokens)) => write_object(json, fields, tokens, options),
+        (FieldType::Array(inner_ft), Token::Array(tokens)) | (FieldType::FixedArray(inner_ft, _), Token::FixedArray(tokens)) => {
+            write_array(json, tokens, |json, token| write_value(json, inner_ft, token, options))
+        }
+        _ => json.push_str("null"),
+    }
+}
+
+/// Writes the value `token_to_json` renders.
+fn write_token(json: &mut String, token: &Token, options: &RenderOptions) {
+    match token {
+        Token::Address(addr) => write_hex(json, addr.as_bytes(), options),
+        Token::FixedBytes(bytes) | Token::Bytes(bytes) => match options.binary_encoding {
+            BinaryEncoding::Hex => write_hex(json, bytes, options),
+            BinaryEncoding::Base64 => write_base64(json, bytes),
+            BinaryEncoding::Both => {
+                json.push_str("{\"base64\":");
+                write_base64(json, bytes);
+                json.push_str(",\"hex\":");
+                write_hex(json, bytes, options);
+                json.push('}');
+            }
+        },
+        Token::Int(i) | Token::Uint(i) => {
+            let _ = write!(json, "\"{}\"", i);
+        }
+        Token::Bool(b) => json.push_str(if *b { "true" } else { "false" }),
+        Token::String(s) => write_string(json, s),
+        Token::Array(tokens) | Token::FixedArray(tokens) | Token::Tuple(tokens) => write_array(json, tokens, |json, token| write_token(json, token, options)),
+    }
+}
+
+fn write_array(json: &mut String, tokens: &[Token], write: impl Fn(&mut String, &Token)) {
+    json.push('[');
+    for (i, token) in tokens.iter().enumerate() {
+        if i > 0 {
+            json.push(',');
+        }
+        write(json, token);
+    }
+    json.push(']');
+}
+
+/// Writes bytes as a hex string, the same as `RenderOptions::hex`.
+fn write_hex(json: &mut String, bytes: &[u8], options: &RenderOptions) {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    json.reserve(bytes.len() * 2 + 4);
+    json.push_str(if options.hex_prefix { "\"0x" } else { "\"" });
+    for byte in bytes {
+        json.push(DIGITS[(byte >> 4) as usize] as char);
+        json.push(DIGITS[(byte & 0xf) as usize] as char);
+    }
+    json.push('"');
+}
+
+fn write_base64(json: &mut String, bytes: &[u8]) {
+    json.push('"');
+    BASE64_STANDARD.encode_string(bytes, json);
+    json.push('"');
+}
+
+/// Writes a JSON string with the escapes of `serde_json`: quotes, backslashes and control characters, the latter as
+/// `\b`, `\t`, `\n`, `\f`, `\r` or `\u00XX`.
+fn write_string(json: &mut String, string: &str) {
+    json.push('"');
+    let mut start = 0;
+    for (i, byte) in string.bytes().enumerate() {
+        let escape = match byte {
+            b'"' => "\\\"",
+            b'\\' => "\\\\",
+            0x08 => "\\b",
+            b'\t' => "\\t",
+            b'\n' => "\\n",
+            0x0c => "\\f",
+            b'\r' => "\\r",
+            0x00..=0x1f => "",
+            _ => continue,
+        };
+        json.push_str(&string[start..i]);
+        match escape {
+            "" => {
+                let _ = write!(json, "\\u{:04x}", byte);
+            }
+            escape => json.push_str(escape),
+        }
+        start = i + 1;
+    }
+    json.push_str(&string[start..]);
+    json.push('"');
+}
+
+/// Compact form of a decoded `bool` or `address` array, `None` for other types: a bitstring for booleans (`"101"`
+/// for `[true, false, true]`), and the count, number of distinct values and distinct values in first-seen order for
+/// addresses, e.g. for large committee votes or signer sets.
+pub fn compact_array(ft: &FieldType, value: &Value) -> Option<Value> {
+    let (FieldType::Array(inner) | FieldType::FixedArray(inner, _)) = ft else {
+        return None;
+    };
+    let values = value.as_array()?;
+    match inner.as_ref() {
+        FieldType::Primitive(ParamType::Bool) => Some(Value::String(
+            values.iter().map(|value| if value.as_bool() == Some(true) { '1' } else { '0' }).collect(),
+        )),
+        FieldType::Primitive(ParamType::Address) => {
+            let mut unique: Vec<&Value> = vec![];
+            for value in values {
+                if !unique.contains(&value) {
+                    unique.push(value);
+                }
+            }
+            Some(json!({
+                "count": values.len(),
+                "unique_count": unique.len(),
+                "unique": unique,
+            }))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_multi_byte_field_names() {
+        let fields = parse_schema_fields("string née,uint8 b").unwrap();
+        assert_eq!(fields.iter().map(|(_, name)| name.as_str()).collect::<Vec<_>>(), ["née", "b"]);
+    }
+
+    #[test]
+    fn rejects_unbalanced_parentheses() {
+        assert!(parse_schema_fields(")uint8 a,(uint8 b").is_err());
+        assert!(parse_schema_fields("(uint8 a").is_err());
+    }
+
+    #[test]
+    fn rejects_deeply_nested_schemas() {
+        let tuples = format!("{}uint8 a{} b", "(".repeat(10_000), ")".repeat(10_000));
+        assert!(parse_schema_fields(&tuples).is_err());
+        let arrays = format!("uint8{} a", "[]".repeat(10_000));
+        assert!(parse_schema_fields(&arrays).is_err());
+    }
+
+    #[test]
+    fn canonicalizes_spacing_and_tuple_syntax() {
+        let fields = parse_schema_fields("uint256  score , tuple(address who, bool ok)[] votes").unwrap();
+        assert_eq!(canonical_schema(&fields), "uint256 score,(address who,bool ok)[] votes");
+    }
+
+    #[test]
+    fn decodes_named_nested_tuples_as_objects() {
+        let schema = "tuple(uint256 amount, tuple(string name, string url) meta) details, tuple(string name, tuple(string name, string url)[] links)[] items";
+        let link = |name: &str, url: &str| Token::Tuple(vec![Token::String(name.to_string()), Token::String(url.to_string())]);
+        let data = ethabi::encode(&[
+            Token::Tuple(vec![Token::Uint(7.into()), link("eas", "https://attest.org")]),
+            Token::Array(vec![Token::Tuple(vec![
+                Token::String("docs".to_string()),
+                Token::Array(vec![link("a", "https://a"), link("b", "https://b")]),
+            ])]),
+        ]);
+
+        let decoded = crate::decode_data(&data, schema);
+        assert_eq!(
+            Value::Object(decoded),
+            json!({
+                "details": {"amount": "7", "meta": {"name": "eas", "url": "https://attest.org"}},
+                "items": [{"name": "docs", "links": [{"name": "a", "url": "https://a"}, {"name": "b", "url": "https://b"}]}],
+            })
+        );
+    }
+
+    #[test]
+    fn renders_bool_and_address_arrays_in_full_and_compact_forms() {
+        let fields = parse_schema_fields("bool[] votes,address[3] signers,uint8[] scores").unwrap();
+        let address = |byte: u8| Token::Address(ethabi::Address::repeat_byte(byte));
+        let data = ethabi::encode(&[
+            Token::Array(vec![Token::Bool(true), Token::Bool(false), Token::Bool(true)]),
+            Token::FixedArray(vec![address(0xaa), address(0xbb), address(0xaa)]),
+            Token::Array(vec![Token::Uint(1.into())]),
+        ]);
+        let decoded = crate::decode_data(&data, "bool[] votes,address[3] signers,uint8[] scores");
+        let (aa, bb) = (format!("0x{}", "aa".repeat(20)), format!("0x{}", "bb".repeat(20)));
+        assert_eq!(decoded["votes"], json!([true, false, true]));
+        assert_eq!(decoded["signers"], json!([aa, bb, aa]));
+
+        assert_eq!(compact_array(&fields[0].0, &decoded["votes"]), Some(json!("101")));
+        assert_eq!(
+            compact_array(&fields[1].0, &decoded["signers"]),
+            Some(json!({"count": 3, "unique_count": 2, "unique": [aa, bb]}))
+        );
+        assert_eq!(compact_array(&fields[2].0, &decoded["scores"]), None);
+    }
+
+    #[test]
+    fn rejects_malformed_fields() {
+        assert!(parse_schema_fields("uint8é a").is_err());
+        assert!(parse_schema_fields("uint8[2]bool a").is_err());
+        assert!(parse_schema_fields("uint8 a b").is_err());
+        assert!(parse_schema_fields("uint8 a,,bool b").is_err());
+        assert!(parse_schema_fields("uint8[2 a").is_err());
+        // unnamed fields and a trailing comma are accepted
+        let fields = parse_schema_fields("( uint8 a , bool ) t,uint8,").unwrap();
+        assert_eq!(canonical_schema(&fields), "(uint8 a,bool field) t,uint8 field");
+    }
+
+    #[test]
+    fn accepts_whitespace_before_array_suffixes() {
+        let fields = parse_schema_fields("uint256 [] a,(bool ok) [2] [] t,bytes32\t[3] b").unwrap();
+        assert_eq!(canonical_schema(&fields), "uint256[] a,(bool ok)[2][] t,bytes32[3] b");
+    }
+
+    #[test]
+    fn rejects_zero_sized_types() {
+        assert!(parse_schema_fields("()[] a").is_err());
+        assert!(parse_schema_fields("uint8[0][] a").is_err());
+    }
+
+    /// Deterministic xorshift generator, so that failing cases reproduce from their seed.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, n: usize) -> usize {
+            (self.next() % n as u64) as usize
+        }
+
+        fn bytes(&mut self, len: usize) -> Vec<u8> {
+            (0..len).map(|_| self.next() as u8).collect()
+        }
+    }
+
+    fn random_type(rng: &mut Rng, depth: usize) -> ParamType {
+        match rng.below(if depth == 0 { 7 } else { 10 }) {
+            0 => ParamType::Uint(8 * (1 + rng.below(32))),
+            1 => ParamType::Int(8 * (1 + rng.below(32))),
+            2 => ParamType::FixedBytes(1 + rng.below(32)),
+            3 => ParamType::Bytes,
+            4 => ParamType::String,
+            5 => ParamType::Bool,
+            6 => ParamType::Address,
+            7 => ParamType::Array(Box::new(random_type(rng, depth - 1))),
+            8 => ParamType::FixedArray(Box::new(random_type(rng, depth - 1)), 1 + rng.below(3)),
+            _ => ParamType::Tuple((0..1 + rng.below(3)).map(|_| random_type(rng, depth - 1)).collect()),
+        }
+    }
+
+    /// Schema type string of a param type, naming tuple components `f0`, `f1`, ...
+    fn type_string(param: &ParamType) -> String {
+        match param {
+            ParamType::Uint(bits) => format!("uint{}", bits),
+            ParamType::Int(bits) => format!("int{}", bits),
+            ParamType::FixedBytes(len) => format!("bytes{}", len),
+            ParamType::Bytes => "bytes".to_string(),
+            ParamType::String => "string".to_string(),
+            ParamType::Bool => "bool".to_string(),
+            ParamType::Address => "address".to_string(),
+            ParamType::Array(inner) => format!("{}[]", type_string(inner)),
+            ParamType::FixedArray(inner, size) => format!("{}[{}]", type_string(inner), size),
+            ParamType::Tuple(params) => format!("({})", fields_string(params)),
+        }
+    }
+
+    fn fields_string(params: &[ParamType]) -> String {
+        params
+            .iter()
+            .enumerate()
+            .map(|(i, param)| format!("{} f{}", type_string(param), i))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    fn random_token(rng: &mut Rng, param: &ParamType) -> Token {
+        match param {
+            ParamType::Uint(bits) => Token::Uint((rng.next() >> (64 - (*bits).min(64))).into()),
+            ParamType::Int(_) => Token::Int(if rng.below(2) == 0 { rng.below(1_000).into() } else { ethabi::Int::MAX }),
+            ParamType::FixedBytes(len) => Token::FixedBytes(rng.bytes(*len)),
+            ParamType::Bytes => {
+                let len = rng.below(40);
+                Token::Bytes(rng.bytes(len))
+            }
+            ParamType::String => {
+                let len = rng.below(20);
+                Token::String((0..len).map(|_| (b'a' + rng.below(26) as u8) as char).collect())
+            }
+            ParamType::Bool => Token::Bool(rng.below(2) == 0),
+            ParamType::Address => Token::Address(ethabi::Address::from_slice(&rng.bytes(20))),
+            ParamType::Array(inner) => Token::Array((0..rng.below(3)).map(|_| random_token(rng, inner)).collect()),
+            ParamType::FixedArray(inner, size) => Token::FixedArray((0..*size).map(|_| random_token(rng, inner)).collect()),
+            ParamType::Tuple(params) => Token::Tuple(params.iter().map(|param| random_token(rng, param)).collect()),
+        }
+    }
+
+    /// Asserts that a decoded JSON value has the JSON type of its param type, recursing into arrays and tuples.
+    fn assert_json_type(param: &ParamType, token: &Token, value: &Value, schema: &str) {
+        match (param, token) {
+            (ParamType::Bool, Token::Bool(b)) => assert_eq!(value, &json!(*b), "schema: {}", schema),
+            (ParamType::Uint(_), Token::Uint(u)) => assert_eq!(value, &json!(u.to_string()), "schema: {}", schema),
+            (ParamType::Array(inner), Token::Array(tokens)) | (ParamType::FixedArray(inner, _), Token::FixedArray(tokens)) => {
+                let values = value
+                    .as_array()
+                    .unwrap_or_else(|| panic!("expected an array, got {} for schema: {}", value, schema));
+                assert_eq!(values.len(), tokens.len(), "schema: {}", schema);
+                for (token, value) in tokens.iter().zip(values) {
+                    assert_json_type(inner, token, value, schema);
+                }
+            }
+            (ParamType::Tuple(params), Token::Tuple(tokens)) => {
+                let object = value
+                    .as_object()
+                    .unwrap_or_else(|| panic!("expected an object, got {} for schema: {}", value, schema));
+                assert_eq!(object.len(), params.len(), "schema: {}", schema);
+                for (i, (param, token)) in params.iter().zip(tokens).enumerate() {
+                    assert_json_type(param, token, &object[&format!("f{}", i)], schema);
+                }
+            }
+            _ => assert!(value.is_string(), "expected a string, got {} for schema: {}", value, schema),
+        }
+    }
+
+    #[test]
+    fn decodes_random_schemas_round_trip() {
+        for seed in 1..=500 {
+            let mut rng = Rng(seed);
+            let params: Vec<ParamType> = (0..1 + rng.below(5)).map(|_| random_type(&mut rng, 3)).collect();
+            let tokens: Vec<Token> = params.iter().map(|param| random_token(&mut rng, param)).collect();
+            let schema = fields_string(&params);
+
+            let decoded = crate::decode_data(&ethabi::encode(&tokens), &schema);
+            assert_eq!(decoded.len(), params.len(), "seed {}, schema: {}, decoded: {:?}", seed, schema, decoded);
+            for (i, (param, token)) in params.iter().zip(&tokens).enumerate() {
+                assert_json_type(param, token, &decoded[&format!("f{}", i)], &schema);
+            }
+        }
+    }
+
+    #[test]
+    fn writes_the_same_json_as_the_value_rendering() {
+        let value_json = |fields: &[(FieldType, String)], tokens: &[Token], options: &RenderOptions| {
+            let values = fields
+                .iter()
+                .zip(tokens.iter().map(Some).chain(std::iter::repeat(None)))
+                .map(|((ft, name), token)| {
+                    let value = token.map_or(Value::Null, |token| token_to_json_with_schema(ft, token, options));
+                    (options.key_style.apply(name), value)
+                });
+            Value::Object(serde_json::Map::from_iter(values)).to_string()
+        };
+        let options: Vec<RenderOptions> = [KeyStyle::AsIs, KeyStyle::Camel]
+            .into_iter()
+            .flat_map(|key_style| {
+                [BinaryEncoding::Hex, BinaryEncoding::Base64, BinaryEncoding::Both]
+                    .into_iter()
+                    .map(move |binary_encoding| RenderOptions {
+                        key_style,
+                        binary_encoding,
+                        hex_prefix: binary_encoding != BinaryEncoding::Base64,
+                    })
+            })
+            .collect();
+
+        for seed in 1..=200 {
+            let mut rng = Rng(seed);
+            let params: Vec<ParamType> = (0..1 + rng.below(5)).map(|_| random_type(&mut rng, 3)).collect();
+            let tokens: Vec<Token> = params.iter().map(|param| random_token(&mut rng, param)).collect();
+            let fields = parse_schema_fields(&fields_string(&params)).unwrap();
+            for options in &options {
+                assert_eq!(
+                    fields_to_json(&fields, &tokens, options),
+                    value_json(&fields, &tokens, options),
+                    "seed {}",
+                    seed
+                );
+            }
+        }
+
+        // escaped strings, duplicate keys (the last one wins) and a missing trailing field
+        let fields = parse_schema_fields("string note,bool flag_value,uint8 note,bool flagValue,address missing").unwrap();
+        let tokens = [
+            Token::String("say \"hi\"\\\n\t\u{1}é".to_string()),
+            Token::Bool(true),
+            Token::Uint(7.into()),
+            Token::Bool(false),
+        ];
+        for options in &options {
+            assert_eq!(fields_to_json(&fields, &tokens, options), value_json(&fields, &tokens, options));
+        }
+    }
+}
@@ -0,0 +1,238 @@
+use crate::params::Params;
+use crate::pb::contract::v1 as contract;
+use crate::rpc::{EasRpc, RpcFailure};
+use crate::{parse_schema_ids, schemas, Attestation, FieldType, Schema};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use substreams::store::{StoreGet, StoreGetProto};
+use substreams::Hex;
+
+/// Schema records known without RPC, e.g. the `Registered` events kept by `store_schemas`.
+pub trait SchemaLookup {
+    /// Returns the schema record of the given UID, `None` when unknown.
+    fn lookup(&self, schema_id: &[u8; 32]) -> Option<Schema>;
+}
+
+impl SchemaLookup for StoreGetProto<contract::Schema> {
+    fn lookup(&self, schema_id: &[u8; 32]) -> Option<Schema> {
+        let schema = self.get_last(Hex(schema_id).to_string())?;
+        Some(Schema {
+            uid_id: *schema_id,
+            resolver: schema.current_resolver,
+            revocable: schema.revocable,
+            schema: schema.schema,
+        })
+    }
+}
+
+/// Schema signatures known up front, set with the `schema_snapshot` param (`;` separated `<schema_uid>:<signature>`
+/// entries), so that streams starting at a recent block do not need RPC for schemas registered long before. Schemas
+/// being immutable once registered, the snapshot is consulted before any other source. Its records carry no
+/// resolver, and are taken as revocable.
+#[derive(Debug, Clone, Default)]
+pub struct SchemaSnapshot {
+    signatures: BTreeMap<[u8; 32], String>,
+}
+
+impl SchemaSnapshot {
+    pub fn from_params(params: &Params) -> Result<Self, substreams::errors::Error> {
+        let mut signatures = BTreeMap::new();
+        for entry in params
+            .get("schema_snapshot")
+            .unwrap_or_default()
+            .split(';')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+        {
+            let invalid = || substreams::errors::Error::msg(format!("invalid schema '{}' for param 'schema_snapshot'", entry));
+            let (schema_id, signature) = entry.split_once(':').ok_or_else(invalid)?;
+            let schema_id = parse_schema_ids("schema_snapshot", &[schema_id])?.into_iter().next().ok_or_else(invalid)?;
+            signatures.insert(schema_id, signature.trim().to_string());
+        }
+        Ok(SchemaSnapshot { signatures })
+    }
+}
+
+impl SchemaLookup for SchemaSnapshot {
+    fn lookup(&self, schema_id: &[u8; 32]) -> Option<Schema> {
+        Some(Schema {
+            uid_id: *schema_id,
+            resolver: vec![],
+            revocable: true,
+            schema: self.signatures.get(schema_id)?.clone(),
+        })
+    }
+}
+
+/// Source of the schema records tried first by a [`SchemaResolver`], set with the `schema_source` param, the other
+/// one being the fallback.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum SchemaSource {
+    /// `getSchema` RPC calls to the schema registry, falling back to the schema store when they fail.
+    #[default]
+    Rpc,
+    /// The schema store, falling back to RPC for schemas registered before the start block of the modules.
+    Store,
+}
+
+impl FromStr for SchemaSource {
+    type Err = String;
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        match source {
+            "rpc" => Ok(SchemaSource::Rpc),
+            "store" => Ok(SchemaSource::Store),
+            _ => Err(format!("Unsupported schema source: {}", source)),
+        }
+    }
+}
+
+/// [`EasRpc`] resolving schemas in layers: the `snapshot`, then the `primary` source, then the other one for the
+/// schemas they cannot resolve. Attestations are always read through `rpc`, parsed schema fields from
+/// `parsed_schemas` when set.
+pub struct SchemaResolver<'a, R: EasRpc, S: SchemaLookup> {
+    pub rpc: R,
+    pub store: &'a S,
+    pub snapshot: &'a SchemaSnapshot,
+    pub primary: SchemaSource,
+    pub parsed_schemas: Option<&'a StoreGetProto<contract::SchemaFields>>,
+}
+
+impl<R: EasRpc, S: SchemaLookup> EasRpc for SchemaResolver<'_, R, S> {
+    fn get_attestations(&self, uids: &[[u8; 32]]) -> Vec<Result<Attestation, RpcFailure>> {
+        self.rpc.get_attestations(uids)
+    }
+
+    fn get_attestations_at(&self, eas: &[u8; 20], uids: &[[u8; 32]]) -> Vec<Result<Attestation, RpcFailure>> {
+        self.rpc.get_attestations_at(eas, uids)
+    }
+
+    fn get_schemas(&self, schema_ids: &[[u8; 32]]) -> Vec<Result<Schema, RpcFailure>> {
+        with_fallback(schema_ids, self.snapshot, |missing| self.get_layered_schemas(missing))
+    }
+
+    /// Schema UIDs hashing the schema record, the snapshot and the store resolve the schemas of any registry, RPC
+    /// reads go to `registry`.
+    fn get_schemas_at(&self, registry: &[u8; 20], schema_ids: &[[u8; 32]]) -> Vec<Result<Schema, RpcFailure>> {
+        with_fallback(schema_ids, self.snapshot, |missing| {
+            with_fallback(missing, self.store, |missing| self.rpc.get_schemas_at(registry, missing))
+        })
+    }
+
+    fn parsed_fields(&self, signature: &str) -> Option<Vec<(FieldType, String)>> {
+        let fields = self.parsed_schemas?.get_last(schemas::parsed_schema_key(signature))?;
+        schemas::schema_fields(&fields).ok()
+    }
+}
+
+impl<R: EasRpc, S: SchemaLookup> SchemaResolver<'_, R, S> {
+    /// Resolves schemas from the `primary` source, falling back to the other one.
+    fn get_layered_schemas(&self, schema_ids: &[[u8; 32]]) -> Vec<Result<Schema, RpcFailure>> {
+        match self.primary {
+            SchemaSource::Rpc => self
+                .rpc
+                .get_schemas(schema_ids)
+                .into_iter()
+                .zip(schema_ids)
+                .map(|(schema, schema_id)| schema.or_else(|failure| self.store.lookup(schema_id).ok_or(failure)))
+                .collect(),
+            SchemaSource::Store => with_fallback(schema_ids, self.store, |missing| self.rpc.get_schemas(missing)),
+        }
+    }
+}
+
+/// Resolves the schemas known to `lookup`, and the others, in a single call, through `fallback`. Keeps the order of
+/// `schema_ids`; missing schemas `fallback` returned no result for fail like a call returning no data.
+fn with_fallback(
+    schema_ids: &[[u8; 32]],
+    lookup: &impl SchemaLookup,
+    fallback: impl FnOnce(&[[u8; 32]]) -> Vec<Result<Schema, RpcFailure>>,
+) -> Vec<Result<Schema, RpcFailure>> {
+    let known: Vec<Option<Schema>> = schema_ids.iter().map(|schema_id| lookup.lookup(schema_id)).collect();
+    let missing: Vec<[u8; 32]> = schema_ids
+        .iter()
+        .zip(&known)
+        .filter(|(_, schema)| schema.is_none())
+        .map(|(schema_id, _)| *schema_id)
+        .collect();
+    let mut resolved = fallback(&missing).into_iter();
+    schema_ids
+        .iter()
+        .zip(known)
+        .map(|(schema_id, schema)| match schema {
+            Some(schema) => Ok(schema),
+            None => resolved.next().unwrap_or_else(|| {
+                Err(RpcFailure {
+                    call: "getSchema",
+                    contract: vec![],
+                    uid: *schema_id,
+                    return_data_len: 0,
+                    reason: "no result returned".to_string(),
+                })
+            }),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::FakeRpc;
+
+    #[test]
+    fn schemas_resolve_from_snapshot_then_rpc_and_store() {
+        let schema = |uid: u8| Schema {
+            uid_id: [uid; 32],
+            resolver: vec![0; 20],
+            revocable: true,
+            schema: format!("uint8 field{}", uid),
+        };
+        // schema 1 only known to RPC (registered before the start block), 2 only to the store, 3 to neither
+        let store = FakeRpc::default().with_schema(schema(2));
+        // schema 4 only known to the snapshot, taking precedence over the other sources for schema 1
+        let snapshot = SchemaSnapshot::from_params(&Params::parse(&format!(
+            "schema_snapshot={}:uint8 snapshotted; {}:bool flag",
+            Hex([1; 32]),
+            Hex([4; 32])
+        )))
+        .unwrap();
+
+        for primary in [SchemaSource::Rpc, SchemaSource::Store] {
+            let resolver = SchemaResolver {
+                rpc: FakeRpc::default().with_schema(schema(1)),
+                store: &store,
+                snapshot: &Default::default(),
+                primary,
+                parsed_schemas: None,
+            };
+            let resolved = resolver.get_schemas(&[[1; 32], [2; 32], [3; 32]]);
+            assert_eq!(resolved[0].as_ref().unwrap().schema, "uint8 field1");
+            assert_eq!(resolved[1].as_ref().unwrap().schema, "uint8 field2");
+            assert_eq!(resolved[2].as_ref().unwrap_err().uid, [3; 32]);
+
+            let resolver = SchemaResolver {
+                snapshot: &snapshot,
+                ..resolver
+            };
+            let resolved = resolver.get_schemas(&[[4; 32], [2; 32], [1; 32]]);
+            let schemas: Vec<_> = resolved.iter().map(|schema| schema.as_ref().unwrap().schema.as_str()).collect();
+            assert_eq!(schemas, ["bool flag", "uint8 field2", "uint8 snapshotted"]);
+        }
+        assert!("registry".parse::<SchemaSource>().is_err());
+        assert!(SchemaSnapshot::from_params(&Params::parse("schema_snapshot=uint8 score")).is_err());
+    }
+
+    #[test]
+    fn missing_fallback_results_fail_instead_of_panicking() {
+        let store = FakeRpc::default().with_schema(Schema {
+            uid_id: [2; 32],
+            resolver: vec![0; 20],
+            revocable: true,
+            schema: "uint8 field2".to_string(),
+        });
+        let resolved = with_fallback(&[[1; 32], [2; 32], [3; 32]], &store, |_| vec![]);
+
+        assert_eq!(resolved[0].as_ref().unwrap_err().uid, [1; 32]);
+        assert_eq!(resolved[1].as_ref().unwrap().schema, "uint8 field2");
+        assert_eq!(resolved[2].as_ref().unwrap_err().reason, "no result returned");
+    }
+}
@@ -0,0 +1,817 @@
+use crate::abi::eas_contract::events::{Attested, Revoked};
+use crate::abi::eas_schema_registry_contract::events::Registered;
+use crate::params::Params;
+use crate::pb::contract::v1 as contract;
+use crate::rpc::{self, EasRpc, SubstreamsRpc};
+use crate::{calls, keccak256, networks, parse_schema, schema_parser, schema_shape_id, FieldType};
+use ethabi::ParamType;
+use std::collections::{BTreeMap, BTreeSet};
+use substreams::pb::substreams::store_delta::Operation;
+use substreams::pb::substreams::Clock;
+use substreams::store::{
+    DeltaInt64, DeltaProto, Deltas, StoreAdd, StoreAddInt64, StoreAppend, StoreGet, StoreGetArray, StoreGetInt64, StoreGetProto, StoreSet, StoreSetIfNotExists,
+    StoreSetIfNotExistsProto, StoreSetProto,
+};
+use substreams::Hex;
+use substreams_ethereum::pb::eth::v2 as eth;
+use substreams_ethereum::Event;
+
+/// Key of the schema index under which every attested schema UID is appended.
+pub const SCHEMA_INDEX_KEY: &str = "schemas";
+
+/// Key of the registered schema index under which every registered schema UID is appended.
+pub const REGISTERED_SCHEMA_INDEX_KEY: &str = "registered";
+
+/// Key of the cumulative attestation counter.
+pub const ATTESTATION_TOTAL_KEY: &str = "total";
+
+/// Attestation counts at which a schema adoption event is emitted, the first attestation included.
+const ADOPTION_MILESTONES: [i64; 5] = [1, 10, 100, 1_000, 10_000];
+
+/// `(ordinal, key)` increments of the attestations of the block, one per attestation, keyed by `key`.
+fn attestation_increments(events: &contract::Events, key: impl Fn(&contract::EasAttested) -> String) -> Vec<(u64, String)> {
+    events.eas_attesteds.iter().map(|attested| (attested.evt_index as u64, key(attested))).collect()
+}
+
+/// Counts attestations per schema UID, keyed by the hex-encoded schema UID.
+#[substreams::handlers::store]
+fn store_schema_attestation_counts(events: contract::Events, store: StoreAddInt64) {
+    for (ordinal, key) in attestation_increments(&events, |attested| Hex(&attested.schema_id).to_string()) {
+        store.add(ordinal, key, 1);
+    }
+}
+
+/// Counts all attestations under a single key, for checkpoints.
+#[substreams::handlers::store]
+fn store_attestation_total(events: contract::Events, store: StoreAddInt64) {
+    for (ordinal, key) in attestation_increments(&events, |_| ATTESTATION_TOTAL_KEY.to_string()) {
+        store.add(ordinal, key, 1);
+    }
+}
+
+/// `(ordinal, schema key)` appends of the schemas created in the per-schema count store, i.e. receiving their first
+/// attestation.
+fn schema_index_appends(deltas: Deltas<DeltaInt64>) -> Vec<(u64, String)> {
+    deltas
+        .deltas
+        .into_iter()
+        .filter(|delta| delta.operation == Operation::Create)
+        .map(|delta| (delta.ordinal, delta.key))
+        .collect()
+}
+
+/// Schema index: appends the hex-encoded UID of every schema receiving its first attestation under a single key,
+/// so that modules can enumerate all attested schemas.
+#[substreams::handlers::store]
+fn store_schema_index(deltas: Deltas<DeltaInt64>, store: StoreAppend<String>) {
+    for (ordinal, schema_id) in schema_index_appends(deltas) {
+        store.append(ordinal, SCHEMA_INDEX_KEY, schema_id);
+    }
+}
+
+/// Emits an event when a schema receives its first attestation and when its attestation count
+/// reaches one of the adoption milestones.
+#[substreams::handlers::map]
+fn map_schema_adoption(events: contract::Events, deltas: Deltas<DeltaInt64>) -> Result<contract::SchemaAdoptions, substreams::errors::Error> {
+    let schema_adoptions = deltas
+        .deltas
+        .iter()
+        .filter_map(|delta| {
+            let milestone = ADOPTION_MILESTONES
+                .iter()
+                .find(|milestone| delta.old_value < **milestone && delta.new_value >= **milestone)?;
+            let attested = events.eas_attesteds.iter().find(|attested| attested.evt_index as u64 == delta.ordinal)?;
+
+            Some(contract::SchemaAdoption {
+                evt_tx_hash: attested.evt_tx_hash.clone(),
+                evt_index: attested.evt_index,
+                evt_block_time: attested.evt_block_time,
+                evt_block_timestamp_seconds: attested.evt_block_timestamp_seconds,
+                evt_block_number: attested.evt_block_number,
+                schema_id: attested.schema_id.clone(),
+                uid: attested.uid.clone(),
+                milestone: *milestone as u64,
+            })
+        })
+        .collect();
+
+    Ok(contract::SchemaAdoptions { schema_adoptions })
+}
+
+/// Emits the schemas registered in the block, with the resolver given at registration (from the `register` calldata)
+/// next to the resolver currently returned by the registry, flagging schemas whose resolver differs. The registry is
+/// selected with the `chain_family` and `chain` params, like for `map_events`.
+#[substreams::handlers::map]
+fn map_schemas(params: String, blk: eth::Block) -> Result<contract::Schemas, substreams::errors::Error> {
+    let network = networks::from_params(&Params::parse(&params))?;
+    network.check_block(blk.number)?;
+    let traces = calls::has_call_traces(&blk);
+    let registrations: Vec<_> = blk
+        .logs()
+        .filter(|log| log.address() == network.contracts.schema_registry)
+        .filter_map(|log| Registered::match_and_decode(&log).map(|event| (log.receipt.transaction, log.log, event)))
+        .collect();
+
+    let schema_ids: Vec<_> = registrations.iter().map(|(_, _, event)| event.uid).collect();
+    let schemas = registrations
+        .into_iter()
+        .zip(SubstreamsRpc { contracts: network.contracts }.get_schemas(&schema_ids))
+        .map(|((trx, log, event), schema)| {
+            let schema = schema.map_err(|failure| substreams::errors::Error::msg(format!("failed to read schema: {}", failure)))?;
+            let registered_resolver = traces.then(|| calls::registration_resolver(trx, log)).flatten();
+            let fields = parse_schema(&schema.schema);
+
+            Ok(contract::Schema {
+                evt_tx_hash: trx.hash.clone(),
+                evt_index: log.block_index,
+                evt_address: log.address.clone(),
+                evt_block_time: Some(blk.timestamp().to_owned()),
+                evt_block_timestamp_seconds: blk.timestamp_seconds(),
+                evt_block_number: blk.number,
+                uid: Vec::from(event.uid),
+                registerer: event.registerer,
+                // empty for schemas that cannot be parsed
+                schema_shape_id: fields.as_ref().map(|fields| schema_shape_id(fields).to_vec()).unwrap_or_default(),
+                canonical_schema: fields.as_ref().map(|fields| schema_parser::canonical_schema(fields)).unwrap_or_default(),
+                schema: schema.schema,
+                revocable: schema.revocable,
+                registered_resolver: registered_resolver.unwrap_or_default(),
+                current_resolver: schema.resolver,
+            })
+        })
+        .collect::<Result<_, substreams::errors::Error>>()?;
+
+    Ok(contract::Schemas { schemas })
+}
+
+/// Keeps every registered schema, keyed by the hex-encoded schema UID.
+#[substreams::handlers::store]
+fn store_schemas(schemas: contract::Schemas, store: StoreSetIfNotExistsProto<contract::Schema>) {
+    for schema in schemas.schemas {
+        store.set_if_not_exists(schema.evt_index as u64, Hex(&schema.uid).to_string(), &schema);
+    }
+}
+
+/// Store key of the parsed fields of a schema signature: the hex-encoded keccak-256 hash of the signature text.
+pub fn parsed_schema_key(signature: &str) -> String {
+    Hex(keccak256(signature.as_bytes())).to_string()
+}
+
+/// Keeps the parsed fields of every registered schema signature, keyed by [`parsed_schema_key`], so that `map_events`
+/// skips parsing the signatures registered in previous blocks. Schemas sharing a signature share the entry, signatures
+/// that cannot be parsed are left out.
+#[substreams::handlers::store]
+fn store_parsed_schemas(schemas: contract::Schemas, store: StoreSetIfNotExistsProto<contract::SchemaFields>) {
+    for schema in schemas.schemas {
+        if let Ok(fields) = parse_schema(&schema.schema) {
+            let fields = contract::SchemaFields {
+                fields: fields.iter().map(|(field_type, name)| schema_field(field_type, name)).collect(),
+            };
+            store.set_if_not_exists(schema.evt_index as u64, parsed_schema_key(&schema.schema), &fields);
+        }
+    }
+}
+
+/// Stored form of a parsed field: tuples have their components and no primitive type, array dimensions are listed
+/// innermost first, `0` for dynamic arrays.
+pub fn schema_field(field_type: &FieldType, name: &str) -> contract::SchemaField {
+    match field_type {
+        FieldType::Primitive(param_type) => contract::SchemaField {
+            name: name.to_string(),
+            primitive: param_type.to_string(),
+            ..Default::default()
+        },
+        FieldType::Tuple(fields) => contract::SchemaField {
+            name: name.to_string(),
+            components: fields.iter().map(|(field_type, name)| schema_field(field_type, name)).collect(),
+            ..Default::default()
+        },
+        FieldType::Array(inner) => {
+            let mut field = schema_field(inner, name);
+            field.array_lengths.push(0);
+            field
+        }
+        FieldType::FixedArray(inner, len) => {
+            let mut field = schema_field(inner, name);
+            field.array_lengths.push(*len as u64);
+            field
+        }
+    }
+}
+
+/// Parsed fields rebuilt from their stored form, failing on primitive types the parser does not support.
+pub fn schema_fields(fields: &contract::SchemaFields) -> Result<Vec<(FieldType, String)>, String> {
+    fields.fields.iter().map(|field| Ok((field_type(field)?, field.name.clone()))).collect()
+}
+
+fn field_type(field: &contract::SchemaField) -> Result<FieldType, String> {
+    let field_type = match field.primitive.as_str() {
+        "" => FieldType::Tuple(schema_fields(&contract::SchemaFields {
+            fields: field.components.clone(),
+        })?),
+        primitive => FieldType::Primitive(schema_parser::primitive_type(primitive)?),
+    };
+    Ok(field.array_lengths.iter().fold(field_type, |field_type, len| match len {
+        0 => FieldType::Array(Box::new(field_type)),
+        len => FieldType::FixedArray(Box::new(field_type), *len as usize),
+    }))
+}
+
+/// Sets the registration block and time of their schema on the attestations of the block, left empty for schemas
+/// whose registration was not seen.
+pub fn set_schema_registrations(events: &mut contract::Events, schemas: &StoreGetProto<contract::Schema>) {
+    for attested in events.eas_attesteds.iter_mut() {
+        if let Some(schema) = schemas.get_last(Hex(&attested.schema_id).to_string()) {
+            attested.schema_registered_block = schema.evt_block_number;
+            attested.schema_registered_time = schema.evt_block_time;
+        }
+    }
+}
+
+/// Compares the text of every newly registered schema with the one registered under the same UID on each sibling
+/// schema registry deployment listed in the `siblings` param (comma separated addresses), flagging divergences.
+#[substreams::handlers::map]
+fn map_schema_divergences(params: String, schemas: contract::Schemas) -> Result<contract::SchemaDivergences, substreams::errors::Error> {
+    let params = Params::parse(&params);
+    let siblings = params
+        .get_list("siblings")
+        .into_iter()
+        .map(|sibling| {
+            hex::decode(sibling.trim_start_matches("0x"))
+                .ok()
+                .filter(|address| address.len() == 20)
+                .ok_or_else(|| substreams::errors::Error::msg(format!("invalid sibling registry address '{}'", sibling)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let schema_ids: Vec<[u8; 32]> = schemas
+        .schemas
+        .iter()
+        .map(|schema| schema.uid.as_slice().try_into().expect("schema UID should be 32 bytes"))
+        .collect();
+
+    let mut schema_divergences = Vec::new();
+    for sibling in siblings {
+        let sibling_schemas = rpc::fetch_schemas_at(&sibling, &schema_ids);
+        for (schema, sibling_schema) in schemas.schemas.iter().zip(sibling_schemas) {
+            // unregistered UIDs resolve to an empty record
+            let sibling_schema = sibling_schema.ok().filter(|sibling_schema| sibling_schema.uid_id != [0; 32]);
+            let sibling_registered = sibling_schema.is_some();
+            let sibling_schema = sibling_schema.map(|sibling_schema| sibling_schema.schema).unwrap_or_default();
+
+            schema_divergences.push(contract::SchemaDivergence {
+                evt_tx_hash: schema.evt_tx_hash.clone(),
+                evt_block_number: schema.evt_block_number,
+                uid: schema.uid.clone(),
+                schema: schema.schema.clone(),
+                sibling_registry: sibling.clone(),
+                sibling_registered,
+                is_divergent: sibling_registered && sibling_schema != schema.schema,
+                sibling_schema,
+            });
+        }
+    }
+
+    Ok(contract::SchemaDivergences { schema_divergences })
+}
+
+/// Attestation counts of the indexed schemas, in index order, the count of each schema read through `count`.
+fn schema_attestation_counts(schema_ids: Vec<String>, count: impl Fn(&str) -> Option<i64>) -> Vec<contract::SchemaAttestationCount> {
+    schema_ids
+        .into_iter()
+        .map(|schema_id| contract::SchemaAttestationCount {
+            attestations: count(&schema_id).unwrap_or(0),
+            schema_id: hex::decode(&schema_id).unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// Every `interval` blocks (param, default `1000`), emits the cumulative number of attestations, in total and per
+/// schema, so that sinks can reconcile their row counts against the stream when restarting.
+#[substreams::handlers::map]
+fn map_attestation_checkpoints(
+    params: String,
+    clock: Clock,
+    total: StoreGetInt64,
+    schema_index: StoreGetArray<String>,
+    schema_counts: StoreGetInt64,
+) -> Result<contract::AttestationCheckpoint, substreams::errors::Error> {
+    let interval = Params::parse(&params).get_or("interval", 1000u64)?;
+    if interval == 0 || !clock.number.is_multiple_of(interval) {
+        return Ok(contract::AttestationCheckpoint::default());
+    }
+
+    let schema_ids = schema_index.get_last(SCHEMA_INDEX_KEY).unwrap_or_default();
+    let schema_attestation_counts = schema_attestation_counts(schema_ids, |schema_id| schema_counts.get_last(schema_id));
+
+    Ok(contract::AttestationCheckpoint {
+        block_number: clock.number,
+        block_timestamp_seconds: clock.timestamp.map(|timestamp| timestamp.seconds as u64).unwrap_or_default(),
+        block_time: clock.timestamp,
+        total_attestations: total.get_last(ATTESTATION_TOTAL_KEY).unwrap_or(0),
+        schema_attestation_counts,
+    })
+}
+/// Registered schema index: appends the hex-encoded UID of every newly stored schema under a single key, so that
+/// modules can enumerate all registered schemas.
+#[substreams::handlers::store]
+fn store_registered_schema_index(deltas: Deltas<DeltaProto<contract::Schema>>, store: StoreAppend<String>) {
+    for delta in deltas.deltas.into_iter().filter(|delta| delta.operation == Operation::Create) {
+        store.append(delta.ordinal, REGISTERED_SCHEMA_INDEX_KEY, delta.key);
+    }
+}
+
+/// Emits every schema registered so far, in registration order, in the blocks registering new schemas, so that a
+/// fresh sink can bootstrap its schema dictionary from the latest catalog without replaying the history.
+#[substreams::handlers::map]
+fn map_schema_catalog(
+    clock: Clock,
+    schemas: contract::Schemas,
+    schema_index: StoreGetArray<String>,
+    schema_store: StoreGetProto<contract::Schema>,
+) -> Result<contract::SchemaCatalog, substreams::errors::Error> {
+    if schemas.schemas.is_empty() {
+        return Ok(contract::SchemaCatalog::default());
+    }
+
+    Ok(contract::SchemaCatalog {
+        block_number: clock.number,
+        schemas: schema_index
+            .get_last(REGISTERED_SCHEMA_INDEX_KEY)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|schema_id| schema_store.get_last(schema_id))
+            .collect(),
+    })
+}
+/// Indexes the registered schemas by shape: appends the hex-encoded UID of every parsable schema under its
+/// hex-encoded `schema_shape_id`.
+#[substreams::handlers::store]
+fn store_schema_shapes(schemas: contract::Schemas, store: StoreAppend<String>) {
+    for schema in schemas.schemas.into_iter().filter(|schema| !schema.schema_shape_id.is_empty()) {
+        store.append(schema.evt_index as u64, Hex(&schema.schema_shape_id).to_string(), Hex(&schema.uid).to_string());
+    }
+}
+
+/// Emits, for every schema registered in the block whose canonical text was already registered under other UIDs,
+/// an alias record linking it to those UIDs, so consumers can unify logically identical schemas.
+#[substreams::handlers::map]
+fn map_schema_aliases(schemas: contract::Schemas, shapes: StoreGetArray<String>) -> Result<contract::SchemaAliases, substreams::errors::Error> {
+    let schema_aliases = schemas
+        .schemas
+        .into_iter()
+        .filter(|schema| !schema.schema_shape_id.is_empty())
+        .filter_map(|schema| {
+            let uid = Hex(&schema.uid).to_string();
+            let alias_uids: Vec<_> = shapes
+                .get_last(Hex(&schema.schema_shape_id).to_string())
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|alias_uid| *alias_uid != uid)
+                .filter_map(|alias_uid| hex::decode(alias_uid).ok())
+                .collect();
+            if alias_uids.is_empty() {
+                return None;
+            }
+
+            Some(contract::SchemaAlias {
+                evt_tx_hash: schema.evt_tx_hash,
+                evt_index: schema.evt_index,
+                evt_block_time: schema.evt_block_time,
+                evt_block_timestamp_seconds: schema.evt_block_timestamp_seconds,
+                evt_block_number: schema.evt_block_number,
+                uid: schema.uid,
+                schema_shape_id: schema.schema_shape_id,
+                canonical_schema: schema.canonical_schema,
+                alias_uids,
+            })
+        })
+        .collect();
+
+    Ok(contract::SchemaAliases { schema_aliases })
+}
+
+/// Dictionary entries of the schemas attested with their text in the block, as `(ordinal, key, entry)` writes: the
+/// handle of a schema is its 1-based position in the schema index `schema_ids`. Schemas missing from the index get no
+/// entry.
+pub fn schema_dictionary_writes(events: &contract::Events, schema_ids: Vec<String>) -> Vec<(u64, String, contract::SchemaDictionaryEntry)> {
+    let handles: BTreeMap<String, u64> = schema_ids
+        .into_iter()
+        .enumerate()
+        .map(|(position, schema_id)| (schema_id, position as u64 + 1))
+        .collect();
+
+    events
+        .eas_attesteds
+        .iter()
+        .filter(|attested| !attested.schema.is_empty())
+        .filter_map(|attested| {
+            let key = Hex(&attested.schema_id).to_string();
+            let handle = *handles.get(&key)?;
+            let entry = contract::SchemaDictionaryEntry {
+                handle,
+                schema_id: attested.schema_id.clone(),
+                schema: attested.schema.clone(),
+            };
+            Some((attested.evt_index as u64, key, entry))
+        })
+        .collect()
+}
+
+/// Schema dictionary: assigns every schema an integer handle, the 1-based position of the schema in the schema index,
+/// and keeps it with the schema text, keyed by the hex-encoded schema UID. Schemas enter the dictionary with their
+/// first attestation carrying their text.
+#[substreams::handlers::store]
+fn store_schema_dictionary(events: contract::Events, schema_index: StoreGetArray<String>, store: StoreSetIfNotExistsProto<contract::SchemaDictionaryEntry>) {
+    if events.eas_attesteds.iter().all(|attested| attested.schema.is_empty()) {
+        return;
+    }
+    let schema_ids = schema_index.get_last(SCHEMA_INDEX_KEY).unwrap_or_default();
+    for (ordinal, key, entry) in schema_dictionary_writes(&events, schema_ids) {
+        store.set_if_not_exists(ordinal, key, &entry);
+    }
+}
+
+/// Re-emits the events of `map_events` with the schema text of the attestations replaced by their handle in the
+/// schema dictionary (`schema_handle`), and emits the text of every schema once, in the block it enters the
+/// dictionary (`schema_dictionary`), so that streams dominated by a few verbose schemas do not carry them on every
+/// attestation. Attestations without schema text keep a `0` handle.
+#[substreams::handlers::map]
+fn map_schema_dictionary_events(
+    mut events: contract::Events,
+    schema_dictionary: StoreGetProto<contract::SchemaDictionaryEntry>,
+) -> Result<contract::SchemaDictionaryEvents, substreams::errors::Error> {
+    let mut entries: BTreeMap<u64, contract::SchemaDictionaryEntry> = BTreeMap::new();
+    for attested in events.eas_attesteds.iter_mut().filter(|attested| !attested.schema.is_empty()) {
+        let key = Hex(&attested.schema_id).to_string();
+        let Some(entry) = schema_dictionary.get_last(&key) else {
+            continue;
+        };
+        attested.schema_handle = entry.handle;
+        attested.schema.clear();
+        // schemas entering the dictionary in this block, the others having been emitted already
+        if schema_dictionary.get_first(&key).is_none() {
+            entries.entry(entry.handle).or_insert(entry);
+        }
+    }
+
+    Ok(contract::SchemaDictionaryEvents {
+        schema_dictionary: entries.into_values().collect(),
+        events: Some(events),
+    })
+}
+
+/// Every `interval` blocks (param, default `1000`), emits the whole schema dictionary, in handle order, so that
+/// consumers starting past the first blocks of `map_schema_dictionary_events` can rebuild it.
+#[substreams::handlers::map]
+fn map_schema_dictionary(
+    params: String,
+    clock: Clock,
+    schema_index: StoreGetArray<String>,
+    schema_dictionary: StoreGetProto<contract::SchemaDictionaryEntry>,
+) -> Result<contract::SchemaDictionary, substreams::errors::Error> {
+    let interval = Params::parse(&params).get_or("interval", 1000u64)?;
+    if interval == 0 || !clock.number.is_multiple_of(interval) {
+        return Ok(contract::SchemaDictionary::default());
+    }
+
+    // the schema index lists the schemas in handle order, those attested without text having no entry
+    let schema_dictionary = schema_index
+        .get_last(SCHEMA_INDEX_KEY)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|schema_id| schema_dictionary.get_last(schema_id))
+        .collect();
+
+    Ok(contract::SchemaDictionary {
+        block_number: clock.number,
+        schema_dictionary,
+    })
+}
+
+/// Schema text of the "Describe a Schema" attestations of the EAS conventions, without whitespace.
+const SCHEMA_DESCRIPTION_SCHEMA: &str = "bytes32schemaId,stringdescription";
+
+/// Whether a schema text is the one of the "Describe a Schema" attestations, whatever its whitespace.
+pub fn is_description_schema(schema: &str) -> bool {
+    schema.split_whitespace().collect::<String>() == SCHEMA_DESCRIPTION_SCHEMA
+}
+
+/// Returns the `(schema UID, description)` encoded in the data of a "Describe a Schema" attestation, `None` when the
+/// data does not decode.
+pub fn schema_description(data: &[u8]) -> Option<([u8; 32], String)> {
+    let mut tokens = ethabi::decode(&[ParamType::FixedBytes(32), ParamType::String], data).ok()?.into_iter();
+    let schema_id = tokens.next()?.into_fixed_bytes()?.try_into().ok()?;
+    Some((schema_id, tokens.next()?.into_string()?))
+}
+
+/// Store key under which the description set by a "Describe a Schema" attestation is also kept, to find the described
+/// schema of a revoked attestation.
+fn description_attestation_key(uid: &[u8]) -> String {
+    format!("attestation:{}", Hex(uid))
+}
+
+/// Store key marking a revoked "Describe a Schema" attestation.
+fn description_revocation_key(uid: &[u8]) -> String {
+    format!("revoked:{}", Hex(uid))
+}
+
+/// Emits the trusted "Describe a Schema" attestations and the revocations of "Describe a Schema" attestations of the
+/// block, read from the logs of the EAS contract of the network (`chain_family` and `chain` params) so that the
+/// params shaping `map_events` (redaction, filters, pseudonymization) do not hide them. Descriptions are only taken
+/// from the registerer of the described schema or from the `trusted_attesters` (param, comma separated addresses).
+#[substreams::handlers::map]
+fn map_schema_descriptions(
+    params: String,
+    blk: eth::Block,
+    schemas: StoreGetProto<contract::Schema>,
+) -> Result<contract::SchemaDescriptionChanges, substreams::errors::Error> {
+    let params = Params::parse(&params);
+    let network = networks::from_params(&params)?;
+    network.check_block(blk.number)?;
+    let trusted_attesters = crate::parse_addresses("trusted_attesters", &params.get_list("trusted_attesters"))?;
+
+    schema_description_changes(
+        &blk,
+        &network.contracts.eas,
+        &SubstreamsRpc { contracts: network.contracts },
+        |schema_id| schemas.get_last(Hex(schema_id).to_string()),
+        &trusted_attesters,
+    )
+}
+
+/// Reads the "Describe a Schema" attestations and their revocations from the logs of `eas`, recognized by the schema
+/// text of their schema record (given by `schema`, `None` for unseen schemas), and keeps the trusted descriptions.
+pub fn schema_description_changes(
+    blk: &eth::Block,
+    eas: &[u8; 20],
+    rpc: &impl EasRpc,
+    schema: impl Fn(&[u8; 32]) -> Option<contract::Schema>,
+    trusted_attesters: &[[u8; 20]],
+) -> Result<contract::SchemaDescriptionChanges, substreams::errors::Error> {
+    let describes = |schema_id: &[u8; 32]| schema(schema_id).is_some_and(|schema| is_description_schema(&schema.schema));
+    let eas_logs: Vec<_> = blk.logs().filter(|log| log.address() == *eas).collect();
+
+    let attesteds: Vec<_> = eas_logs
+        .iter()
+        .filter_map(|log| Attested::match_and_decode(log.log).map(|event| (log.log.block_index, event)))
+        .filter(|(_, event)| describes(&event.schema))
+        .collect();
+    let uids: Vec<_> = attesteds.iter().map(|(_, event)| event.uid).collect();
+    let mut schema_descriptions = Vec::new();
+    for ((evt_index, _), attestation) in attesteds.into_iter().zip(rpc.get_attestations(&uids)) {
+        let attestation = attestation.map_err(|failure| substreams::errors::Error::msg(format!("failed to read attestation: {}", failure)))?;
+        let registerer = |schema_id: &[u8; 32]| schema(schema_id).map(|schema| schema.registerer);
+        if let Some(description) = trusted_schema_description(&attestation, registerer, trusted_attesters) {
+            schema_descriptions.push(contract::SchemaDescription { evt_index, ..description });
+        }
+    }
+
+    let revocations = eas_logs
+        .iter()
+        .filter_map(|log| Revoked::match_and_decode(log.log).map(|event| (log.log.block_index, event)))
+        .filter(|(_, event)| describes(&event.schema))
+        .map(|(evt_index, event)| contract::SchemaDescriptionRevocation {
+            attestation_uid: event.uid.to_vec(),
+            evt_index,
+        })
+        .collect();
+
+    Ok(contract::SchemaDescriptionChanges {
+        schema_descriptions,
+        revocations,
+    })
+}
+
+/// Returns the description carried by a "Describe a Schema" attestation when its attester is the registerer of the
+/// described schema (given by `registerer`, `None` for unseen schemas) or one of the `trusted_attesters`.
+pub fn trusted_schema_description(
+    attestation: &crate::Attestation,
+    registerer: impl Fn(&[u8; 32]) -> Option<Vec<u8>>,
+    trusted_attesters: &[[u8; 20]],
+) -> Option<contract::SchemaDescription> {
+    let (schema_id, description) = schema_description(&attestation.data)?;
+    let trusted = registerer(&schema_id).as_deref() == Some(attestation.attester.as_slice()) || trusted_attesters.contains(&attestation.attester);
+    trusted.then(|| contract::SchemaDescription {
+        schema_id: schema_id.to_vec(),
+        attestation_uid: attestation.uid.to_vec(),
+        attester: attestation.attester.to_vec(),
+        description,
+        evt_index: 0,
+    })
+}
+
+/// Keeps the description of every schema described by a trusted "Describe a Schema" attestation, keyed by the
+/// hex-encoded UID of the described schema, and by `description_attestation_key` of the describing attestation; the
+/// latest one wins. Revoked describing attestations are marked under `description_revocation_key`, with a record
+/// holding only their UID.
+#[substreams::handlers::store]
+fn store_schema_descriptions(changes: contract::SchemaDescriptionChanges, store: StoreSetProto<contract::SchemaDescription>) {
+    for description in &changes.schema_descriptions {
+        store.set(description.evt_index as u64, Hex(&description.schema_id).to_string(), description);
+        store.set(
+            description.evt_index as u64,
+            description_attestation_key(&description.attestation_uid),
+            description,
+        );
+    }
+    for revocation in &changes.revocations {
+        let revoked = contract::SchemaDescription {
+            attestation_uid: revocation.attestation_uid.clone(),
+            ..Default::default()
+        };
+        store.set(revocation.evt_index as u64, description_revocation_key(&revocation.attestation_uid), &revoked);
+    }
+}
+
+/// Emits the schemas registered in the block, and the ones described or whose description was revoked in the block,
+/// with their current description: unset when the schema was never described, or when the attestation of its latest
+/// description is revoked. Schema records described before their registration was seen are left out.
+#[substreams::handlers::map]
+fn map_described_schemas(
+    schemas: contract::Schemas,
+    changes: contract::SchemaDescriptionChanges,
+    descriptions: StoreGetProto<contract::SchemaDescription>,
+    schema_store: StoreGetProto<contract::Schema>,
+) -> Result<contract::DescribedSchemas, substreams::errors::Error> {
+    let registered: BTreeSet<String> = schemas.schemas.iter().map(|schema| Hex(&schema.uid).to_string()).collect();
+    let described: BTreeSet<String> = changes
+        .schema_descriptions
+        .iter()
+        .map(|description| Hex(&description.schema_id).to_string())
+        .chain(
+            changes
+                .revocations
+                .iter()
+                .filter_map(|revocation| descriptions.get_last(description_attestation_key(&revocation.attestation_uid)))
+                .map(|description| Hex(&description.schema_id).to_string()),
+        )
+        .filter(|key| !registered.contains(key))
+        .collect();
+
+    let described_schemas = schemas
+        .schemas
+        .into_iter()
+        .chain(described.iter().filter_map(|key| schema_store.get_last(key)))
+        .map(|schema| {
+            let description = descriptions
+                .get_last(Hex(&schema.uid).to_string())
+                .filter(|description| descriptions.get_last(description_revocation_key(&description.attestation_uid)).is_none());
+            contract::DescribedSchema {
+                schema: Some(schema),
+                description,
+            }
+        })
+        .collect();
+
+    Ok(contract::DescribedSchemas { described_schemas })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethabi::Token;
+
+    fn attested(evt_index: u32, schema_id: u8) -> contract::EasAttested {
+        contract::EasAttested {
+            evt_index,
+            evt_block_number: 9,
+            schema_id: vec![schema_id; 32],
+            uid: vec![evt_index as u8; 32],
+            ..Default::default()
+        }
+    }
+
+    fn count_delta(operation: Operation, ordinal: u64, schema_id: u8, old_value: i64, new_value: i64) -> DeltaInt64 {
+        DeltaInt64 {
+            operation,
+            ordinal,
+            key: Hex(&[schema_id; 32]).to_string(),
+            old_value,
+            new_value,
+        }
+    }
+
+    #[test]
+    fn attestations_are_counted_per_schema_and_in_total_at_their_log_index() {
+        let events = contract::Events {
+            eas_attesteds: vec![attested(1, 0x55), attested(4, 0x66)],
+            ..Default::default()
+        };
+
+        let per_schema = attestation_increments(&events, |attested| Hex(&attested.schema_id).to_string());
+        assert_eq!(per_schema, [(1, "55".repeat(32)), (4, "66".repeat(32))]);
+        let total = attestation_increments(&events, |_| ATTESTATION_TOTAL_KEY.to_string());
+        assert_eq!(total, [(1, "total".to_string()), (4, "total".to_string())]);
+
+        let counts = |schema_id: &str| (schema_id == "55".repeat(32)).then_some(3);
+        let checkpoint: Vec<_> = schema_attestation_counts(vec!["66".repeat(32), "55".repeat(32)], counts)
+            .into_iter()
+            .map(|count| (count.schema_id[0], count.attestations))
+            .collect();
+        assert_eq!(checkpoint, [(0x66, 0), (0x55, 3)]);
+    }
+
+    #[test]
+    fn schema_adoptions_are_emitted_at_milestones_on_the_crossing_attestation() {
+        let events = contract::Events {
+            eas_attesteds: vec![attested(1, 0x55), attested(2, 0x66), attested(3, 0x55)],
+            ..Default::default()
+        };
+        let deltas = Deltas {
+            deltas: vec![
+                count_delta(Operation::Create, 1, 0x55, 0, 1),
+                count_delta(Operation::Update, 2, 0x66, 8, 9),
+                count_delta(Operation::Update, 3, 0x55, 9, 10),
+            ],
+        };
+
+        let adoptions: Vec<_> = map_schema_adoption(events, deltas)
+            .unwrap()
+            .schema_adoptions
+            .into_iter()
+            .map(|adoption| (adoption.evt_index, adoption.schema_id[0], adoption.uid[0], adoption.milestone))
+            .collect();
+        assert_eq!(adoptions, [(1, 0x55, 1, 1), (3, 0x55, 3, 10)]);
+    }
+
+    #[test]
+    fn schema_index_appends_schemas_on_their_first_attestation() {
+        let deltas = Deltas {
+            deltas: vec![
+                count_delta(Operation::Create, 2, 0x55, 0, 1),
+                count_delta(Operation::Update, 3, 0x55, 1, 2),
+                count_delta(Operation::Create, 5, 0x66, 0, 1),
+            ],
+        };
+        assert_eq!(schema_index_appends(deltas), [(2, "55".repeat(32)), (5, "66".repeat(32))]);
+    }
+
+    #[test]
+    fn schema_descriptions_are_recognized_by_schema_text() {
+        let described = [0x55; 32];
+        assert!(is_description_schema("bytes32 schemaId, string description"));
+        assert!(!is_description_schema("bytes32 schemaId,string name"));
+
+        let data = ethabi::encode(&[Token::FixedBytes(described.to_vec()), Token::String("Proof of humanity".to_string())]);
+        assert_eq!(schema_description(&data), Some((described, "Proof of humanity".to_string())));
+        assert_eq!(schema_description(&[0x01; 4]), None);
+    }
+
+    #[test]
+    fn schema_dictionary_handles_are_schema_index_positions() {
+        let attested = |evt_index: u32, schema_id: u8, schema: &str| contract::EasAttested {
+            evt_index,
+            schema_id: vec![schema_id; 32],
+            schema: schema.to_string(),
+            ..Default::default()
+        };
+        let events = contract::Events {
+            eas_attesteds: vec![
+                attested(1, 0xbb, "bool b"),
+                attested(2, 0xcc, ""),
+                attested(3, 0xaa, "bool a"),
+                attested(4, 0xdd, "bool d"),
+            ],
+            ..Default::default()
+        };
+        let schema_ids = [0xaa, 0xcc, 0xbb].iter().map(|schema_id| Hex(&[*schema_id; 32]).to_string()).collect();
+
+        let writes: Vec<_> = schema_dictionary_writes(&events, schema_ids)
+            .into_iter()
+            .map(|(ordinal, key, entry)| {
+                (
+                    ordinal,
+                    key == Hex(&entry.schema_id).to_string(),
+                    entry.schema_id[0],
+                    entry.handle,
+                    entry.schema,
+                )
+            })
+            .collect();
+        // the undecoded attestation and the schema missing from the index get no entry
+        assert_eq!(writes, [(1, true, 0xbb, 3, "bool b".to_string()), (3, true, 0xaa, 1, "bool a".to_string())]);
+    }
+
+    #[test]
+    fn schema_descriptions_are_only_trusted_from_the_registerer_or_trusted_attesters() {
+        let described = [0x55; 32];
+        let attested = |attester: u8| crate::Attestation {
+            attester: [attester; 20],
+            ..crate::testing::attestation(
+                0x77,
+                ethabi::encode(&[Token::FixedBytes(described.to_vec()), Token::String("Proof of humanity".to_string())]),
+            )
+        };
+        let registerer = |schema_id: &[u8; 32]| (*schema_id == described).then(|| vec![0x11; 20]);
+        let trusted = |attester: u8, trusted_attesters: &[[u8; 20]]| {
+            trusted_schema_description(&attested(attester), registerer, trusted_attesters).map(|description| description.attester[0])
+        };
+
+        assert_eq!(trusted(0x11, &[]), Some(0x11));
+        assert_eq!(trusted(0x22, &[]), None);
+        assert_eq!(trusted(0x22, &[[0x22; 20]]), Some(0x22));
+        let unseen = trusted_schema_description(&attested(0x11), |_| None, &[]);
+        assert_eq!(unseen, None);
+    }
+}
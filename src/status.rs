@@ -0,0 +1,309 @@
+use crate::params::Params;
+use crate::pb::contract::v1 as contract;
+use crate::references;
+use contract::AttestationState;
+use substreams::pb::substreams::store_delta::Operation;
+use substreams::pb::substreams::Clock;
+use substreams::store::{DeltaProto, Deltas, StoreAdd, StoreAddInt64, StoreGet, StoreGetArray, StoreGetInt64, StoreGetProto, StoreSet, StoreSetProto};
+use substreams::Hex;
+
+/// Status writes of the attestations and revocations of the block, as `(ordinal, uid key, status)` in ordinal order.
+fn status_writes(events: contract::Events) -> Vec<(u64, String, contract::AttestationStatus)> {
+    let attested = events.eas_attesteds.into_iter().map(|attested| {
+        (
+            attested.evt_index as u64,
+            contract::AttestationStatus {
+                uid: attested.uid,
+                schema_id: attested.schema_id,
+                attester: attested.attester,
+                recipient: attested.recipient,
+                state: AttestationState::Active as i32,
+                updated_block_number: attested.evt_block_number,
+            },
+        )
+    });
+    let revoked = events.eas_revokeds.into_iter().map(|revoked| {
+        (
+            revoked.evt_index as u64,
+            contract::AttestationStatus {
+                uid: revoked.uid,
+                schema_id: revoked.schema,
+                attester: revoked.attester,
+                recipient: revoked.recipient,
+                state: AttestationState::Revoked as i32,
+                updated_block_number: revoked.evt_block_number,
+            },
+        )
+    });
+
+    let mut writes: Vec<_> = attested
+        .chain(revoked)
+        .map(|(ordinal, status)| (ordinal, Hex(&status.uid).to_string(), status))
+        .collect();
+    // writes must follow log order so that deltas, and their undo on re-orgs, replay in order
+    writes.sort_by_key(|(ordinal, _, _)| *ordinal);
+    writes
+}
+
+/// Status writes marking superseded the attestations referenced by a later attestation of the same schema to the same
+/// recipient, as `(ordinal, uid key, status)` at the ordinal of the superseding attestation. Revoked attestations keep
+/// their `Revoked` state.
+fn superseded_writes(
+    events: &contract::Events,
+    attestation_block: impl Fn(u64, String) -> Option<contract::AttestationBlock>,
+    revocation_block: impl Fn(u64, String) -> Option<i64>,
+) -> Vec<(u64, String, contract::AttestationStatus)> {
+    references::supersessions(events, attestation_block, revocation_block)
+        .into_iter()
+        .map(|(attested, referenced)| {
+            (
+                attested.evt_index as u64,
+                Hex(&attested.ref_uid).to_string(),
+                contract::AttestationStatus {
+                    uid: attested.ref_uid.clone(),
+                    schema_id: referenced.schema_id,
+                    attester: referenced.attester,
+                    recipient: referenced.recipient,
+                    state: AttestationState::Superseded as i32,
+                    updated_block_number: attested.evt_block_number,
+                },
+            )
+        })
+        .collect()
+}
+
+/// Every write of the status store for the block: the superseded attestations and the status of the attestations and
+/// revocations, in ordinal order.
+fn attestation_status_writes(
+    events: contract::Events,
+    attestation_block: impl Fn(u64, String) -> Option<contract::AttestationBlock>,
+    revocation_block: impl Fn(u64, String) -> Option<i64>,
+) -> Vec<(u64, String, contract::AttestationStatus)> {
+    let mut writes = superseded_writes(&events, attestation_block, revocation_block);
+    writes.extend(status_writes(events));
+    // stable sort: a superseded write precedes the write of its superseding attestation at the same ordinal
+    writes.sort_by_key(|(ordinal, _, _)| *ordinal);
+    writes
+}
+
+/// Status store: keeps the current state of each attestation, keyed by the hex-encoded attestation UID.
+#[substreams::handlers::store]
+fn store_attestation_status(
+    events: contract::Events,
+    attestation_blocks: StoreGetProto<contract::AttestationBlock>,
+    revoked_attestations: StoreGetInt64,
+    store: StoreSetProto<contract::AttestationStatus>,
+) {
+    let writes = attestation_status_writes(
+        events,
+        |ordinal, key| attestation_blocks.get_at(ordinal, key),
+        |ordinal, key| revoked_attestations.get_at(ordinal, key),
+    );
+    for (ordinal, key, status) in writes {
+        store.set(ordinal, key, &status);
+    }
+}
+
+/// `(ordinal, schema key, change)` of the active counts for the status deltas entering or leaving the active state.
+fn active_count_changes(deltas: Deltas<DeltaProto<contract::AttestationStatus>>) -> Vec<(u64, String, i64)> {
+    deltas
+        .deltas
+        .into_iter()
+        .filter_map(|delta| {
+            let was_active = delta.operation != Operation::Create && delta.old_value.state == AttestationState::Active as i32;
+            let is_active = delta.operation != Operation::Delete && delta.new_value.state == AttestationState::Active as i32;
+
+            match (was_active, is_active) {
+                (false, true) => Some((delta.ordinal, Hex(&delta.new_value.schema_id).to_string(), 1)),
+                (true, false) => Some((delta.ordinal, Hex(&delta.old_value.schema_id).to_string(), -1)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Counts the active (attested and not revoked) attestations per schema, keyed by the hex-encoded schema UID.
+#[substreams::handlers::store]
+fn store_schema_active_counts(deltas: Deltas<DeltaProto<contract::AttestationStatus>>, store: StoreAddInt64) {
+    for (ordinal, key, change) in active_count_changes(deltas) {
+        store.add(ordinal, key, change);
+    }
+}
+
+/// Emits the changes of the status store in the block, in ordinal order, with the previous status (`old_status`,
+/// unset for attestations entering the store) and the new one, so that sinks maintaining validity tables apply them
+/// as is instead of reprocessing the events.
+#[substreams::handlers::map]
+fn map_status_deltas(deltas: Deltas<DeltaProto<contract::AttestationStatus>>) -> Result<contract::StatusDeltas, substreams::errors::Error> {
+    let status_deltas = deltas
+        .deltas
+        .into_iter()
+        .map(|delta| contract::StatusDelta {
+            key: delta.key,
+            ordinal: delta.ordinal,
+            old_status: (delta.operation != Operation::Create).then_some(delta.old_value),
+            new_status: (delta.operation != Operation::Delete).then_some(delta.new_value),
+        })
+        .collect();
+
+    Ok(contract::StatusDeltas { status_deltas })
+}
+
+/// Active attestation counts of the indexed schemas, in index order, the count of each schema read through `count`.
+fn schema_active_counts(schema_ids: Vec<String>, count: impl Fn(&str) -> Option<i64>) -> Vec<contract::SchemaActiveCount> {
+    schema_ids
+        .into_iter()
+        .map(|schema_id| contract::SchemaActiveCount {
+            active_attestations: count(&schema_id).unwrap_or(0),
+            schema_id: hex::decode(&schema_id).unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// Every `interval` blocks (param, default `1000`), emits the number of active attestations of every schema seen so far.
+#[substreams::handlers::map]
+fn map_validity_snapshots(
+    params: String,
+    clock: Clock,
+    schema_index: StoreGetArray<String>,
+    active_counts: StoreGetInt64,
+) -> Result<contract::ValiditySnapshot, substreams::errors::Error> {
+    let interval = Params::parse(&params).get_or("interval", 1000u64)?;
+    if interval == 0 || !clock.number.is_multiple_of(interval) {
+        return Ok(contract::ValiditySnapshot::default());
+    }
+
+    let schema_ids = schema_index.get_last(crate::schemas::SCHEMA_INDEX_KEY).unwrap_or_default();
+    let schema_active_counts = schema_active_counts(schema_ids, |schema_id| active_counts.get_last(schema_id));
+
+    Ok(contract::ValiditySnapshot {
+        block_number: clock.number,
+        block_timestamp_seconds: clock.timestamp.map(|timestamp| timestamp.seconds as u64).unwrap_or_default(),
+        block_time: clock.timestamp,
+        schema_active_counts,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_writes_follow_log_order() {
+        let events = contract::Events {
+            eas_attesteds: vec![contract::EasAttested {
+                evt_index: 5,
+                uid: vec![2; 32],
+                ..Default::default()
+            }],
+            eas_revokeds: vec![contract::EasRevoked {
+                evt_index: 3,
+                uid: vec![1; 32],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let writes = status_writes(events);
+        assert_eq!(writes.iter().map(|(ordinal, _, _)| *ordinal).collect::<Vec<_>>(), [3, 5]);
+        assert_eq!(writes[0].2.state, AttestationState::Revoked as i32);
+    }
+
+    #[test]
+    fn superseded_attestations_are_written_before_their_superseding_attestation() {
+        let attested = |evt_index: u32, uid: u8, ref_uid: Vec<u8>, recipient: u8| contract::EasAttested {
+            evt_index,
+            evt_block_number: 9,
+            uid: vec![uid; 32],
+            ref_uid,
+            schema_id: vec![0x55; 32],
+            recipient: vec![recipient; 20],
+            ..Default::default()
+        };
+        let events = contract::Events {
+            // 0x02 supersedes 0x01, 0x03 references it for another recipient
+            eas_attesteds: vec![attested(2, 0x02, vec![0x01; 32], 0x44), attested(4, 0x03, vec![0x01; 32], 0x45)],
+            ..Default::default()
+        };
+        let attestation_block = |ordinal: u64, key: String| {
+            (ordinal > 0 && key == "01".repeat(32)).then(|| contract::AttestationBlock {
+                schema_id: vec![0x55; 32],
+                recipient: vec![0x44; 20],
+                ..Default::default()
+            })
+        };
+
+        let writes: Vec<_> = attestation_status_writes(events, attestation_block, |_, _| None)
+            .into_iter()
+            .map(|(ordinal, key, status)| (ordinal, key, status.state))
+            .collect();
+        let (superseded, active) = (AttestationState::Superseded as i32, AttestationState::Active as i32);
+        assert_eq!(
+            writes,
+            [(2, "01".repeat(32), superseded), (2, "02".repeat(32), active), (4, "03".repeat(32), active),]
+        );
+    }
+
+    #[test]
+    fn revoked_attestations_are_not_superseded() {
+        let superseding = contract::EasAttested {
+            evt_index: 2,
+            evt_block_number: 9,
+            uid: vec![0x02; 32],
+            ref_uid: vec![0x01; 32],
+            schema_id: vec![0x55; 32],
+            recipient: vec![0x44; 20],
+            ..Default::default()
+        };
+        let events = contract::Events {
+            eas_attesteds: vec![superseding],
+            ..Default::default()
+        };
+        let attestation_block = |_: u64, key: String| {
+            (key == "01".repeat(32)).then(|| contract::AttestationBlock {
+                schema_id: vec![0x55; 32],
+                recipient: vec![0x44; 20],
+                ..Default::default()
+            })
+        };
+        // 0x01 was revoked in block 8, before the superseding attestation
+        let revocation_block = |_: u64, key: String| (key == "01".repeat(32)).then_some(8);
+
+        let writes: Vec<_> = attestation_status_writes(events, attestation_block, revocation_block)
+            .into_iter()
+            .map(|(ordinal, key, status)| (ordinal, key, status.state))
+            .collect();
+        assert_eq!(writes, [(2, "02".repeat(32), AttestationState::Active as i32)]);
+    }
+
+    #[test]
+    fn active_counts_follow_status_changes_and_snapshots_keep_index_order() {
+        let status = |state: AttestationState| contract::AttestationStatus {
+            schema_id: vec![0x55; 32],
+            state: state as i32,
+            ..Default::default()
+        };
+        let delta = |operation, ordinal, old_value, new_value| DeltaProto {
+            operation,
+            ordinal,
+            key: "01".repeat(32),
+            old_value,
+            new_value,
+        };
+        let deltas = Deltas {
+            deltas: vec![
+                delta(Operation::Create, 1, Default::default(), status(AttestationState::Active)),
+                delta(Operation::Update, 2, status(AttestationState::Active), status(AttestationState::Active)),
+                delta(Operation::Update, 3, status(AttestationState::Active), status(AttestationState::Revoked)),
+            ],
+        };
+        assert_eq!(active_count_changes(deltas), [(1, "55".repeat(32), 1), (3, "55".repeat(32), -1)]);
+
+        let counts = |schema_id: &str| (schema_id == "55".repeat(32)).then_some(2);
+        let snapshot: Vec<_> = schema_active_counts(vec!["66".repeat(32), "55".repeat(32)], counts)
+            .into_iter()
+            .map(|count| (count.schema_id[0], count.active_attestations))
+            .collect();
+        assert_eq!(snapshot, [(0x66, 0), (0x55, 2)]);
+    }
+}
@@ -0,0 +1,1100 @@
+//! Test harness: block fixtures and golden-file assertions for `map_events`.
+//!
+//! Recorded blocks go in `tests/fixtures/<name>.binpb` (a protobuf-encoded `sf.ethereum.type.v2.Block`) and their
+//! expected `map_events` output in `tests/golden/<name>.txt`. Run `UPDATE_GOLDEN=1 cargo test` to (re)write goldens.
+//! RPC reads are served by [`FakeRpc`]: recorded blocks are decoded from calldata, without RPC.
+use crate::networks;
+use crate::params::Params;
+use crate::pb::contract::v1 as contract;
+use crate::rpc::{EasRpc, RpcFailure};
+use crate::schema_resolver::SchemaLookup;
+use crate::{
+    decode_fields, extract_events, has_events, hmac_keccak256, keccak256, parse_schema, schemas, Attestation, EventsConfig, FieldType, LogLevel, Schema,
+};
+use ethabi::Token;
+use hex_literal::hex;
+use prost::Message;
+use ruzstd::decoding::StreamingDecoder;
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::PathBuf;
+use substreams::Hex;
+use substreams_ethereum::pb::eth::v2 as eth;
+
+const EAS: [u8; 20] = hex!("4200000000000000000000000000000000000021");
+const REVOKED_OFFCHAIN_TOPIC: [u8; 32] = hex!("92a1f7a41a7c585a8b09e25b195e225b1d43248daca46b0faf9e0792777a2229");
+const TIMESTAMPED_TOPIC: [u8; 32] = hex!("5aafceeb1c7ad58e4a84898bdee37c02c0fc46e7d24e6b60e8209449f183459f");
+const ATTESTED_TOPIC: [u8; 32] = hex!("8bf46bf4cfd674fa735a3d63ec1c9ad4153f033c290341f3a588b75685141b35");
+
+/// Params of the recorded fixtures: attestations are rebuilt from calldata, the ones that cannot be are flagged.
+const RECORDED_PARAMS: &str = "attestation_source=calldata&enrichment=lenient";
+
+/// In-memory [`EasRpc`] serving the attestations, schemas and parsed schema fields it was given, `None` for any other
+/// UID or signature.
+#[derive(Debug, Default)]
+pub struct FakeRpc {
+    pub attestations: BTreeMap<[u8; 32], Attestation>,
+    pub schemas: BTreeMap<[u8; 32], Schema>,
+    pub parsed_fields: BTreeMap<String, Vec<(FieldType, String)>>,
+}
+
+impl FakeRpc {
+    pub fn with_attestation(mut self, attestation: Attestation) -> Self {
+        self.attestations.insert(attestation.uid, attestation);
+        self
+    }
+
+    pub fn with_attestations(self, attestations: &[Attestation]) -> Self {
+        attestations.iter().cloned().fold(self, Self::with_attestation)
+    }
+
+    pub fn with_schema(mut self, schema: Schema) -> Self {
+        self.schemas.insert(schema.uid_id, schema);
+        self
+    }
+
+    pub fn with_parsed_fields(mut self, signature: &str, fields: Vec<(FieldType, String)>) -> Self {
+        self.parsed_fields.insert(signature.to_string(), fields);
+        self
+    }
+}
+
+/// Failure of a read of a UID unknown to the [`FakeRpc`], as if the call reverted.
+fn not_found(call: &'static str, uid: [u8; 32]) -> RpcFailure {
+    RpcFailure {
+        call,
+        contract: vec![],
+        uid,
+        return_data_len: 0,
+        reason: "reverted".to_string(),
+    }
+}
+
+impl SchemaLookup for FakeRpc {
+    fn lookup(&self, schema_id: &[u8; 32]) -> Option<Schema> {
+        self.schemas.get(schema_id).cloned()
+    }
+}
+
+impl EasRpc for FakeRpc {
+    fn get_attestations(&self, uids: &[[u8; 32]]) -> Vec<Result<Attestation, RpcFailure>> {
+        uids.iter()
+            .map(|uid| self.attestations.get(uid).cloned().ok_or_else(|| not_found("getAttestation", *uid)))
+            .collect()
+    }
+
+    fn get_schemas(&self, schema_ids: &[[u8; 32]]) -> Vec<Result<Schema, RpcFailure>> {
+        schema_ids
+            .iter()
+            .map(|schema_id| self.schemas.get(schema_id).cloned().ok_or_else(|| not_found("getSchema", *schema_id)))
+            .collect()
+    }
+
+    fn parsed_fields(&self, signature: &str) -> Option<Vec<(FieldType, String)>> {
+        self.parsed_fields.get(signature).cloned()
+    }
+}
+
+fn tests_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests")
+}
+
+/// Names of the recorded block fixtures found in `tests/fixtures`.
+pub fn fixture_names() -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(tests_dir().join("fixtures")) else {
+        return vec![];
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok()?.path().file_name()?.to_str()?.strip_suffix(".binpb").map(String::from))
+        .collect();
+    names.sort();
+    names
+}
+
+/// Loads the recorded block `tests/fixtures/<name>.binpb`.
+pub fn load_block(name: &str) -> eth::Block {
+    let path = tests_dir().join("fixtures").join(format!("{}.binpb", name));
+    let bytes = std::fs::read(&path).unwrap_or_else(|e| panic!("failed to read fixture {}: {}", path.display(), e));
+    eth::Block::decode(bytes.as_slice()).unwrap_or_else(|e| panic!("failed to decode fixture {}: {}", path.display(), e))
+}
+
+/// Asserts that `actual` matches `tests/golden/<name>.txt`, rewriting the golden file when `UPDATE_GOLDEN` is set.
+pub fn assert_golden(name: &str, actual: &str) {
+    let path = tests_dir().join("golden").join(format!("{}.txt", name));
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("failed to read golden {}: {} (run with UPDATE_GOLDEN=1)", path.display(), e));
+    assert_eq!(expected, actual, "output differs from golden {}", path.display());
+}
+
+/// Renders `map_events` output as one debug line per event, grouped by event type, for line-based golden diffs.
+pub fn render_events(events: &contract::Events) -> String {
+    let mut lines = Vec::new();
+    lines.extend(events.eas_attesteds.iter().map(|event| format!("{:?}", event)));
+    lines.extend(events.eas_revokeds.iter().map(|event| format!("{:?}", event)));
+    lines.extend(events.eas_revoked_offchains.iter().map(|event| format!("{:?}", event)));
+    lines.extend(events.eas_timestampeds.iter().map(|event| format!("{:?}", event)));
+    lines.push(format!("touched_keys: {:?}", events.touched_keys));
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
+
+/// Builds a block holding the given transactions, numbering their logs in block order.
+pub fn block(number: u64, seconds: i64, mut transactions: Vec<eth::TransactionTrace>) -> eth::Block {
+    let mut block_index = 0;
+    for transaction in transactions.iter_mut() {
+        for log in transaction.receipt.iter_mut().flat_map(|receipt| receipt.logs.iter_mut()) {
+            log.block_index = block_index;
+            log.ordinal = block_index as u64;
+            block_index += 1;
+        }
+    }
+
+    eth::Block {
+        number,
+        header: Some(eth::BlockHeader {
+            number,
+            timestamp: Some(prost_types::Timestamp { seconds, nanos: 0 }),
+            ..Default::default()
+        }),
+        transaction_traces: transactions,
+        ..Default::default()
+    }
+}
+
+/// Builds a successful transaction emitting the given logs.
+pub fn transaction(hash: [u8; 32], logs: Vec<eth::Log>) -> eth::TransactionTrace {
+    eth::TransactionTrace {
+        hash: hash.to_vec(),
+        status: eth::TransactionTraceStatus::Succeeded as i32,
+        receipt: Some(eth::TransactionReceipt { logs, ..Default::default() }),
+        ..Default::default()
+    }
+}
+
+/// Builds a log of the given contract.
+pub fn log(address: &[u8], topics: Vec<Vec<u8>>, data: Vec<u8>) -> eth::Log {
+    eth::Log {
+        address: address.to_vec(),
+        topics,
+        data,
+        ..Default::default()
+    }
+}
+
+/// Left-pads a value to a 32 bytes topic.
+pub fn topic(value: &[u8]) -> Vec<u8> {
+    let mut topic = vec![0u8; 32 - value.len()];
+    topic.extend_from_slice(value);
+    topic
+}
+
+pub const SCHEMA_ID: [u8; 32] = [0x55; 32];
+pub const ATTESTER: [u8; 20] = [0x11; 20];
+pub const RECIPIENT: [u8; 20] = [0x44; 20];
+pub const BLOCK_TIME: u64 = 1_700_000_000;
+
+/// Builds a revocable attestation `[uid; 32]` of [`SCHEMA_ID`] from [`ATTESTER`] to [`RECIPIENT`], made at
+/// [`BLOCK_TIME`] without expiration nor reference; other fields are set with struct update syntax.
+pub fn attestation(uid: u8, data: Vec<u8>) -> Attestation {
+    Attestation {
+        uid: [uid; 32],
+        schema: SCHEMA_ID,
+        time: BLOCK_TIME,
+        expiration_time: 0,
+        revocation_time: 0,
+        ref_uid: [0; 32],
+        recipient: RECIPIENT,
+        attester: ATTESTER,
+        revocable: true,
+        data,
+    }
+}
+
+/// Builds a schema [`SCHEMA_ID`] without resolver.
+pub fn schema(signature: &str, revocable: bool) -> Schema {
+    Schema {
+        uid_id: SCHEMA_ID,
+        resolver: vec![0; 20],
+        revocable,
+        schema: signature.to_string(),
+    }
+}
+
+/// Builds the `Attested` log of the attestation, emitted by the given contract.
+pub fn attested_log(address: &[u8], attestation: &Attestation) -> eth::Log {
+    log(
+        address,
+        vec![
+            ATTESTED_TOPIC.to_vec(),
+            topic(&attestation.recipient),
+            topic(&attestation.attester),
+            attestation.schema.to_vec(),
+        ],
+        attestation.uid.to_vec(),
+    )
+}
+
+/// Builds block 6000 at [`BLOCK_TIME`] holding one transaction emitting the `Attested` logs of the given
+/// attestations on the EAS contract.
+pub fn attestations_block(attestations: &[Attestation]) -> eth::Block {
+    let logs = attestations.iter().map(|attestation| attested_log(&EAS, attestation)).collect();
+    block(6_000, BLOCK_TIME as i64, vec![transaction([0xee; 32], logs)])
+}
+
+#[test]
+fn recorded_fixtures_match_golden_outputs() {
+    for name in fixture_names() {
+        let events = extract_events(RECORDED_PARAMS, &load_block(&name), &FakeRpc::default()).unwrap();
+        assert_golden(&name, &render_events(&events));
+    }
+}
+
+/// Decoded output of schemas and data covering the decoding rules, pinned per `DECODING_RULESET_VERSION`: a change of
+/// the output requires a bump of the version, which starts a new golden file.
+#[test]
+fn decoding_ruleset_matches_golden_output() {
+    let word = |value: u64| ethabi::encode(&[Token::Uint(value.into())]);
+    let cases = [
+        (
+            "uint256 score,bool ok,address who",
+            ethabi::encode(&[Token::Uint(7.into()), Token::Bool(true), Token::Address([0x11; 20].into())]),
+        ),
+        (
+            "string name,bytes blob",
+            ethabi::encode(&[Token::String("née".to_string()), Token::Bytes(vec![0xab, 0xcd])]),
+        ),
+        (
+            "tuple(uint64 at,(bytes32 ref,string uri)[] links) meta,int8 delta",
+            ethabi::encode(&[
+                Token::Tuple(vec![
+                    Token::Uint(1.into()),
+                    Token::Array(vec![Token::Tuple(vec![
+                        Token::FixedBytes(vec![0x22; 32]),
+                        Token::String("ipfs://x".to_string()),
+                    ])]),
+                ]),
+                Token::Int(3.into()),
+            ]),
+        ),
+        (
+            "uint8 [] votes,bytes32\t[2] refs",
+            ethabi::encode(&[
+                Token::Array(vec![Token::Uint(1.into())]),
+                Token::FixedArray(vec![Token::FixedBytes(vec![0x33; 32]); 2]),
+            ]),
+        ),
+        ("uint256 score", [word(7), word(8)].concat()),
+        ("string name", ethabi::encode(&[Token::String("a".to_string()), Token::String("b".to_string())])),
+        ("uint256 score,uint256 rank", word(7)),
+        ("uint256 score", vec![0x01; 8]),
+        ("uint256 score,,bool ok", word(7)),
+    ];
+
+    let rendered: String = cases
+        .iter()
+        .map(|(schema, data)| {
+            let decoded = parse_schema(schema).map_err(|_| "Invalid schema").and_then(|fields| {
+                let options = crate::schema_parser::RenderOptions::default();
+                let decoded = decode_fields(data, &fields, &options)?;
+                let json = serde_json::Value::Object(serde_json::Map::from_iter(decoded.fields(&fields, &options)));
+                Ok(format!(
+                    "{} extra_data=0x{} missing_fields={:?}",
+                    json,
+                    Hex(&decoded.extra_data),
+                    decoded.missing_fields
+                ))
+            });
+            format!("{} => {}\n", schema.escape_debug(), decoded.unwrap_or_else(|error| error.to_string()))
+        })
+        .collect();
+    assert_golden(&format!("decoding_ruleset_v{}", crate::DECODING_RULESET_VERSION), &rendered);
+}
+
+#[test]
+fn offchain_events_match_golden_output() {
+    let revoker = hex!("1111111111111111111111111111111111111111");
+    let uid = hex!("2222222222222222222222222222222222222222222222222222222222222222");
+    let data = hex!("3333333333333333333333333333333333333333333333333333333333333333");
+
+    let blk = block(
+        1_000,
+        1_700_000_000,
+        vec![
+            transaction(
+                [0xaa; 32],
+                vec![
+                    log(
+                        &EAS,
+                        vec![REVOKED_OFFCHAIN_TOPIC.to_vec(), topic(&revoker), uid.to_vec(), topic(&[0x65, 0x53, 0xf1, 0x00])],
+                        vec![],
+                    ),
+                    log(&EAS, vec![TIMESTAMPED_TOPIC.to_vec(), data.to_vec(), topic(&[0x65, 0x53, 0xf1, 0x00])], vec![]),
+                ],
+            ),
+            transaction(
+                [0xbb; 32],
+                vec![log(
+                    &EAS,
+                    vec![REVOKED_OFFCHAIN_TOPIC.to_vec(), topic(&revoker), uid.to_vec(), topic(&[0x65, 0x53, 0xf1, 0x01])],
+                    vec![],
+                )],
+            ),
+        ],
+    );
+
+    let events = extract_events("", &blk, &FakeRpc::default()).unwrap();
+    assert_golden("offchain_events", &render_events(&events));
+}
+
+#[test]
+fn attestations_match_golden_output() {
+    let known = attestation(0x66, ethabi::encode(&[Token::Uint(42.into()), Token::Bool(true)]));
+    let unknown = attestation(0x77, vec![]);
+    let rpc = FakeRpc::default()
+        .with_schema(schema("uint256 score,bool verified", true))
+        .with_attestation(known.clone());
+    let logs = vec![attested_log(&EAS, &known), attested_log(&EAS, &unknown)];
+    let blk = block(2_000, BLOCK_TIME as i64, vec![transaction([0xcc; 32], logs)]);
+
+    let error = extract_events("", &blk, &rpc).expect_err("strict enrichment should fail on the unknown attestation");
+    // the error names the failed read
+    assert!(error.to_string().contains(&format!("getAttestation({})", Hex(&unknown.uid))), "{}", error);
+
+    let events = extract_events("enrichment=lenient&json_keys=camel&decoded_values=true", &blk, &rpc).unwrap();
+    assert_golden("attestations", &render_events(&events));
+
+    let metrics = events.metrics.unwrap();
+    assert_eq!((metrics.attested_total, metrics.eas_logs), (2, 2));
+    // both attestations and the schema of the known one
+    assert_eq!(metrics.rpc_calls, 3);
+    assert_eq!(metrics.decoded_bytes, 64);
+}
+
+#[test]
+fn extra_contracts_are_decoded_like_eas() {
+    let fork = hex!("9999999999999999999999999999999999999999");
+    let revoked_offchain = log(
+        &fork,
+        vec![REVOKED_OFFCHAIN_TOPIC.to_vec(), topic(&[0x11; 20]), vec![0x01; 32], topic(&[0x01])],
+        vec![],
+    );
+    let blk = block(3_000, 1_700_000_000, vec![transaction([0xaa; 32], vec![revoked_offchain])]);
+
+    assert!(extract_events("", &blk, &FakeRpc::default()).unwrap().eas_revoked_offchains.is_empty());
+    let params = format!("extra_contracts=0x{}", Hex(&fork));
+    let events = extract_events(&params, &blk, &FakeRpc::default()).unwrap();
+    assert_eq!(events.eas_revoked_offchains.len(), 1);
+    assert_eq!(events.eas_revoked_offchains[0].evt_address, fork.to_vec());
+    assert!(extract_events("extra_contracts=0x99", &blk, &FakeRpc::default()).is_err());
+}
+
+/// [`EasRpc`] serving the schemas of the wrapped [`FakeRpc`] from the schema registry at the given address only.
+struct ForkRegistry([u8; 20], FakeRpc);
+
+impl EasRpc for ForkRegistry {
+    fn get_attestations(&self, uids: &[[u8; 32]]) -> Vec<Result<Attestation, RpcFailure>> {
+        self.1.get_attestations(uids)
+    }
+
+    fn get_schemas(&self, schema_ids: &[[u8; 32]]) -> Vec<Result<Schema, RpcFailure>> {
+        schema_ids.iter().map(|schema_id| Err(not_found("getSchema", *schema_id))).collect()
+    }
+
+    fn get_schemas_at(&self, registry: &[u8; 20], schema_ids: &[[u8; 32]]) -> Vec<Result<Schema, RpcFailure>> {
+        match *registry == self.0 {
+            true => self.1.get_schemas(schema_ids),
+            false => self.get_schemas(schema_ids),
+        }
+    }
+}
+
+#[test]
+fn extra_contract_schemas_are_read_from_their_registry() {
+    let (fork, registry) = (
+        hex!("9999999999999999999999999999999999999999"),
+        hex!("abababababababababababababababababababab"),
+    );
+    let verified = attestation(0x66, ethabi::encode(&[Token::Bool(true)]));
+    let rpc = ForkRegistry(
+        registry,
+        FakeRpc::default().with_schema(schema("bool verified", true)).with_attestation(verified.clone()),
+    );
+    let blk = block(3_000, BLOCK_TIME as i64, vec![transaction([0xaa; 32], vec![attested_log(&fork, &verified)])]);
+
+    let params = format!("enrichment=lenient&extra_contracts=0x{}:0x{}", Hex(&fork), Hex(&registry));
+    let events = extract_events(&params, &blk, &rpc).unwrap();
+    assert_eq!(events.eas_attesteds[0].decoded_data, r#"{"verified":true}"#);
+    // without registry, the schema is read from the registry of the network, which does not know it
+    let params = format!("enrichment=lenient&extra_contracts=0x{}", Hex(&fork));
+    assert!(extract_events(&params, &blk, &rpc).unwrap().eas_attesteds[0].enrichment_failed);
+    assert!(extract_events(&format!("extra_contracts=0x{}:0x99", Hex(&fork)), &blk, &rpc).is_err());
+}
+
+#[test]
+fn replayed_blocks_after_reorg_only_reflect_their_own_events() {
+    let revoker = hex!("1111111111111111111111111111111111111111");
+    let revoked_offchain = |uid: [u8; 32]| {
+        log(
+            &EAS,
+            vec![REVOKED_OFFCHAIN_TOPIC.to_vec(), topic(&revoker), uid.to_vec(), topic(&[0x01])],
+            vec![],
+        )
+    };
+    let original = block(3_000, 1_700_000_000, vec![transaction([0xaa; 32], vec![revoked_offchain([0x01; 32])])]);
+    let fork = block(3_000, 1_700_000_002, vec![transaction([0xbb; 32], vec![revoked_offchain([0x02; 32])])]);
+
+    let first = extract_events("final_blocks_only=false", &original, &FakeRpc::default()).unwrap();
+    let forked = extract_events("final_blocks_only=false", &fork, &FakeRpc::default()).unwrap();
+    let replayed = extract_events("final_blocks_only=false", &original, &FakeRpc::default()).unwrap();
+
+    assert_eq!(first, replayed, "map output should only depend on the block");
+    assert_eq!(forked.eas_revoked_offchains.len(), 1);
+    assert_eq!(forked.eas_revoked_offchains[0].evt_tx_hash, vec![0xbb; 32]);
+    assert!(!forked.final_blocks_only);
+    assert!(extract_events("final_blocks_only=true", &fork, &FakeRpc::default()).unwrap().final_blocks_only);
+}
+
+#[test]
+fn revocations_carry_same_call_resolver_logs() {
+    let attester = hex!("1111111111111111111111111111111111111111");
+    let resolver = hex!("8888888888888888888888888888888888888888");
+    let revoked_topic = keccak256(b"Revoked(address,address,bytes32,bytes32)");
+    let revoked = log(
+        &EAS,
+        vec![revoked_topic.to_vec(), topic(&[0x44]), topic(&attester), vec![0x55; 32]],
+        vec![0x66; 32],
+    );
+    let reason = log(&resolver, vec![vec![0x99; 32]], b"spam".to_vec());
+    let unrelated = log(&resolver, vec![vec![0x99; 32]], vec![]);
+
+    let mut blk = block(4_000, 1_700_000_000, vec![transaction([0xdd; 32], vec![revoked, reason, unrelated])]);
+    let trx = &mut blk.transaction_traces[0];
+    let logs = &trx.receipt.as_ref().unwrap().logs;
+    trx.calls = vec![
+        eth::Call {
+            index: 1,
+            depth: 0,
+            caller: attester.to_vec(),
+            address: EAS.to_vec(),
+            begin_ordinal: 0,
+            end_ordinal: 1,
+            logs: vec![logs[0].clone()],
+            ..Default::default()
+        },
+        eth::Call {
+            index: 2,
+            parent_index: 1,
+            depth: 1,
+            caller: EAS.to_vec(),
+            address: resolver.to_vec(),
+            begin_ordinal: 1,
+            end_ordinal: 1,
+            logs: vec![logs[1].clone()],
+            ..Default::default()
+        },
+    ];
+
+    let events = extract_events("resolver_context=true&hex_prefix=false", &blk, &FakeRpc::default()).unwrap();
+    assert_eq!(events.eas_revokeds.len(), 1);
+    assert_eq!(
+        events.eas_revokeds[0].resolver_context,
+        vec![contract::ResolverLog {
+            address: resolver.to_vec(),
+            topics: vec!["99".repeat(32)],
+            data: "7370616d".to_string(),
+        }]
+    );
+    let prefixed = extract_events("resolver_context=true", &blk, &FakeRpc::default()).unwrap();
+    assert_eq!(prefixed.eas_revokeds[0].resolver_context[0].data, "0x7370616d");
+    assert!(extract_events("", &blk, &FakeRpc::default()).unwrap().eas_revokeds[0]
+        .resolver_context
+        .is_empty());
+}
+
+#[test]
+fn resolver_context_leaves_out_the_eas_proxy_frame() {
+    let attester = hex!("1111111111111111111111111111111111111111");
+    let implementation = hex!("7777777777777777777777777777777777777777");
+    let resolver = hex!("8888888888888888888888888888888888888888");
+    let revoked = |uid: u8| {
+        log(
+            &EAS,
+            vec![
+                keccak256(b"Revoked(address,address,bytes32,bytes32)").to_vec(),
+                topic(&[0x44]),
+                topic(&attester),
+                vec![0x55; 32],
+            ],
+            vec![uid; 32],
+        )
+    };
+    let reason = log(&resolver, vec![vec![0x99; 32]], b"spam".to_vec());
+
+    // multiRevoke through the predeploy proxy: the implementation runs in a delegate call of the proxy, emitting the
+    // EAS logs, and calls the resolver
+    let mut blk = block(4_000, 1_700_000_000, vec![transaction([0xdd; 32], vec![revoked(0x66), revoked(0x67), reason])]);
+    let trx = &mut blk.transaction_traces[0];
+    let logs = trx.receipt.as_ref().unwrap().logs.clone();
+    trx.calls = vec![
+        eth::Call {
+            index: 1,
+            depth: 0,
+            caller: attester.to_vec(),
+            address: EAS.to_vec(),
+            begin_ordinal: 0,
+            end_ordinal: 3,
+            ..Default::default()
+        },
+        eth::Call {
+            index: 2,
+            parent_index: 1,
+            depth: 1,
+            call_type: eth::CallType::Delegate as i32,
+            caller: EAS.to_vec(),
+            address: implementation.to_vec(),
+            begin_ordinal: 0,
+            end_ordinal: 3,
+            logs: logs[..2].to_vec(),
+            ..Default::default()
+        },
+        eth::Call {
+            index: 3,
+            parent_index: 2,
+            depth: 2,
+            call_type: eth::CallType::Call as i32,
+            caller: EAS.to_vec(),
+            address: resolver.to_vec(),
+            begin_ordinal: 2,
+            end_ordinal: 2,
+            logs: vec![logs[2].clone()],
+            ..Default::default()
+        },
+    ];
+
+    let events = extract_events("resolver_context=true", &blk, &FakeRpc::default()).unwrap();
+    for revoked in &events.eas_revokeds {
+        let addresses: Vec<_> = revoked.resolver_context.iter().map(|resolver_log| resolver_log.address.as_slice()).collect();
+        assert_eq!(addresses, [resolver.as_slice()]);
+    }
+}
+
+#[test]
+fn op_stack_chains_are_tagged_with_their_chain_id() {
+    let blk = block(5_000, 1_700_000_000, vec![]);
+    let events = extract_events("chain_family=op-stack&chain=zora", &blk, &FakeRpc::default()).unwrap();
+    assert_eq!(events.chain_id, 7_777_777);
+    assert!(extract_events("chain_family=op-stack&chain=polygon", &blk, &FakeRpc::default()).is_err());
+}
+
+#[test]
+fn blocks_before_the_deployment_are_rejected() {
+    let network = networks::from_params(&Params::parse("chain=arbitrum-one")).unwrap();
+    assert_eq!(network.chain_id, 42161);
+    assert!(network.check_block(network.deployment_block - 1).is_err());
+    assert!(network.check_block(network.deployment_block).is_ok());
+    assert!(networks::from_params(&Params::parse("chain_family=op-stack&chain=polygon")).is_err());
+}
+
+#[test]
+fn manifest_initial_blocks_match_the_deployment_blocks() {
+    let manifest = std::fs::read_to_string(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("substreams.yaml")).unwrap();
+    let networks = manifest.split("\nnetworks:\n").nth(1).expect("manifest should have a networks section");
+
+    let mut chain = "";
+    let mut checked = 0;
+    for line in networks.lines() {
+        match line.strip_prefix("  ").and_then(|line| line.strip_suffix(':')) {
+            Some(name) if !name.starts_with(' ') => chain = name,
+            _ => {}
+        }
+        let Some((module, initial_block)) = line.strip_prefix("      ").and_then(|line| line.split_once(": ")) else {
+            continue;
+        };
+        if let Ok(initial_block) = initial_block.parse::<u64>() {
+            assert_eq!(crate::deployment_block(chain), Some(initial_block), "initial block of {} on {}", module, chain);
+            checked += 1;
+        }
+    }
+    assert!(checked > 0);
+    assert_eq!(crate::deployment_block("base"), Some(0));
+    assert_eq!(crate::deployment_block("unknown"), None);
+}
+
+#[test]
+fn hinted_amounts_get_a_scaled_companion() {
+    let grant = attestation(0x66, ethabi::encode(&[Token::Uint(1_500_000.into()), Token::Uint(7.into())]));
+    let rpc = FakeRpc::default()
+        .with_schema(schema("uint256 grantAmount,uint8 score", true))
+        .with_attestation(grant.clone());
+    let blk = attestations_block(&[grant]);
+
+    let params = format!("json_keys=snake&scale_decimals={}:grantAmount:6", Hex(&SCHEMA_ID));
+    let events = extract_events(&params, &blk, &rpc).unwrap();
+    assert_eq!(
+        events.eas_attesteds[0].decoded_data,
+        r#"{"grant_amount":"1500000","grant_amount_scaled":"1.5","score":"7"}"#
+    );
+    assert!(extract_events("scale_decimals=55:grantAmount", &blk, &rpc).is_err());
+}
+
+#[test]
+fn enum_fields_get_a_label_companion() {
+    let attestations = [(0x66, 1), (0x77, 7)].map(|(uid, status)| attestation(uid, ethabi::encode(&[Token::Uint(status.into()), Token::Uint(2.into())])));
+    let rpc = FakeRpc::default()
+        .with_schema(schema("uint8 status,uint8 tier", true))
+        .with_attestations(&attestations);
+    let blk = attestations_block(&attestations);
+
+    let params = format!("enum_labels={}:status=Pending|Approved|Rejected", Hex(&SCHEMA_ID));
+    let events = extract_events(&params, &blk, &rpc).unwrap();
+    assert_eq!(events.eas_attesteds[0].decoded_data, r#"{"status":"1","status_label":"Approved","tier":"2"}"#);
+    // out of range values are labelled null
+    assert_eq!(events.eas_attesteds[1].decoded_data, r#"{"status":"7","status_label":null,"tier":"2"}"#);
+    assert!(extract_events("enum_labels=55:status", &blk, &rpc).is_err());
+}
+
+#[test]
+fn redacted_fields_are_hashed_or_omitted() {
+    let contact = attestation(
+        0x66,
+        ethabi::encode(&[
+            Token::String("alice@example.com".to_string()),
+            Token::String("+1555".to_string()),
+            Token::Uint(7.into()),
+        ]),
+    );
+    let rpc = FakeRpc::default()
+        .with_schema(schema("string email,string phone,uint8 score", true))
+        .with_attestation(contact.clone());
+    let blk = attestations_block(&[contact]);
+
+    let params = format!(
+        "decoded_values=true&redaction_key=secret&redact_fields={}:email,{}:phone:omit",
+        Hex(&SCHEMA_ID),
+        Hex(&SCHEMA_ID)
+    );
+    let attested = &extract_events(&params, &blk, &rpc).unwrap().eas_attesteds[0];
+    let email_hash = format!("0x{}", Hex(hmac_keccak256(b"secret", b"alice@example.com")));
+    assert_eq!(attested.decoded_data, format!(r#"{{"email":"{}","score":"7"}}"#, email_hash));
+    assert_eq!(attested.decoded_values, format!(r#"["{}",null,"7"]"#, email_hash));
+    assert!(attested.data.is_empty(), "raw data holds the redacted values");
+    assert!(extract_events("redact_fields=55:email:mask", &blk, &rpc).is_err());
+    let unkeyed = format!("redact_fields={}:email", Hex(&SCHEMA_ID));
+    assert!(extract_events(&unkeyed, &blk, &rpc).is_err(), "hashing without a key");
+    let omitted = format!("redact_fields={}:email:omit", Hex(&SCHEMA_ID));
+    assert!(extract_events(&omitted, &blk, &rpc).is_ok());
+}
+
+#[test]
+fn revocable_attestations_of_non_revocable_schemas_conflict() {
+    let attestations = [(0x66, true), (0x77, false)].map(|(uid, revocable)| Attestation {
+        revocable,
+        ..attestation(uid, ethabi::encode(&[Token::Uint(7.into())]))
+    });
+    let rpc = FakeRpc::default().with_schema(schema("uint8 score", false)).with_attestations(&attestations);
+    let blk = attestations_block(&attestations);
+
+    let events = extract_events("", &blk, &rpc).unwrap();
+    let conflicts: Vec<_> = events.eas_attesteds.iter().map(|attested| attested.revocability_conflict).collect();
+    assert_eq!(conflicts, [true, false]);
+}
+
+#[test]
+fn attestation_records_expired_at_the_block_time_are_flagged() {
+    let attestations = [(0x66, BLOCK_TIME - 1), (0x77, BLOCK_TIME + 1), (0x88, 0)].map(|(uid, expiration_time)| Attestation {
+        expiration_time,
+        ..attestation(uid, vec![])
+    });
+    let rpc = FakeRpc::default().with_attestations(&attestations);
+    let blk = attestations_block(&attestations);
+
+    let events = extract_events("enrichment=lenient", &blk, &rpc).unwrap();
+    let expired: Vec<_> = events.eas_attesteds.iter().map(|attested| attested.is_expired_at_emission).collect();
+    assert_eq!(expired, [true, false, false]);
+}
+
+#[test]
+fn raw_attestations_are_exported_on_demand() {
+    let referencing = Attestation {
+        expiration_time: 1_800_000_000,
+        ref_uid: [0x77; 32],
+        ..attestation(0x66, vec![0xab, 0xcd])
+    };
+    let rpc = FakeRpc::default().with_attestation(referencing.clone());
+    let blk = attestations_block(&[referencing]);
+
+    let events = extract_events("enrichment=lenient", &blk, &rpc).unwrap();
+    assert_eq!(events.eas_attesteds[0].raw_attestation, None);
+
+    let events = extract_events("enrichment=lenient&raw_attestations=true", &blk, &rpc).unwrap();
+    let raw_attestation = events.eas_attesteds[0].raw_attestation.clone().unwrap();
+    assert_eq!(raw_attestation.uid, format!("0x{}", Hex(&[0x66; 32])));
+    assert_eq!(raw_attestation.ref_uid, format!("0x{}", Hex(&[0x77; 32])));
+    assert_eq!(raw_attestation.attester, format!("0x{}", Hex(&ATTESTER)));
+    assert_eq!((raw_attestation.time, raw_attestation.expiration_time), (BLOCK_TIME, 1_800_000_000));
+    assert_eq!(raw_attestation.data, "0xabcd");
+}
+
+#[test]
+fn strict_mode_fails_on_decode_anomalies() {
+    let value = ethabi::encode(&[Token::Uint(42.into())]);
+    let rpc = FakeRpc::default()
+        .with_schema(schema("uint256 value", true))
+        .with_attestation(attestation(0x66, value.clone()))
+        .with_attestation(attestation(0x77, vec![0x01]))
+        .with_attestation(Attestation {
+            attester: [0x22; 20],
+            ..attestation(0x88, value)
+        });
+    // the logs are emitted by [`ATTESTER`] whatever the record says
+    let blk = |uid: u8| attestations_block(&[attestation(uid, vec![])]);
+
+    assert!(extract_events("strict=true", &blk(0x66), &rpc).is_ok());
+    // undecodable data and a record of another attester are emitted flagged or as is without strict mode
+    assert!(extract_events("", &blk(0x77), &rpc).is_ok());
+    let error = extract_events("strict=true", &blk(0x77), &rpc).unwrap_err();
+    assert!(error.to_string().contains("data does not match its schema"), "{}", error);
+    let error = extract_events("strict=true", &blk(0x88), &rpc).unwrap_err();
+    assert!(error.to_string().contains("does not match the event"), "{}", error);
+    let error = extract_events("strict=true&enrichment=lenient", &blk(0x99), &rpc).unwrap_err();
+    assert!(error.to_string().contains("not resolved"), "{}", error);
+}
+
+#[test]
+fn tx_ordinals_only_count_eas_events() {
+    let upgraded = log(&EAS, vec![keccak256(b"Upgraded(address)").to_vec(), topic(&[0x99; 20])], vec![]);
+    let logs = vec![
+        attested_log(&EAS, &attestation(0x66, vec![])),
+        upgraded,
+        attested_log(&EAS, &attestation(0x77, vec![])),
+    ];
+    let other = transaction([0xef; 32], vec![attested_log(&EAS, &attestation(0x88, vec![]))]);
+    let blk = block(6_000, BLOCK_TIME as i64, vec![transaction([0xee; 32], logs), other]);
+
+    let events = extract_events("enrichment=lenient", &blk, &FakeRpc::default()).unwrap();
+    let ordinals: Vec<_> = events
+        .eas_attesteds
+        .iter()
+        .map(|attested| (attested.evt_index, attested.evt_tx_ordinal))
+        .collect();
+    assert_eq!(ordinals, [(0, 0), (2, 1), (3, 0)]);
+}
+
+#[test]
+fn known_attesters_are_labelled() {
+    let coinbase = ATTESTER;
+    let revoked = log(
+        &EAS,
+        vec![
+            keccak256(b"Revoked(address,address,bytes32,bytes32)").to_vec(),
+            topic(&RECIPIENT),
+            topic(&coinbase),
+            SCHEMA_ID.to_vec(),
+        ],
+        vec![0x66; 32],
+    );
+    let other = Attestation {
+        attester: [0x22; 20],
+        ..attestation(0x77, vec![])
+    };
+    let logs = vec![attested_log(&EAS, &attestation(0x66, vec![])), attested_log(&EAS, &other), revoked];
+    let blk = block(6_000, BLOCK_TIME as i64, vec![transaction([0xee; 32], logs)]);
+
+    let params = format!("enrichment=lenient&attester_labels={}:Coinbase", Hex(&coinbase));
+    let events = extract_events(&params, &blk, &FakeRpc::default()).unwrap();
+    let labels: Vec<_> = events.eas_attesteds.iter().map(|attested| attested.attester_label.as_str()).collect();
+    assert_eq!(labels, ["Coinbase", ""]);
+    assert_eq!(events.eas_revokeds[0].attester_label, "Coinbase");
+    assert!(extract_events("attester_labels=0x11:Coinbase", &blk, &FakeRpc::default()).is_err());
+}
+
+#[test]
+fn parsed_schemas_are_reused_across_blocks() {
+    // the stored form rebuilds nested tuples and arrays as parsed
+    let nested = parse_schema("(address who,uint8[2] scores)[3][] votes,bytes32 tag").unwrap();
+    let stored = contract::SchemaFields {
+        fields: nested.iter().map(|(field_type, name)| schemas::schema_field(field_type, name)).collect(),
+    };
+    let rebuilt = schemas::schema_fields(&stored).unwrap();
+    assert_eq!(format!("{:?}", rebuilt), format!("{:?}", nested));
+    assert_eq!(stored.fields[0].array_lengths, [3, 0]);
+
+    let signature = "uint256 score,bool verified";
+    let scored = attestation(0x66, ethabi::encode(&[Token::Uint(42.into()), Token::Bool(true)]));
+    let rpc = || FakeRpc::default().with_schema(schema(signature, true)).with_attestation(scored.clone());
+    let blk = attestations_block(std::slice::from_ref(&scored));
+
+    let parsed = extract_events("", &blk, &rpc()).unwrap();
+    let cached = rpc().with_parsed_fields(signature, parse_schema(signature).unwrap());
+    let cached = extract_events("", &blk, &cached).unwrap();
+    assert_eq!(cached.eas_attesteds[0].decoded_data, parsed.eas_attesteds[0].decoded_data);
+    let metrics = |events: &contract::Events| events.metrics.as_ref().map(|metrics| (metrics.schemas_parsed, metrics.schema_cache_hits));
+    assert_eq!(metrics(&parsed), Some((1, 0)));
+    assert_eq!(metrics(&cached), Some((0, 1)));
+}
+
+#[test]
+fn decoded_data_is_compressed_on_demand() {
+    let note = attestation(0x66, ethabi::encode(&[Token::String("archived ".repeat(20))]));
+    let rpc = FakeRpc::default().with_schema(schema("string note", true)).with_attestation(note.clone());
+    let blk = attestations_block(&[note]);
+
+    let plain = extract_events("", &blk, &rpc).unwrap();
+    let compressed = extract_events("decoded_data_compression=zstd", &blk, &rpc).unwrap();
+    let (plain, compressed) = (&plain.eas_attesteds[0], &compressed.eas_attesteds[0]);
+    assert!(plain.decoded_data_compressed.is_empty());
+    assert!(compressed.decoded_data.is_empty());
+
+    let mut decoded_data = String::new();
+    StreamingDecoder::new(compressed.decoded_data_compressed.as_slice())
+        .unwrap()
+        .read_to_string(&mut decoded_data)
+        .unwrap();
+    assert_eq!(decoded_data, plain.decoded_data);
+    assert!(extract_events("decoded_data_compression=gzip", &blk, &rpc).is_err());
+}
+
+#[test]
+fn rejecting_resolver_calls_of_successful_attestations_are_flagged() {
+    let resolver = hex!("8888888888888888888888888888888888888888");
+    let attested = attested_log(&EAS, &attestation(0x66, vec![]));
+    let hook = keccak256(b"attest((bytes32,bytes32,uint64,uint64,uint64,bytes32,address,address,bool,bytes))");
+
+    let mut blk = block(7_000, BLOCK_TIME as i64, vec![transaction([0xdd; 32], vec![attested])]);
+    let trx = &mut blk.transaction_traces[0];
+    let eas_call = eth::Call {
+        index: 1,
+        depth: 0,
+        caller: ATTESTER.to_vec(),
+        address: EAS.to_vec(),
+        begin_ordinal: 0,
+        end_ordinal: 2,
+        logs: trx.receipt.as_ref().unwrap().logs.clone(),
+        ..Default::default()
+    };
+    let hook_call = |status_failed: bool, accepted: bool| eth::Call {
+        index: 2,
+        parent_index: 1,
+        depth: 1,
+        caller: EAS.to_vec(),
+        address: resolver.to_vec(),
+        input: hook[..4].to_vec(),
+        begin_ordinal: 1,
+        end_ordinal: 1,
+        status_failed,
+        return_data: if status_failed { vec![] } else { ethabi::encode(&[Token::Bool(accepted)]) },
+        ..Default::default()
+    };
+
+    let flagged = |blk: &eth::Block| extract_events("enrichment=lenient", blk, &FakeRpc::default()).unwrap().eas_attesteds[0].resolver_call_rejected;
+    trx.calls = vec![eas_call.clone(), hook_call(true, false)];
+    assert!(flagged(&blk), "reverted hook call");
+    blk.transaction_traces[0].calls = vec![eas_call.clone(), hook_call(false, false)];
+    assert!(flagged(&blk), "hook call returning false");
+    blk.transaction_traces[0].calls = vec![eas_call, hook_call(false, true)];
+    assert!(!flagged(&blk));
+}
+
+#[test]
+fn single_attestations_decode_to_json() {
+    let data = ethabi::encode(&[Token::Uint(42.into()), Token::Bool(true)]);
+    assert_eq!(
+        crate::decode_attestation_json(&format!("0x{}", Hex(&data)), "uint256 score,bool verified").unwrap(),
+        r#"{"score":"42","verified":true}"#
+    );
+    assert_eq!(
+        crate::decode_attestation_json("0xzz", "uint256 score").unwrap_err(),
+        "Invalid data hex: Invalid character 'z' at position 0"
+    );
+    assert_eq!(
+        crate::decode_attestation_json(&Hex(&data).to_string(), "uint256 score,(bool").unwrap_err(),
+        "Invalid schema"
+    );
+}
+
+#[test]
+fn trailing_data_is_kept_as_extra_data() {
+    let fields = parse_schema("uint8 score,string comment").unwrap();
+    let declared = ethabi::encode(&[Token::Uint(7.into()), Token::String("ok".to_string())]);
+    let data = [declared.clone(), vec![0x09; 32]].concat();
+
+    let decoded = decode_fields(&data, &fields, &Default::default()).unwrap();
+    assert_eq!(decoded.tokens.len(), 2);
+    assert_eq!(decoded.extra_data, vec![0x09; 32]);
+    assert!(decode_fields(&declared, &fields, &Default::default()).unwrap().extra_data.is_empty());
+
+    // a field appended by a later version of the schema puts its head before the `comment` tail
+    let appended = ethabi::encode(&[Token::Uint(7.into()), Token::String("ok".to_string()), Token::Uint(9.into())]);
+    let decoded = decode_fields(&appended, &fields, &Default::default()).unwrap();
+    assert_eq!(decoded.extra_data, ethabi::encode(&[Token::Uint(9.into())]));
+    let appended = ethabi::encode(&[Token::Uint(7.into()), Token::String("ok".to_string()), Token::String("v2".to_string())]);
+    let decoded = decode_fields(&appended, &fields, &Default::default()).unwrap();
+    assert_eq!(decoded.extra_data, [&appended[64..96], &appended[160..]].concat());
+}
+
+#[test]
+fn truncated_data_keeps_its_leading_fields() {
+    let fields = parse_schema("uint8 score,string comment,bool verified").unwrap();
+    let data = ethabi::encode(&[Token::Uint(7.into()), Token::String("ok".to_string()), Token::Bool(true)]);
+
+    // drops the `comment` tail: only the heads of `score` and `comment` remain
+    let decoded = decode_fields(&data[..64], &fields, &Default::default()).unwrap();
+    assert_eq!(decoded.missing_fields, ["comment", "verified"]);
+    let decoded_fields = decoded.fields(&fields, &Default::default());
+    assert_eq!(decoded_fields[0], ("score".to_string(), serde_json::json!("7")));
+    assert_eq!(decoded_fields[2], ("verified".to_string(), serde_json::Value::Null));
+    assert!(decode_fields(&data[..16], &fields, &Default::default()).is_err());
+}
+
+#[test]
+fn time_window_bounds_are_inclusive() {
+    let config = EventsConfig::from_params("from_time=1000&to_time=2000").unwrap();
+    assert!(!config.in_time_window(999));
+    assert!(config.in_time_window(1000));
+    assert!(config.in_time_window(2000));
+    assert!(!config.in_time_window(2001));
+
+    assert!(EventsConfig::from_params("").unwrap().in_time_window(0));
+    assert!(EventsConfig::from_params("from_time=yesterday").is_err());
+}
+
+#[test]
+fn log_level_defaults_to_info() {
+    assert_eq!(EventsConfig::from_params("").unwrap().log_level, LogLevel::Info);
+    assert_eq!(EventsConfig::from_params("log_level=off").unwrap().log_level, LogLevel::Off);
+    assert_eq!(EventsConfig::from_params("log_level=off&debug=true").unwrap().log_level, LogLevel::Debug);
+    assert!(EventsConfig::from_params("log_level=trace").is_err());
+}
+
+#[test]
+fn empty_blocks_are_only_emitted_on_demand() {
+    assert!(!EventsConfig::from_params("").unwrap().emit_empty_blocks);
+    assert!(EventsConfig::from_params("emit_empty_blocks=true").unwrap().emit_empty_blocks);
+    let unrelated = log(&[0x12; 20], vec![ATTESTED_TOPIC.to_vec()], vec![]);
+    let blk = block(3_000, 1_700_000_000, vec![transaction([0xaa; 32], vec![unrelated])]);
+    let events = extract_events("", &blk, &FakeRpc::default()).unwrap();
+    assert!(!has_events(&events));
+    assert_eq!(events.metrics.map(|metrics| metrics.block_number), Some(3_000));
+}
+
+#[test]
+fn sampling_keeps_one_in_n_uids() {
+    let config = EventsConfig::from_params("sample_rate=4").unwrap();
+    let kept = (0u64..400).filter(|n| config.sampled(&keccak256(&n.to_be_bytes()))).count();
+    assert!((60..140).contains(&kept), "kept {} of 400", kept);
+    assert!(EventsConfig::from_params("").unwrap().sampled(&[0xff; 32]));
+}
+
+#[test]
+fn pseudonyms_are_keyed_and_consistent() {
+    // RFC 2104 HMAC over keccak256 (136 bytes block), checked against the RustCrypto `hmac` and `sha3` crates
+    assert_eq!(
+        hmac_keccak256(b"Jefe", b"what do ya want for nothing?"),
+        hex!("aa9aed448c7abc8b5e326ffa6a01cdedf7b4b831881468c044ba8dd4566369a1")
+    );
+    // keys longer than a block are hashed first
+    assert_eq!(
+        hmac_keccak256(&[0xaa; 150], b"Test Using Larger Than Block-Size Key - Hash Key First"),
+        hex!("facc938fc42e0e8332beb4b7545e3a0169fd532ff6952ddd2d9468cbc190a6b7")
+    );
+
+    let config = EventsConfig::from_params("pseudonym_key=secret").unwrap();
+    let (mut first, mut second) = (vec![0x11; 20], vec![0x11; 20]);
+    config.pseudonymize(&mut first);
+    config.pseudonymize(&mut second);
+    assert_eq!(first.len(), 20);
+    assert_eq!(first, second);
+    assert_ne!(first, vec![0x11; 20]);
+
+    let mut events = contract::Events {
+        eas_attesteds: vec![contract::EasAttested {
+            evt_tx_hash: vec![0xee; 32],
+            uid: vec![0x66; 32],
+            ..Default::default()
+        }],
+        eas_revokeds: vec![contract::EasRevoked {
+            uid: vec![0x66; 32],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    crate::pseudonymize_addresses(&config, &mut events);
+    let attested = &events.eas_attesteds[0];
+    assert_ne!(attested.evt_tx_hash, vec![0xee; 32]);
+    assert_ne!(attested.uid, vec![0x66; 32]);
+    assert!(attested.ref_uid.is_empty(), "no reference");
+    assert_eq!(events.eas_revokeds[0].uid, attested.uid, "revocations keep matching their attestation");
+}
+
+#[test]
+fn schema_descriptions_are_read_from_the_eas_logs() {
+    let description_schema = [0x66; 32];
+    let describe = |uid: u8, attester: [u8; 20], description: &str| Attestation {
+        schema: description_schema,
+        attester,
+        ..attestation(
+            uid,
+            ethabi::encode(&[Token::FixedBytes(SCHEMA_ID.to_vec()), Token::String(description.to_string())]),
+        )
+    };
+    let (trusted, untrusted, other) = (
+        describe(0x01, ATTESTER, "Proof of humanity"),
+        describe(0x02, [0x22; 20], "Spam"),
+        attestation(0x03, vec![]),
+    );
+    let revoked = log(
+        &EAS,
+        vec![
+            keccak256(b"Revoked(address,address,bytes32,bytes32)").to_vec(),
+            topic(&RECIPIENT),
+            topic(&ATTESTER),
+            description_schema.to_vec(),
+        ],
+        vec![0x09; 32],
+    );
+    let logs = vec![
+        attested_log(&EAS, &trusted),
+        attested_log(&EAS, &untrusted),
+        attested_log(&EAS, &other),
+        revoked,
+    ];
+    let blk = block(6_000, BLOCK_TIME as i64, vec![transaction([0xee; 32], logs)]);
+    let rpc = FakeRpc::default().with_attestations(&[trusted, untrusted, other]);
+    let schema = |schema_id: &[u8; 32]| match *schema_id {
+        SCHEMA_ID => Some(contract::Schema {
+            registerer: ATTESTER.to_vec(),
+            schema: "bool verified".to_string(),
+            ..Default::default()
+        }),
+        id if id == description_schema => Some(contract::Schema {
+            schema: "bytes32 schemaId, string description".to_string(),
+            ..Default::default()
+        }),
+        _ => None,
+    };
+
+    let changes = schemas::schema_description_changes(&blk, &EAS, &rpc, schema, &[]).unwrap();
+    let descriptions: Vec<_> = changes
+        .schema_descriptions
+        .iter()
+        .map(|description| (description.evt_index, description.attestation_uid[0], description.description.as_str()))
+        .collect();
+    assert_eq!(
+        descriptions,
+        [(0, 0x01, "Proof of humanity")],
+        "the registerer is trusted, other attesters are not"
+    );
+    assert_eq!(
+        changes.revocations,
+        [contract::SchemaDescriptionRevocation {
+            attestation_uid: vec![0x09; 32],
+            evt_index: 3
+        }]
+    );
+
+    let curated = schemas::schema_description_changes(&blk, &EAS, &rpc, schema, &[[0x22; 20]]).unwrap();
+    assert_eq!(curated.schema_descriptions.len(), 2);
+}
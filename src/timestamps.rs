@@ -0,0 +1,42 @@
+use crate::pb::contract::v1 as contract;
+use substreams::store::{StoreSetIfNotExists, StoreSetIfNotExistsProto};
+use substreams::Hex;
+
+/// `(ordinal, key, event)` writes of the `Timestamped` events of the block, in log order.
+fn timestamp_writes(events: contract::Events) -> Vec<(u64, String, contract::EasTimestamped)> {
+    events
+        .eas_timestampeds
+        .into_iter()
+        .map(|timestamped| (timestamped.evt_index as u64, Hex(&timestamped.data).to_string(), timestamped))
+        .collect()
+}
+
+/// Keeps the first `Timestamped` event of each 32-byte data hash, keyed by the hex-encoded hash,
+/// so that "when was this hash first timestamped?" can be answered from the store.
+#[substreams::handlers::store]
+fn store_timestamps(events: contract::Events, store: StoreSetIfNotExistsProto<contract::EasTimestamped>) {
+    for (ordinal, key, timestamped) in timestamp_writes(events) {
+        store.set_if_not_exists(ordinal, key, &timestamped);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timestamps_are_keyed_by_data_hash_at_their_log_index() {
+        let timestamped = |evt_index: u32, data: u8| contract::EasTimestamped {
+            evt_index,
+            data: vec![data; 32],
+            ..Default::default()
+        };
+        let events = contract::Events {
+            eas_timestampeds: vec![timestamped(2, 0xaa), timestamped(4, 0xbb)],
+            ..Default::default()
+        };
+
+        let writes: Vec<_> = timestamp_writes(events).into_iter().map(|(ordinal, key, _)| (ordinal, key)).collect();
+        assert_eq!(writes, [(2, "aa".repeat(32)), (4, "bb".repeat(32))]);
+    }
+}
@@ -0,0 +1,110 @@
+use crate::params::Params;
+use crate::pb::contract::v1 as contract;
+use crate::schema_parser::RenderOptions;
+use crate::{json_friendly, networks};
+use prost_types::Timestamp;
+use serde_json::{json, Value};
+
+/// Identifier of an event, stable across reprocessing so webhook consumers can deduplicate deliveries:
+/// `<tx_hash>-<log_index>`.
+fn event_id(options: &RenderOptions, evt_tx_hash: &[u8], evt_index: u32) -> String {
+    format!("{}-{}", options.hex(evt_tx_hash), evt_index)
+}
+
+/// Decoded data as embedded JSON, `null` when the attestation was not decoded.
+fn decoded_data(decoded_data: &str) -> Value {
+    serde_json::from_str(decoded_data).unwrap_or(Value::Null)
+}
+
+fn envelope(event_type: &str, chain: &str, id: String, occurred_at: Option<Timestamp>, payload: Value) -> contract::WebhookEvent {
+    contract::WebhookEvent {
+        event_type: event_type.to_string(),
+        chain: chain.to_string(),
+        id,
+        occurred_at,
+        payload: payload.to_string(),
+    }
+}
+
+/// Wraps every event of `map_events` in a webhook envelope: event type, chain, unique ID, block time and the event
+/// itself as a JSON payload, with bytes fields as hex strings. The chain is the `chain` param, or the chain ID of the
+/// events when unnamed.
+#[substreams::handlers::map]
+fn map_webhook_events(params: String, events: contract::Events) -> Result<contract::WebhookEvents, substreams::errors::Error> {
+    let params = Params::parse(&params);
+    let options = RenderOptions {
+        hex_prefix: params.get_or("hex_prefix", true)?,
+        ..Default::default()
+    };
+    let chain = match networks::from_params(&params)?.name {
+        "" => events.chain_id.to_string(),
+        name => name.to_string(),
+    };
+    let hex = |bytes: &[u8]| json_friendly::hex(&options, bytes);
+
+    let attesteds = events.eas_attesteds.iter().map(|attested| {
+        let payload = json!({
+            "uid": hex(&attested.uid),
+            "schema_id": hex(&attested.schema_id),
+            "attester": hex(&attested.attester),
+            "recipient": hex(&attested.recipient),
+            "ref_uid": hex(&attested.ref_uid),
+            "data": hex(&attested.data),
+            "schema": attested.schema,
+            "decoded_data": decoded_data(&attested.decoded_data),
+            "tx_hash": hex(&attested.evt_tx_hash),
+            "block_number": attested.evt_block_number,
+            "log_index": attested.evt_index,
+        });
+        let id = event_id(&options, &attested.evt_tx_hash, attested.evt_index);
+        (attested.evt_index, envelope("attested", &chain, id, attested.evt_block_time, payload))
+    });
+    let revokeds = events.eas_revokeds.iter().map(|revoked| {
+        let payload = json!({
+            "uid": hex(&revoked.uid),
+            "schema_id": hex(&revoked.schema),
+            "attester": hex(&revoked.attester),
+            "recipient": hex(&revoked.recipient),
+            "delegation_signer": hex(&revoked.delegation_signer),
+            "tx_hash": hex(&revoked.evt_tx_hash),
+            "block_number": revoked.evt_block_number,
+            "log_index": revoked.evt_index,
+        });
+        let id = event_id(&options, &revoked.evt_tx_hash, revoked.evt_index);
+        (revoked.evt_index, envelope("revoked", &chain, id, revoked.evt_block_time, payload))
+    });
+    let revoked_offchains = events.eas_revoked_offchains.iter().map(|revocation| {
+        let payload = json!({
+            "revoker": hex(&revocation.revoker),
+            "data": hex(&revocation.data),
+            "timestamp": revocation.timestamp,
+            "tx_hash": hex(&revocation.evt_tx_hash),
+            "block_number": revocation.evt_block_number,
+            "log_index": revocation.evt_index,
+        });
+        let id = event_id(&options, &revocation.evt_tx_hash, revocation.evt_index);
+        (
+            revocation.evt_index,
+            envelope("revoked_offchain", &chain, id, revocation.evt_block_time, payload),
+        )
+    });
+    let timestampeds = events.eas_timestampeds.iter().map(|timestamped| {
+        let payload = json!({
+            "data": hex(&timestamped.data),
+            "timestamp": timestamped.timestamp,
+            "tx_hash": hex(&timestamped.evt_tx_hash),
+            "block_number": timestamped.evt_block_number,
+            "log_index": timestamped.evt_index,
+        });
+        let id = event_id(&options, &timestamped.evt_tx_hash, timestamped.evt_index);
+        (timestamped.evt_index, envelope("timestamped", &chain, id, timestamped.evt_block_time, payload))
+    });
+
+    // in log order, as emitted on chain
+    let mut webhook_events: Vec<_> = attesteds.chain(revokeds).chain(revoked_offchains).chain(timestampeds).collect();
+    webhook_events.sort_by_key(|(evt_index, _)| *evt_index);
+
+    Ok(contract::WebhookEvents {
+        webhook_events: webhook_events.into_iter().map(|(_, event)| event).collect(),
+    })
+}